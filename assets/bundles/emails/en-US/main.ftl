@@ -0,0 +1,2 @@
+welcome = Welcome aboard, { $name }!
+hello = Hello from the mailroom
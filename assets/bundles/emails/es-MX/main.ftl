@@ -0,0 +1,2 @@
+welcome = ¡Bienvenido a bordo, { $name }!
+hello = Hola desde el correo
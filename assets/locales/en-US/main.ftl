@@ -0,0 +1,11 @@
+hello = Hello
+greeting = Hello, { $name }!
+welcome_message = Good { $time }, { $user }!
+user_info = { $user } signed in this { $time }
+farewell = Goodbye
+emails = { $count ->
+    [one] You have one email
+   *[other] You have { $count } emails
+}
+login-button = Log in
+    .title = Click to log in
@@ -0,0 +1,10 @@
+hello = Hola
+greeting = ¡Hola, { $name }!
+welcome_message = ¡Buenos días, { $user }!
+user_info = { $user } inició sesión esta { $time }
+emails = { $count ->
+    [one] Tienes un correo
+   *[other] Tienes { $count } correos
+}
+login-button = Iniciar sesión
+    .title = Haz clic para iniciar sesión
@@ -0,0 +1 @@
+hello = Welcome to Acme
@@ -0,0 +1,520 @@
+use fluent_syntax::ast::Entry;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Data, DeriveInput, Fields, FieldsNamed, Ident, LitStr, Token, Type, parse_macro_input};
+
+// Shared with `i18n`, so JSON and YAML keys are read the same way
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[path = "../../src/keys.rs"]
+mod keys;
+
+const DEFAULT_LANG: &str = "en-US";
+const DEFAULT_DIR: &str = "./assets/locales/";
+
+/// Extensions of the translation files `i18n` reads from a locale directory
+const EXTENSIONS: &[&str] = &[
+    "ftl",
+    #[cfg(feature = "json")]
+    "json",
+    #[cfg(feature = "yaml")]
+    "yaml",
+    #[cfg(feature = "yaml")]
+    "yml",
+];
+
+
+/// Input of `t_static!`: a key literal followed by optional `name = value` arguments
+struct StaticKey {
+    key: LitStr,
+    rest: TokenStream2,
+}
+
+impl Parse for StaticKey {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        let rest = if input.is_empty() {
+            TokenStream2::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            input.parse()?
+        };
+
+        Ok(Self { key, rest })
+    }
+}
+
+/// Translates a key that is validated against the default locale at compile time
+///
+/// Reads the translation files of the default locale (`I18N_ID`, or "en-US")
+/// in the locale directory (`I18N_DIR`, or "./assets/locales/", relative to
+/// the calling crate), including sub-directories and, with the `json` and
+/// `yaml` features, JSON and YAML files, and emits a compile error if the key
+/// does not exist. Expands to `i18n::t!`, so the same inline arguments are
+/// accepted, and recompiles whenever a file that was read changes.
+///
+/// # Examples
+/// ```ignore
+/// let hello = i18n::t_static!("hello");
+/// let greeting = i18n::t_static!("greeting", name = "Bob");
+/// let oops = i18n::t_static!("helo");  // error: unknown translation key `helo`
+/// ```
+#[proc_macro]
+pub fn t_static(input: TokenStream) -> TokenStream {
+    let StaticKey { key, rest } = parse_macro_input!(input as StaticKey);
+
+    let files = match validate(&key.value()) {
+        Ok(files) => files.iter().map(|file| file.display().to_string()).collect::<Vec<_>>(),
+        Err(message) => return syn::Error::new(key.span(), message).to_compile_error().into(),
+    };
+
+    let translate = if rest.is_empty() {
+        quote! { ::i18n::t!(#key) }
+    } else {
+        quote! { ::i18n::t!(#key, #rest) }
+    };
+
+    quote! {{
+        #(const _: &str = include_str!(#files);)*
+        #translate
+    }}
+    .into()
+}
+
+/// Compiles every FTL file of a locale directory into the binary
+///
+/// The path is relative to the calling crate's manifest directory and must
+/// contain one sub-directory of FTL files per locale. Expands to an
+/// `i18n::EmbeddedLocales` usable in a `static`, and recompiles whenever an
+/// embedded file changes.
+///
+/// # Examples
+/// ```ignore
+/// static LOCALES: i18n::EmbeddedLocales = i18n::embed_locales!("assets/locales");
+/// ```
+#[proc_macro]
+pub fn embed_locales(input: TokenStream) -> TokenStream {
+    let dir = parse_macro_input!(input as LitStr);
+
+    match embedded_locales(&dir.value()) {
+        Ok(locales) => {
+            let locales = locales.into_iter().map(|(name, files)| {
+                quote! { (#name, &[#(include_str!(#files)),*]) }
+            });
+
+            quote! { ::i18n::EmbeddedLocales::new(&[#(#locales),*]) }.into()
+        }
+        Err(message) => syn::Error::new(dir.span(), message).to_compile_error().into(),
+    }
+}
+
+/// Implements `i18n::ToFluentArgs`, setting one argument per field
+///
+/// Fields are cloned and converted with `FluentValue::from`, so they can be
+/// strings or numbers. Arguments are named after the field unless renamed with
+/// `#[fluent(rename = "...")]`, `#[fluent(skip)]` leaves a field out, and
+/// `Option` fields are only set when `Some`.
+///
+/// # Examples
+/// ```ignore
+/// #[derive(i18n::FluentArgs)]
+/// struct Order {
+///     #[fluent(rename = "order-id")]
+///     id: u64,
+///     total: String,
+///     note: Option<String>,
+///     #[fluent(skip)]
+///     internal: Vec<u8>,
+/// }
+///
+/// let message = i18n::new("order-confirmation").with(&order).build();
+/// ```
+#[proc_macro_derive(FluentArgs, attributes(fluent))]
+pub fn derive_fluent_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match fluent_args(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn fluent_args(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "FluentArgs can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(input, "FluentArgs requires a struct with named fields"));
+    };
+
+    let sets = field_args(fields, |ident| quote! { &self.#ident }, |name, value| quote! { args.set(#name, #value); })?;
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::i18n::ToFluentArgs for #ident #ty_generics #where_clause {
+            fn to_fluent_args(&self) -> ::i18n::FluentArgs<'static> {
+                let mut args = ::i18n::FluentArgs::new();
+                #(#sets)*
+                args
+            }
+        }
+    })
+}
+
+/// Implements `Display` for an error enum by translating each variant
+///
+/// A variant's key is its name in kebab case (`NotFound` is "not-found"),
+/// prefixed with `#[localized(prefix = "...")]` on the enum when given, or
+/// set explicitly with `#[localized(key = "...")]` on the variant. Named
+/// fields are passed as arguments like with `FluentArgs`, so they accept the
+/// same `#[fluent(...)]` attributes. Messages are resolved in the active
+/// locale whenever the error is displayed.
+///
+/// # Examples
+/// ```ignore
+/// #[derive(Debug, i18n::LocalizedError)]
+/// #[localized(prefix = "error")]
+/// enum ApiError {
+///     NotFound { id: u64 },  // "error-not-found", with $id
+///     #[localized(key = "error-rate-limit")]
+///     TooManyRequests,
+/// }
+///
+/// impl std::error::Error for ApiError {}
+///
+/// let message = ApiError::NotFound { id: 7 }.to_string();
+/// ```
+#[proc_macro_derive(LocalizedError, attributes(localized, fluent))]
+pub fn derive_localized_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match localized_error(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn localized_error(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "LocalizedError can only be derived for enums"));
+    };
+    let prefix = localized(&input.attrs, "prefix")?;
+
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let key = match (localized(&variant.attrs, "key")?, &prefix) {
+            (Some(key), _) => key,
+            (None, Some(prefix)) => format!("{prefix}-{}", kebab_case(&ident.to_string())),
+            (None, None) => kebab_case(&ident.to_string()),
+        };
+
+        arms.push(match &variant.fields {
+            Fields::Named(fields) => {
+                let idents = fields.named.iter().map(|field| &field.ident);
+                let sets = field_args(fields, |ident| quote! { #ident }, |name, value| quote! {
+                    builder = builder.set_arg(#name, #value);
+                })?;
+
+                quote! {
+                    #[allow(unused_variables)]
+                    Self::#ident { #(#idents),* } => {
+                        let mut builder = ::i18n::new(#key);
+                        #(#sets)*
+                        f.write_str(&builder.build())
+                    }
+                }
+            }
+            Fields::Unnamed(_) => quote! { Self::#ident(..) => f.write_str(&::i18n::get(#key)), },
+            Fields::Unit => quote! { Self::#ident => f.write_str(&::i18n::get(#key)), },
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Reads the string value of `name` from `#[localized(...)]` attributes
+fn localized(attrs: &[Attribute], name: &str) -> syn::Result<Option<String>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("localized")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                value = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error(format!("expected `{name} = \"...\"`")))
+            }
+        })?;
+    }
+
+    Ok(value)
+}
+
+/// Converts a variant name to kebab case, keeping acronyms together
+fn kebab_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut kebab = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_lower) {
+                kebab.push('-');
+            }
+        }
+        kebab.extend(c.to_lowercase());
+    }
+
+    kebab
+}
+
+/// Generates a `set` statement per field, honouring `#[fluent(...)]` attributes
+///
+/// `binding` returns a reference to the field's value, and `set` receives the
+/// argument name and its `FluentValue`. `Option` fields only set when `Some`.
+fn field_args<B, S>(fields: &FieldsNamed, binding: B, set: S) -> syn::Result<Vec<TokenStream2>>
+where
+    B: Fn(&Ident) -> TokenStream2,
+    S: Fn(&str, TokenStream2) -> TokenStream2,
+{
+    let mut sets = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let mut name = ident.to_string();
+        let mut skip = false;
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("fluent")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+
+        if skip {
+            continue;
+        }
+
+        let value = binding(ident);
+        sets.push(if is_option(&field.ty) {
+            let set = set(&name, quote! { ::i18n::FluentValue::from(::std::clone::Clone::clone(value)).into_owned() });
+            quote! {
+                if let ::std::option::Option::Some(value) = #value {
+                    #set
+                }
+            }
+        } else {
+            set(&name, quote! { ::i18n::FluentValue::from(::std::clone::Clone::clone(#value)).into_owned() })
+        });
+    }
+
+    Ok(sets)
+}
+
+/// Returns whether `ty` is spelled as an `Option`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.segments.last().is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Defines the `filters` module used by Askama templates, with a `t` filter
+///
+/// Askama resolves filter arguments at compile time, so the argument names
+/// used in templates are listed once per module. `locale` is always
+/// accepted. Expands to a `filters` module in the calling scope, next to the
+/// template structs.
+///
+/// # Examples
+/// ```ignore
+/// i18n::askama::filters!(name, count);
+///
+/// #[derive(askama::Template)]
+/// #[template(source = r#"{{ "greeting"|t(name=user) }}"#, ext = "html")]
+/// struct Greeting {
+///     user: String,
+/// }
+/// ```
+#[proc_macro]
+pub fn askama_filters(input: TokenStream) -> TokenStream {
+    let names = parse_macro_input!(input with Punctuated::<Ident, Token![,]>::parse_terminated);
+
+    let setters = names.iter().filter(|name| *name != "locale").map(|name| {
+        let setter = format_ident!("with_{name}");
+        let key = name.to_string();
+
+        quote! {
+            pub fn #setter<T: ::i18n::askama::IntoArg>(self, value: T) -> Self {
+                Self(self.0.arg(#key, value))
+            }
+        }
+    });
+
+    quote! {
+        pub mod filters {
+            #[allow(non_camel_case_types)]
+            #[derive(Default)]
+            pub struct t(::i18n::askama::Translate);
+
+            impl t {
+                #(#setters)*
+
+                pub fn with_locale<T: ::std::fmt::Display>(self, locale: T) -> Self {
+                    Self(self.0.locale(locale))
+                }
+
+                pub fn execute<K: ::std::fmt::Display>(self, key: K, values: &dyn ::askama::Values) -> ::askama::Result<::std::string::String> {
+                    self.0.execute(key, values)
+                }
+            }
+
+            impl ::askama::filters::ValidFilterInvocation for t {}
+            impl<const N: usize> ::askama::filters::ValidArgIdx<N> for t {}
+        }
+    }
+    .into()
+}
+
+/// Lists each locale sub-directory of `dir` with its FTL files, sorted
+fn embedded_locales(dir: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let dir = manifest_relative(Path::new(dir));
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("unable to read locale directory `{}`: {e}", dir.display()))?;
+
+    let mut locales = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+
+        let mut files = Vec::new();
+        collect_files(&path, &["ftl"], &mut files)
+            .map_err(|e| format!("unable to read `{}`: {e}", path.display()))?;
+        files.sort();
+
+        locales.push((name, files.iter().map(|file| file.display().to_string()).collect()));
+    }
+
+    locales.sort();
+    Ok(locales)
+}
+
+/// Collects the files below `dir` with one of `extensions`
+fn collect_files(dir: &Path, extensions: &[&str], files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for path in fs::read_dir(dir)?.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, extensions, files)?;
+        } else if path.extension().is_some_and(|ext| extensions.iter().any(|e| ext == *e)) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the default locale defines `key`, returning the files read
+fn validate(key: &str) -> Result<Vec<PathBuf>, String> {
+    let locale = env::var("I18N_ID").unwrap_or_else(|_| DEFAULT_LANG.to_string());
+    let locale = locale.split(',').next().unwrap_or(DEFAULT_LANG).trim().to_string();
+    let dir = locale_dir().join(&locale);
+
+    let mut files = Vec::new();
+    collect_files(&dir, EXTENSIONS, &mut files)
+        .map_err(|e| format!("unable to read locale directory `{}`: {e}", dir.display()))?;
+    files.sort();
+
+    for (i, path) in files.iter().enumerate() {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("unable to read `{}`: {e}", path.display()))?;
+
+        if defines(path, &source, &locale, key)? {
+            files.truncate(i + 1);
+            return Ok(files);
+        }
+    }
+
+    Err(format!("unknown translation key `{key}` for locale `{locale}`"))
+}
+
+/// Returns whether a translation file of `locale` defines `key`, a message ID or `message.attribute`
+#[cfg_attr(not(any(feature = "json", feature = "yaml")), allow(unused_variables))]
+fn defines(path: &Path, source: &str, locale: &str, key: &str) -> Result<bool, String> {
+    let (message, attr) = match key.split_once('.') {
+        Some((message, attr)) => (message, Some(attr)),
+        None => (key, None),
+    };
+
+    #[cfg(any(feature = "json", feature = "yaml"))]
+    if let Some(ids) = structured_ids(path, source, locale)? {
+        return Ok(attr.is_none() && ids.iter().any(|id| id == message));
+    }
+
+    let resource = fluent_syntax::parser::parse(source)
+        .unwrap_or_else(|(resource, _)| resource);
+
+    let found = resource.body.iter().any(|entry| match entry {
+        Entry::Message(m) if m.id.name == message => match attr {
+            Some(attr) => m.attributes.iter().any(|a| a.id.name == attr),
+            None => m.value.is_some(),
+        },
+        _ => false,
+    });
+
+    Ok(found)
+}
+
+/// Returns the message IDs of a JSON or YAML file of `locale`, as `i18n` loads it
+///
+/// Returns `None` for files of other formats.
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn structured_ids(path: &Path, source: &str, locale: &str) -> Result<Option<Vec<String>>, String> {
+    let error = |e: &dyn std::fmt::Display| format!("unable to parse `{}`: {e}", path.display());
+    let value: serde_json::Value = match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "json")]
+        Some("json") => serde_json::from_str(source).map_err(|e| error(&e))?,
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => serde_yaml::from_str(source).map_err(|e| error(&e))?,
+        _ => return Ok(None),
+    };
+
+    let locale = locale.parse().ok();
+
+    Ok(Some(keys::messages(&value, locale.as_ref()).into_iter().map(|(id, _)| id).collect()))
+}
+
+fn locale_dir() -> PathBuf {
+    let dir = env::var("I18N_DIR").unwrap_or_else(|_| DEFAULT_DIR.to_string());
+
+    manifest_relative(Path::new(&dir))
+}
+
+/// Resolves a relative path against the calling crate's manifest directory
+fn manifest_relative(dir: &Path) -> PathBuf {
+    match env::var("CARGO_MANIFEST_DIR") {
+        Ok(root) if dir.is_relative() => Path::new(&root).join(dir),
+        _ => dir.to_path_buf(),
+    }
+}
@@ -0,0 +1,82 @@
+//! Actix-web integration resolving the request locale
+//!
+//! ```no_run
+//! use actix_web::{App, HttpServer, middleware, web};
+//!
+//! async fn hello() -> String {
+//!     i18n::get("hello")  // Uses the visitor's locale
+//! }
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! HttpServer::new(|| {
+//!     App::new()
+//!         .wrap(middleware::from_fn(i18n::actix::resolve_locale))
+//!         .route("/", web::get().to(hello))
+//! })
+//! .bind(("127.0.0.1", 8080))?
+//! .run()
+//! .await
+//! # }
+//! ```
+
+use crate::Locale;
+use crate::negotiate::{LOCALE_PARAM, cookie_value, query_param};
+use crate::task::with_locale;
+use ::actix_web::body::MessageBody;
+use ::actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use ::actix_web::http::header::{ACCEPT_LANGUAGE, COOKIE};
+use ::actix_web::middleware::Next;
+use ::actix_web::{Error, FromRequest, HttpMessage, HttpRequest};
+use std::convert::Infallible;
+use std::future::{Ready, ready};
+
+/// Middleware making the request's locale active while it is handled
+///
+/// The locale is taken from the `lang` query parameter, then the `lang`
+/// cookie, then the `Accept-Language` header, and defaults to the active
+/// locale. It is stored in the request extensions and set as the task-local
+/// locale, so every `i18n::get` call inside the handler uses it. Use it with
+/// `actix_web::middleware::from_fn`.
+///
+/// # Parameters
+/// * `request` - The incoming request
+/// * `next` - The rest of the middleware stack
+///
+/// # Return
+/// Returns the response of the inner service
+pub async fn resolve_locale<B>(
+    request: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error>
+where
+    B: MessageBody,
+{
+    let locale = from_request(request.request());
+    request.extensions_mut().insert(locale.clone());
+
+    with_locale(locale.0, next.call(request)).await
+}
+
+/// Extracts the request locale
+///
+/// Uses the locale stored by `resolve_locale` when the middleware is installed,
+/// and resolves it from the request otherwise.
+impl FromRequest for Locale {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let locale = request.extensions().get::<Locale>().cloned();
+
+        ready(Ok(locale.unwrap_or_else(|| from_request(request))))
+    }
+}
+
+fn from_request(request: &HttpRequest) -> Locale {
+    let header = |name| request.headers().get(name).and_then(|value| value.to_str().ok());
+
+    let query = query_param(request.query_string(), LOCALE_PARAM);
+    let cookie = header(COOKIE).and_then(|cookie| cookie_value(cookie, LOCALE_PARAM));
+
+    Locale(crate::resolve_locale(header(ACCEPT_LANGUAGE), cookie, query))
+}
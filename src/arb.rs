@@ -0,0 +1,600 @@
+use crate::catalog::Bundle;
+use crate::ftl::{Messages, Pattern, variable};
+use crate::keys::{self, CATEGORIES};
+use crate::{ERROR_LOCK, I18nError, LanguageIdentifier, instance, locale, negotiate};
+use fluent_syntax::ast::{self, Expression, InlineExpression, PatternElement, VariantKey};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Metadata key holding the Fluent ID of an ARB message
+const FLUENT_ID: &str = "x-fluent-id";
+
+/// Placeholders of an ARB message, with their Dart type if known
+type Placeholders = BTreeMap<String, Option<&'static str>>;
+
+/// Exports a locale as an ARB file for Flutter apps
+///
+/// Every message and attribute of the locale becomes an ARB message, written
+/// in ICU MessageFormat: `{ $name }` becomes `{name}`, and selects on a
+/// variable become `plural` or `select` arguments. Anything without an ICU
+/// counterpart, such as message references or function calls, is resolved to
+/// its text. Keys are the Fluent IDs in camel case (`login-button.title`
+/// becomes `loginButtonTitle`), so they are valid Dart method names, and each
+/// message's metadata records its placeholders and original ID for
+/// `arb_to_ftl`. Literal braces are quoted ICU style (`'{'`), which Flutter
+/// reads with `use-escaping: true`. Only the locale's own messages are
+/// exported, as Flutter resolves fallbacks itself. An invalid language
+/// identifier is ignored and the active locale is used instead.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+///
+/// # Return
+/// Returns the ARB document as a JSON object
+///
+/// # Examples
+/// ```
+///
+/// let arb = i18n::export_arb("es-MX");
+/// assert_eq!(&arb["@@locale"], "es_MX");
+/// assert_eq!(&arb["greeting"], "¡Hola, {name}!");
+/// assert_eq!(&arb["@loginButtonTitle"]["x-fluent-id"], "login-button.title");
+/// ```
+pub fn export_arb<T>(locale: T) -> Value
+where
+    T: ToString,
+{
+    let locale: LanguageIdentifier = locale.to_string().parse().unwrap_or_else(|_| self::locale());
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let installed = negotiate::matching(&[i18n.alias(&locale)], catalog.available())
+        .first()
+        .map(|locale| (*locale).clone());
+
+    let mut arb = Map::new();
+    let name = installed.as_ref().unwrap_or(&locale).to_string().replace('-', "_");
+    arb.insert("@@locale".to_string(), Value::String(name));
+
+    let messages = installed.map(|installed| catalog.convert(&installed, |_, bundle, pattern| icu(bundle, pattern))).unwrap_or_default();
+    for (id, (message, placeholders)) in messages {
+        let key = camel_case(&id);
+        if arb.contains_key(&key) {
+            continue;
+        }
+
+        let mut metadata = Map::new();
+        if !placeholders.is_empty() {
+            let placeholders = placeholders
+                .into_iter()
+                .map(|(name, kind)| {
+                    let mut placeholder = Map::new();
+                    if let Some(kind) = kind {
+                        placeholder.insert("type".to_string(), Value::String(kind.to_string()));
+                    }
+
+                    (name, Value::Object(placeholder))
+                })
+                .collect();
+
+            metadata.insert("placeholders".to_string(), Value::Object(placeholders));
+        }
+        metadata.insert(FLUENT_ID.to_string(), Value::String(id));
+
+        arb.insert(format!("@{key}"), Value::Object(metadata));
+        arb.insert(key, Value::String(message));
+    }
+
+    Value::Object(arb)
+}
+
+/// Converts an ARB file to FTL
+///
+/// Messages are read as ICU MessageFormat: `{name}` becomes `{ $name }`,
+/// `plural` and `select` arguments become select expressions, with `=1` keys
+/// as `[1]` and `#` as the plural's variable, and `number`, `date`, and `time`
+/// arguments keep their variable. Each message takes its Fluent ID from the
+/// `x-fluent-id` metadata written by `export_arb`, or else from its key in
+/// kebab case (`loginButton` becomes `login-button`). Metadata entries are
+/// skipped.
+///
+/// # Parameters
+/// * `arb` - The contents of an `.arb` file
+///
+/// # Return
+/// Returns the FTL source, or an error if `arb` is malformed or a message is
+/// not valid ICU MessageFormat
+///
+/// # Examples
+/// ```
+///
+/// let arb = r#"{
+///     "@@locale": "es",
+///     "greeting": "¡Hola, {name}!",
+///     "unreadEmails": "{count, plural, =0{Sin correos} one{Un correo} other{# correos}}"
+/// }"#;
+///
+/// let ftl = i18n::arb_to_ftl(arb).unwrap();
+/// assert_eq!(ftl, "\
+/// greeting = ¡Hola, { $name }!
+/// unread-emails = { $count ->
+///         [0] Sin correos
+///         [one] Un correo
+///        *[other] { $count } correos
+///     }
+/// ");
+/// ```
+pub fn arb_to_ftl(arb: &str) -> Result<String, I18nError> {
+    let value: Value = serde_json::from_str(arb).map_err(|e| I18nError::Loader(format!("ARB: {e}")))?;
+    let Value::Object(arb) = value else {
+        return Err(I18nError::Loader("ARB: expected a JSON object".to_string()));
+    };
+
+    let mut messages = Messages::default();
+    for (key, value) in &arb {
+        let Value::String(text) = value else {
+            continue;
+        };
+
+        if key.starts_with('@') {
+            continue;
+        }
+
+        let id = arb
+            .get(&format!("@{key}"))
+            .and_then(|metadata| metadata.get(FLUENT_ID))
+            .and_then(Value::as_str)
+            .filter(|id| id.split('.').all(keys::is_identifier))
+            .map(str::to_string)
+            .or_else(|| keys::identifier(&kebab_case(key)));
+
+        let Some(id) = id else {
+            continue;
+        };
+
+        let pattern = Parser::new(text).message("", None, false).map_err(|e| I18nError::Loader(format!("ARB message {key}: {e}")))?;
+        messages.insert(&id, pattern);
+    }
+
+    Ok(messages.finish())
+}
+
+/// Converts a Fluent ID to an ARB key, such as `login-button.title` to `loginButtonTitle`
+fn camel_case(id: &str) -> String {
+    let mut key = String::new();
+
+    for (i, word) in id.split(['-', '_', '.']).filter(|word| !word.is_empty()).enumerate() {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                key.push(first.to_ascii_lowercase());
+            } else {
+                key.push(first.to_ascii_uppercase());
+            }
+            key.extend(chars);
+        }
+    }
+
+    key
+}
+
+/// Converts an ARB key to a Fluent ID, such as `loginButton` to `login-button`
+fn kebab_case(key: &str) -> String {
+    let mut id = String::new();
+
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            if !id.is_empty() {
+                id.push('-');
+            }
+            id.push(c.to_ascii_lowercase());
+        } else {
+            id.push(c);
+        }
+    }
+
+    id
+}
+
+/// Converts a Fluent pattern to ICU MessageFormat, collecting its placeholders
+fn icu(bundle: &Bundle, pattern: &ast::Pattern<&str>) -> (String, Placeholders) {
+    let mut writer = Icu { bundle, text: String::new(), apostrophe: false, placeholders: Placeholders::new() };
+    writer.pattern(pattern, false);
+
+    (writer.text, writer.placeholders)
+}
+
+/// Writes ICU MessageFormat, quoting literal text where ICU would read syntax
+struct Icu<'a> {
+    bundle: &'a Bundle,
+    text: String,
+    /// Whether the text ends with a lone literal apostrophe
+    apostrophe: bool,
+    placeholders: Placeholders,
+}
+
+impl Icu<'_> {
+    fn pattern(&mut self, pattern: &ast::Pattern<&str>, plural: bool) {
+        for element in &pattern.elements {
+            match element {
+                PatternElement::TextElement { value } => self.literal(value, plural),
+                PatternElement::Placeable { expression } => self.expression(expression, plural),
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression<&str>, plural: bool) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                let Some(name) = variable(selector) else {
+                    return self.resolve(expression, plural);
+                };
+
+                let is_plural = variants.iter().all(|variant| match &variant.key {
+                    VariantKey::Identifier { name } => CATEGORIES.contains(name),
+                    VariantKey::NumberLiteral { .. } => true,
+                });
+                let has_other = variants.iter().any(|variant| matches!(variant.key, VariantKey::Identifier { name: "other" }));
+
+                let kind = if is_plural { "plural" } else { "select" };
+                self.placeholder(name, Some(if is_plural { "num" } else { "String" }));
+                self.syntax(&format!("{{{name}, {kind},"));
+
+                for variant in variants {
+                    let key = match &variant.key {
+                        _ if variant.default && !has_other => "other".to_string(),
+                        VariantKey::Identifier { name } => name.to_string(),
+                        VariantKey::NumberLiteral { value } => format!("={value}"),
+                    };
+
+                    self.syntax(&format!(" {key}{{"));
+                    self.pattern(&variant.value, is_plural || plural);
+                    self.syntax("}");
+                }
+
+                self.syntax("}");
+            }
+            Expression::Inline(expression) => self.inline(expression, plural),
+        }
+    }
+
+    fn inline(&mut self, expression: &InlineExpression<&str>, plural: bool) {
+        match expression {
+            InlineExpression::NumberLiteral { value } => self.literal(value, plural),
+            InlineExpression::VariableReference { id } => {
+                self.placeholder(id.name, None);
+                self.syntax(&format!("{{{}}}", id.name));
+            }
+            InlineExpression::Placeable { expression } => self.expression(expression, plural),
+            expression => match variable(expression) {
+                Some(name) => {
+                    self.placeholder(name, Some("num"));
+                    self.syntax(&format!("{{{name}}}"));
+                }
+                None => self.resolve(&Expression::Inline(expression.clone()), plural),
+            },
+        }
+    }
+
+    /// Writes an expression without an ICU counterpart as the text it resolves to
+    fn resolve(&mut self, expression: &Expression<&str>, plural: bool) {
+        let pattern = ast::Pattern { elements: vec![PatternElement::Placeable { expression: expression.clone() }] };
+        let text = self.bundle.format_pattern(&pattern, None, &mut Vec::new()).into_owned();
+
+        self.literal(&text, plural);
+    }
+
+    fn placeholder(&mut self, name: &str, kind: Option<&'static str>) {
+        let placeholder = self.placeholders.entry(name.to_string()).or_default();
+        if placeholder.is_none() {
+            *placeholder = kind;
+        }
+    }
+
+    fn literal(&mut self, text: &str, plural: bool) {
+        for c in text.chars() {
+            match c {
+                '{' | '}' => self.syntax(&format!("'{c}'")),
+                '#' if plural => self.syntax("'#'"),
+                '\'' => {
+                    self.syntax("'");
+                    self.apostrophe = true;
+                }
+                c => {
+                    self.text.push(c);
+                    self.apostrophe = false;
+                }
+            }
+        }
+    }
+
+    /// Appends syntax, doubling a preceding literal apostrophe so it stays literal
+    fn syntax(&mut self, syntax: &str) {
+        if self.apostrophe {
+            self.text.push('\'');
+        }
+
+        self.text.push_str(syntax);
+        self.apostrophe = false;
+    }
+}
+
+/// Reads ICU MessageFormat into FTL patterns
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Self {
+        Self { chars: text.chars().collect(), position: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    /// Reads up to the next character in `ends`, trimmed
+    fn until(&mut self, ends: &[char]) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(|c| !ends.contains(&c)) {
+            self.position += 1;
+        }
+
+        self.chars[start..self.position].iter().collect::<String>().trim().to_string()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected '{expected}', found '{c}'")),
+            None => Err(format!("expected '{expected}', found the end of the message")),
+        }
+    }
+
+    /// Reads a message, or a variant up to its closing brace if `nested`
+    ///
+    /// Continuation lines are indented with `indent`, and `#` stands for the
+    /// `pound` variable inside plurals.
+    fn message(&mut self, indent: &str, pound: Option<&str>, nested: bool) -> Result<String, String> {
+        let mut pattern = Pattern::new(indent);
+
+        loop {
+            match (self.next(), pound) {
+                (None, _) if nested => return Err("unclosed '{'".to_string()),
+                (None, _) => break,
+                (Some('}'), _) if nested => break,
+                (Some('}'), _) => return Err("unexpected '}'".to_string()),
+                (Some('{'), _) => self.argument(&mut pattern, indent)?,
+                (Some('#'), Some(name)) => pattern.variable(name),
+                (Some('\''), _) => match self.peek() {
+                    Some('\'') => {
+                        self.position += 1;
+                        pattern.text("'");
+                    }
+                    Some('{' | '}') => pattern.text(&self.quoted()),
+                    Some('#') if pound.is_some() => pattern.text(&self.quoted()),
+                    _ => pattern.text("'"),
+                },
+                (Some(c), _) => pattern.text(c.encode_utf8(&mut [0; 4])),
+            }
+        }
+
+        Ok(pattern.finish())
+    }
+
+    /// Reads quoted literal text, after its opening apostrophe
+    fn quoted(&mut self) -> String {
+        let mut text = String::new();
+
+        while let Some(c) = self.next() {
+            match c {
+                '\'' if self.peek() == Some('\'') => {
+                    self.position += 1;
+                    text.push('\'');
+                }
+                '\'' => break,
+                c => text.push(c),
+            }
+        }
+
+        text
+    }
+
+    /// Reads an argument, after its opening brace
+    fn argument(&mut self, pattern: &mut Pattern, indent: &str) -> Result<(), String> {
+        let name = self.until(&[',', '}']);
+        if !keys::is_identifier(&name) {
+            return Err(format!("invalid argument name '{name}'"));
+        }
+
+        match self.next() {
+            Some('}') => {
+                pattern.variable(&name);
+                return Ok(());
+            }
+            Some(_) => {}
+            None => return Err("unclosed '{'".to_string()),
+        }
+
+        let kind = self.until(&[',', '}']);
+        match kind.as_str() {
+            "plural" | "select" => {
+                self.expect(',')?;
+                let select = self.select(&name, kind == "plural", indent)?;
+                pattern.raw(&select);
+            }
+            // Formatting styles are left to the FTL
+            "number" | "date" | "time" => {
+                self.skip_argument()?;
+                pattern.variable(&name);
+            }
+            kind => return Err(format!("unsupported argument type '{kind}'")),
+        }
+
+        Ok(())
+    }
+
+    /// Skips the rest of an argument, including its closing brace
+    fn skip_argument(&mut self) -> Result<(), String> {
+        let mut depth = 1;
+
+        while depth > 0 {
+            match self.next() {
+                Some('{') => depth += 1,
+                Some('}') => depth -= 1,
+                Some(_) => {}
+                None => return Err("unclosed '{'".to_string()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the variants of a plural or select argument as an FTL select expression
+    fn select(&mut self, name: &str, plural: bool, indent: &str) -> Result<String, String> {
+        let nested = format!("{indent}        ");
+        let pound = plural.then_some(name);
+        let mut variants = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.position += 1;
+                break;
+            }
+
+            // Plural offsets have no FTL counterpart
+            let key = self.until(&['{', '}']);
+            let key = match key.strip_prefix("offset:") {
+                Some(rest) => rest.trim_start_matches(|c: char| !c.is_whitespace()).trim().to_string(),
+                None => key,
+            };
+
+            if key.is_empty() {
+                return Err("expected a variant".to_string());
+            }
+            self.expect('{')?;
+            let value = self.message(&nested, pound, true)?;
+
+            let key = match key.strip_prefix('=') {
+                Some(number) if number.parse::<f64>().is_ok() => number.to_string(),
+                _ if keys::is_identifier(&key) => key,
+                _ => return Err(format!("invalid variant key '{key}'")),
+            };
+
+            variants.push((key, value));
+        }
+
+        let default = variants
+            .iter()
+            .position(|(key, _)| key == "other")
+            .or_else(|| variants.len().checked_sub(1))
+            .ok_or_else(|| format!("'{name}' has no variants"))?;
+
+        let mut select = format!("{{ ${name} ->\n");
+        for (i, (key, value)) in variants.iter().enumerate() {
+            let marker = if i == default { "   *" } else { "    " };
+            select.push_str(&format!("{indent}{marker}[{key}] {value}\n"));
+        }
+        select.push_str(&format!("{indent}}}"));
+
+        Ok(select)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+    use serde_json::json;
+
+    const FTL: &str = "\
+greeting = Hi, { $name }!
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+braces = Use {\"{\"} here
+login-button = Log in
+    .title = Click
+who = { $g ->
+    [female] She
+   *[other] They
+}
+";
+
+    #[test]
+    fn messages_export_as_icu() {
+        let _guard = fixtures(&[("en-US", FTL)]);
+        let arb = export_arb("en-US");
+
+        assert_eq!(arb["@@locale"], "en_US");
+        assert_eq!(arb["greeting"], "Hi, {name}!");
+        assert_eq!(arb["emails"], "{count, plural, one{One email} other{{count} emails}}");
+        assert_eq!(arb["who"], "{g, select, female{She} other{They}}");
+        assert_eq!(arb["braces"], "Use '{' here");
+        assert_eq!(arb["loginButtonTitle"], "Click");
+    }
+
+    #[test]
+    fn metadata_records_placeholders_and_ids() {
+        let _guard = fixtures(&[("en-US", FTL)]);
+        let arb = export_arb("en-US");
+
+        assert_eq!(arb["@emails"], json!({ "placeholders": { "count": { "type": "num" } }, "x-fluent-id": "emails" }));
+        assert_eq!(arb["@who"]["placeholders"]["g"], json!({ "type": "String" }));
+        assert_eq!(arb["@loginButtonTitle"], json!({ "x-fluent-id": "login-button.title" }));
+    }
+
+    #[test]
+    fn exported_files_convert_back() {
+        let _guard = fixtures(&[("en-US", FTL)]);
+        let ftl = arb_to_ftl(&export_arb("en-US").to_string()).unwrap();
+
+        assert!(ftl.contains("login-button = Log in\n    .title = Click\n"));
+        assert!(ftl.contains("braces = Use {\"{\"} here\n"));
+        assert!(ftl.contains("emails = { $count ->\n        [one] One email\n       *[other] { $count } emails\n    }\n"));
+    }
+
+    #[test]
+    fn icu_quotes_and_types_are_read() {
+        let arb = r#"{"quoted": "It''s '{' {when, date}", "gender": "{g, select, female{Ella} other{Elle}}", "@gender": {"x-fluent-id": "who.gender"}}"#;
+
+        assert_eq!(arb_to_ftl(arb).unwrap(), "\
+quoted = It's {\"{\"} { $when }
+who =
+    .gender = { $g ->
+            [female] Ella
+           *[other] Elle
+        }
+");
+    }
+
+    #[test]
+    fn keys_change_case() {
+        assert_eq!(camel_case("login-button.title"), "loginButtonTitle");
+        assert_eq!(kebab_case("loginButtonTitle"), "login-button-title");
+    }
+
+    #[test]
+    fn malformed_files_are_reported() {
+        assert!(matches!(arb_to_ftl("[]"), Err(I18nError::Loader(reason)) if reason == "ARB: expected a JSON object"));
+        assert!(matches!(arb_to_ftl(r#"{"a": "{x, foo}"}"#), Err(I18nError::Loader(reason)) if reason.contains("unsupported argument type 'foo'")));
+        assert!(arb_to_ftl(r#"{"a": "{x"}"#).is_err());
+    }
+}
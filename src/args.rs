@@ -0,0 +1,227 @@
+use crate::I18nBuilder;
+use fluent_templates::fluent_bundle::types::FluentNumber;
+use fluent_templates::fluent_bundle::{FluentArgs, FluentValue};
+use std::borrow::Cow;
+
+/// Types that can be passed as the arguments of a translation
+///
+/// Usually derived with `#[derive(i18n::FluentArgs)]` (`macros` feature),
+/// which sets one argument per field. `#[fluent(rename = "...")]` changes the
+/// argument name, `#[fluent(skip)]` leaves a field out, and `Option` fields
+/// are skipped when `None`.
+///
+/// # Examples
+/// ```
+///
+/// use i18n::{FluentArgs, ToFluentArgs};
+///
+/// struct Inbox {
+///     count: u32,
+/// }
+///
+/// impl ToFluentArgs for Inbox {
+///     fn to_fluent_args(&self) -> FluentArgs<'static> {
+///         let mut args = FluentArgs::new();
+///         args.set("count", self.count);
+///         args
+///     }
+/// }
+///
+/// let message = i18n::new("emails")
+///     .with(&Inbox { count: 2 })
+///     .build();
+/// assert_eq!(message, "You have 2 emails");
+/// ```
+pub trait ToFluentArgs {
+    /// Returns the arguments, keyed by the name used in FTL
+    fn to_fluent_args(&self) -> FluentArgs<'static>;
+}
+
+impl ToFluentArgs for FluentArgs<'_> {
+    fn to_fluent_args(&self) -> FluentArgs<'static> {
+        self.iter().map(|(key, value)| (key.to_string(), value.into_owned())).collect()
+    }
+}
+
+/// Types that can be passed as a single argument of a translation
+///
+/// Numbers become Fluent numbers, so plural selectors see the numeric value,
+/// and strings of any lifetime are copied into an owned value.
+///
+/// # Examples
+/// ```
+///
+/// use i18n::IntoFluentValue;
+///
+/// i18n::test_loader(&[("en-US", "forecast = { $temperature }°C")]);
+///
+/// struct Celsius(f64);
+///
+/// impl IntoFluentValue for Celsius {
+///     fn into_fluent_value(self) -> i18n::FluentValue<'static> {
+///         self.0.into()
+///     }
+/// }
+///
+/// let forecast = i18n::new("forecast")
+///     .set_value("temperature", Celsius(21.5))
+///     .build();
+/// assert_eq!(forecast, "21.5°C");
+/// ```
+pub trait IntoFluentValue {
+    /// Returns the value as stored in the translation arguments
+    fn into_fluent_value(self) -> FluentValue<'static>;
+}
+
+macro_rules! numbers {
+    ($($number:ty),+) => {
+        $(
+            impl IntoFluentValue for $number {
+                fn into_fluent_value(self) -> FluentValue<'static> {
+                    self.into()
+                }
+            }
+
+            impl IntoFluentValue for &$number {
+                fn into_fluent_value(self) -> FluentValue<'static> {
+                    (*self).into()
+                }
+            }
+        )+
+    };
+}
+
+numbers!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl IntoFluentValue for FluentNumber {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.into()
+    }
+}
+
+impl IntoFluentValue for FluentValue<'static> {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self
+    }
+}
+
+impl IntoFluentValue for String {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.into()
+    }
+}
+
+impl IntoFluentValue for &String {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.clone().into()
+    }
+}
+
+impl IntoFluentValue for &str {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.to_string().into()
+    }
+}
+
+impl IntoFluentValue for Cow<'_, str> {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.into_owned().into()
+    }
+}
+
+/// Converts an argument given as text, keeping numbers numeric
+///
+/// Text only becomes a number if Fluent writes the number back as the same
+/// text, so values such as "007" or IDs too long for a float stay as given.
+pub(crate) fn text_value(text: String) -> FluentValue<'static> {
+    match text.parse::<FluentNumber>() {
+        Ok(number) if number.value.is_finite() && number.as_string() == text => number.into(),
+        _ => text.into(),
+    }
+}
+
+impl IntoFluentValue for char {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.to_string().into()
+    }
+}
+
+impl IntoFluentValue for bool {
+    fn into_fluent_value(self) -> FluentValue<'static> {
+        self.to_string().into()
+    }
+}
+
+impl I18nBuilder {
+    /// Sets the parameters for the translation from a struct
+    ///
+    /// Arguments already set with the same name are replaced.
+    ///
+    /// # Parameters
+    /// * `value` - A value implementing `ToFluentArgs`, usually derived
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "macros", doc = "```")]
+    #[cfg_attr(not(feature = "macros"), doc = "```ignore")]
+    ///
+    /// i18n::test_loader(&[("en-US", "order-confirmation = Order { $order-id }: { $total }")]);
+    ///
+    /// #[derive(i18n::FluentArgs)]
+    /// struct Order {
+    ///     #[fluent(rename = "order-id")]
+    ///     id: u64,
+    ///     total: String,
+    ///     note: Option<String>,
+    /// }
+    ///
+    /// let order = Order { id: 42, total: "$19.99".into(), note: None };
+    /// let message = i18n::new("order-confirmation")
+    ///     .with(&order)
+    ///     .build();
+    /// assert_eq!(message, "Order 42: $19.99");
+    /// ```
+    pub fn with<T: ToFluentArgs + ?Sized>(mut self, value: &T) -> Self {
+        for (key, value) in value.to_fluent_args() {
+            self.args.set(key, value);
+        }
+
+        self
+    }
+}
+
+/// Copies `args` with their text values passed through `escape`
+///
+/// Numbers and custom values are kept as they are, since their formatted text
+/// comes from the locale rather than from the caller.
+pub(crate) fn escape_args(args: &FluentArgs<'static>, escape: fn(&str) -> String) -> FluentArgs<'static> {
+    args.iter()
+        .map(|(key, value)| {
+            let value = match value {
+                FluentValue::String(text) => FluentValue::from(escape(text)),
+                value => value.into_owned(),
+            };
+
+            (key.to_string(), value)
+        })
+        .collect()
+}
+
+/// Replaces the HTML special characters of `text` with entities
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
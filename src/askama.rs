@@ -0,0 +1,102 @@
+//! Askama integration exposing translations to templates
+//!
+//! `filters!` defines the `filters` module Askama looks up, with a `t` filter
+//! translating its input. Arguments keep their type, so numbers drive plural
+//! selection, and the locale is taken from a `locale` argument, then a
+//! `locale` value passed to `render_with_values`, then the active locale.
+//!
+//! ```
+//! use askama::Template;
+//!
+//! i18n::askama::filters!(name);
+//!
+//! #[derive(Template)]
+//! #[template(source = r#"<p>{{ "greeting"|t(name=user) }}</p>"#, ext = "html")]
+//! struct Greeting {
+//!     user: String,
+//! }
+//!
+//! let html = Greeting { user: "Bob".into() }.render().unwrap();  // Returns "<p>Hello, Bob!</p>"
+//! ```
+
+use crate::I18nBuilder;
+use ::askama::Values;
+use fluent_templates::fluent_bundle::FluentValue;
+use std::fmt::Display;
+
+pub use i18n_macros::askama_filters as filters;
+
+/// Name of the argument and render value selecting the locale
+const LOCALE: &str = "locale";
+
+/// Values accepted as arguments of the `t` filter
+///
+/// Implemented for strings and numbers, owned or borrowed from template
+/// fields.
+pub trait IntoArg {
+    /// Converts the value into a Fluent argument
+    fn into_arg(self) -> FluentValue<'static>;
+}
+
+impl<T: Clone + Into<FluentValue<'static>>> IntoArg for &T {
+    fn into_arg(self) -> FluentValue<'static> {
+        self.clone().into()
+    }
+}
+
+impl IntoArg for &str {
+    fn into_arg(self) -> FluentValue<'static> {
+        FluentValue::from(self.to_string())
+    }
+}
+
+macro_rules! into_arg {
+    ($($ty:ty),*) => {
+        $(impl IntoArg for $ty {
+            fn into_arg(self) -> FluentValue<'static> {
+                self.into()
+            }
+        })*
+    };
+}
+
+into_arg!(String, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// State of one `t` filter call, built by the module `filters!` expands to
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Translate {
+    args: Vec<(&'static str, FluentValue<'static>)>,
+    locale: Option<String>,
+}
+
+#[doc(hidden)]
+impl Translate {
+    pub fn arg<T: IntoArg>(mut self, name: &'static str, value: T) -> Self {
+        self.args.push((name, value.into_arg()));
+        self
+    }
+
+    pub fn locale<T: Display>(mut self, locale: T) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    pub fn execute<K: Display>(self, key: K, values: &dyn Values) -> ::askama::Result<String> {
+        let locale = self.locale.or_else(|| {
+            ::askama::get_value::<String>(values, LOCALE)
+                .cloned()
+                .or_else(|_| ::askama::get_value::<&str>(values, LOCALE).map(|locale| locale.to_string()))
+                .ok()
+        });
+
+        let builder = self.args
+            .into_iter()
+            .fold(crate::new(key), |builder: I18nBuilder, (name, value)| builder.set_arg(name, value));
+
+        Ok(match locale {
+            Some(locale) => builder.locale(locale).build(),
+            None => builder.build(),
+        })
+    }
+}
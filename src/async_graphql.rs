@@ -0,0 +1,130 @@
+//! async-graphql integration resolving the request locale
+//!
+//! ```no_run
+//! use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+//! use i18n::async_graphql::{ContextExt, I18nExtension};
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn hello(&self, ctx: &Context<'_>) -> String {
+//!         ctx.t("hello")  // Uses the request's locale
+//!     }
+//! }
+//!
+//! let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+//!     .extension(I18nExtension)
+//!     .finish();
+//! ```
+
+use crate::{I18nBuilder, Locale};
+use ::async_graphql::async_trait::async_trait;
+use ::async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute};
+use ::async_graphql::{Context, Data, Response};
+use std::sync::Arc;
+
+/// Extension making the request's locale active while a query is executed
+///
+/// The locale is taken from a `Locale` added to the request data (e.g.
+/// `request.data(locale)` with the locale extracted by the `axum` or `actix`
+/// integration), and defaults to the active locale, so it also picks up the
+/// locale set by their middleware or `i18n::tower::LocaleLayer`. It is stored
+/// in the query data and set as the task-local locale, so resolvers can
+/// translate with `ctx.t` or plain `i18n::get` calls.
+///
+/// # Examples
+/// ```
+///
+/// use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+///
+/// # struct Query;
+/// # #[Object]
+/// # impl Query {
+/// #     async fn ok(&self) -> bool { true }
+/// # }
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .extension(i18n::async_graphql::I18nExtension)
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct I18nExtension;
+
+impl ExtensionFactory for I18nExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(LocaleExtension)
+    }
+}
+
+struct LocaleExtension;
+
+#[async_trait]
+impl Extension for LocaleExtension {
+    async fn execute(&self, ctx: &ExtensionContext<'_>, operation_name: Option<&str>, next: NextExecute<'_>) -> Response {
+        let locale = match ctx.data_opt::<Locale>() {
+            Some(locale) => locale.clone(),
+            None => Locale(crate::locale()),
+        };
+
+        let mut data = Data::default();
+        data.insert(locale.clone());
+
+        crate::with_locale(locale.0, next.run_with_data(ctx, operation_name, data)).await
+    }
+}
+
+/// Translation helpers for resolvers
+///
+/// Lookups use the locale stored by `I18nExtension`, or the active locale when
+/// the extension is not installed.
+pub trait ContextExt {
+    /// Resolves the locale of the request
+    ///
+    /// # Return
+    /// Returns the request's locale
+    fn locale(&self) -> Locale;
+
+    /// Retrieves a translation for the given key in the request's locale
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string, using the fallback chain if needed
+    ///
+    /// # Examples
+    /// ```ignore
+    /// async fn user(&self, ctx: &Context<'_>, id: u64) -> async_graphql::Result<User> {
+    ///     find(id).ok_or_else(|| ctx.t("user-not-found").into())
+    /// }
+    /// ```
+    fn t<T>(&self, key: T) -> String
+    where
+        T: ToString,
+    {
+        self.locale().get(key)
+    }
+
+    /// Creates a builder targeting the request's locale, for messages with arguments
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns a builder for the key in the request's locale
+    fn builder<T>(&self, key: T) -> I18nBuilder
+    where
+        T: ToString,
+    {
+        self.locale().builder(key)
+    }
+}
+
+impl ContextExt for Context<'_> {
+    fn locale(&self) -> Locale {
+        match self.data_opt::<Locale>() {
+            Some(locale) => locale.clone(),
+            None => Locale(crate::locale()),
+        }
+    }
+}
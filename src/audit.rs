@@ -0,0 +1,74 @@
+use crate::{ERROR_LOCK, LanguageIdentifier, instance, negotiate};
+use std::collections::BTreeSet;
+
+/// Differences between the catalog and the keys referenced in code
+///
+/// # Fields
+/// * `unused` - Messages defined by an installed locale that no key refers to, sorted
+/// * `missing` - Referenced messages the default locale does not define, sorted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditReport {
+    pub unused: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+impl AuditReport {
+    /// Returns whether every message is referenced and every referenced key defined
+    pub fn is_clean(&self) -> bool {
+        self.unused.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Compares the catalog with the keys referenced in source code
+///
+/// Messages no key refers to are reported as unused, so stale translations
+/// can be pruned before translators spend time on them. A `message.attribute`
+/// key counts as a reference to its message. Keys built at runtime cannot be
+/// found in source, so their messages should be added to `source_keys` by
+/// hand. The keys are typically collected with `i18n::extract::extract_dir`
+/// (`extract` feature).
+///
+/// # Parameters
+/// * `source_keys` - Every key referenced in code
+///
+/// # Return
+/// Returns the unused messages of every installed locale and the referenced
+/// keys missing from the default locale
+///
+/// # Examples
+/// ```
+///
+/// let report = i18n::audit(["hello", "greeting", "login-button.title", "signup"]);
+/// assert!(report.unused.contains(&"farewell".to_string()));
+/// assert_eq!(report.missing, ["signup"]);
+/// ```
+pub fn audit<I, T>(source_keys: I) -> AuditReport
+where
+    I: IntoIterator<Item = T>,
+    T: ToString,
+{
+    let referenced: BTreeSet<String> = source_keys
+        .into_iter()
+        .map(|key| {
+            let key = key.to_string();
+            key.split_once('.').map_or_else(|| key.clone(), |(id, _)| id.to_string())
+        })
+        .collect();
+
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let defined: BTreeSet<&String> = catalog.available().iter().filter_map(|locale| catalog.keys(locale)).flatten().collect();
+    let default = i18n
+        .config
+        .locale
+        .parse::<LanguageIdentifier>()
+        .ok()
+        .and_then(|locale| negotiate::matching(&[&locale], catalog.available()).first().and_then(|locale| catalog.keys(locale)).cloned())
+        .unwrap_or_default();
+
+    AuditReport {
+        unused: defined.into_iter().filter(|key| !referenced.contains(*key)).cloned().collect(),
+        missing: referenced.difference(&default).cloned().collect(),
+    }
+}
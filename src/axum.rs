@@ -0,0 +1,72 @@
+//! Axum integration resolving the request locale
+//!
+//! ```no_run
+//! use axum::{Router, middleware, routing::get};
+//! use i18n::Locale;
+//!
+//! async fn hello(locale: Locale) -> String {
+//!     locale.get("hello")
+//! }
+//!
+//! let app: Router = Router::new()
+//!     .route("/", get(hello))
+//!     .layer(middleware::from_fn(i18n::axum::resolve_locale));
+//! ```
+
+use crate::Locale;
+use crate::negotiate::{LOCALE_PARAM, cookie_value, query_param};
+use crate::task::with_locale;
+use ::axum::extract::{FromRequestParts, Request};
+use ::axum::http::header::{ACCEPT_LANGUAGE, COOKIE};
+use ::axum::http::request::Parts;
+use ::axum::middleware::Next;
+use ::axum::response::Response;
+use std::convert::Infallible;
+
+/// Middleware making the request's locale active while it is handled
+///
+/// The locale is taken from the `lang` query parameter, then the `lang`
+/// cookie, then the `Accept-Language` header, and defaults to the active
+/// locale. It is stored in the request extensions and set as the task-local
+/// locale, so every `i18n::get` call inside the handler uses it. Use it with
+/// `axum::middleware::from_fn`.
+///
+/// # Parameters
+/// * `request` - The incoming request
+/// * `next` - The rest of the middleware stack
+///
+/// # Return
+/// Returns the response of the inner service
+pub async fn resolve_locale(mut request: Request, next: Next) -> Response {
+    let locale = from_parts(&request.uri().query(), request.headers());
+    request.extensions_mut().insert(locale.clone());
+
+    with_locale(locale.0, next.run(request)).await
+}
+
+/// Extracts the request locale
+///
+/// Uses the locale stored by `resolve_locale` when the middleware is installed,
+/// and resolves it from the request otherwise.
+impl<S> FromRequestParts<S> for Locale
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.extensions.get::<Locale>() {
+            Some(locale) => Ok(locale.clone()),
+            None => Ok(from_parts(&parts.uri.query(), &parts.headers)),
+        }
+    }
+}
+
+fn from_parts(query: &Option<&str>, headers: &::axum::http::HeaderMap) -> Locale {
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let query = query.and_then(|query| query_param(query, LOCALE_PARAM));
+    let cookie = header(COOKIE).and_then(|cookie| cookie_value(cookie, LOCALE_PARAM));
+
+    Locale(crate::resolve_locale(header(ACCEPT_LANGUAGE), cookie, query))
+}
@@ -0,0 +1,60 @@
+//! Validates a locale directory for CI pipelines
+//!
+//! ```text
+//! i18n-check [DIR] [--default LOCALE]
+//! ```
+//!
+//! `DIR` and `LOCALE` default to `I18N_DIR` and `I18N_ID`. Syntax errors,
+//! missing keys, and placeholder mismatches are printed, and the exit code is
+//! non-zero when any is found.
+
+use i18n::{I18nConfig, LanguageIdentifier};
+use std::env;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: i18n-check [DIR] [--default LOCALE]";
+
+fn main() -> ExitCode {
+    let mut config = I18nConfig::from_env();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--default" => match args.next() {
+                Some(locale) => config.locale = locale,
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') => return usage(),
+            _ => config.dir = arg,
+        }
+    }
+
+    let Ok(default) = config.locale.parse::<LanguageIdentifier>() else {
+        eprintln!("{}", i18n::I18nError::InvalidLocale(config.locale));
+        return ExitCode::from(2);
+    };
+
+    match i18n::check::check_dir(&config.dir, &default) {
+        Ok(report) if report.is_ok() => {
+            println!("{}: no problems found", config.dir);
+            ExitCode::SUCCESS
+        }
+        Ok(report) => {
+            print!("{report}");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
@@ -0,0 +1,62 @@
+//! Compiles a locale directory into a binary catalog
+//!
+//! ```text
+//! i18n-compile [DIR] --output FILE
+//! ```
+//!
+//! `DIR` defaults to `I18N_DIR`, or `./assets/locales/`. Point `I18N_DIR` at
+//! the written file to load the compiled catalog instead of the directory.
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: i18n-compile [DIR] --output FILE";
+
+fn main() -> ExitCode {
+    let mut dir = None;
+    let mut output = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => match args.next() {
+                Some(file) => output = Some(file),
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') || dir.is_some() => return usage(),
+            _ => dir = Some(arg),
+        }
+    }
+
+    let Some(output) = output else {
+        return usage();
+    };
+    let dir = dir
+        .or_else(|| env::var("I18N_DIR").ok())
+        .unwrap_or_else(|| "./assets/locales/".to_string());
+
+    let catalog = match i18n::compile_catalog(&dir) {
+        Ok(catalog) => catalog,
+        Err(e) => {
+            eprintln!("{dir}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(e) = fs::write(&output, catalog) {
+        eprintln!("{output}: {e}");
+        return ExitCode::from(2);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
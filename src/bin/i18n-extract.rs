@@ -0,0 +1,69 @@
+//! Writes a template FTL file of the translation keys used in Rust source
+//!
+//! ```text
+//! i18n-extract [DIR...] [--output FILE]
+//! ```
+//!
+//! `DIR` defaults to `src`. Every key referenced through `i18n::get`,
+//! `i18n::new`, `t!`, and the like is listed with the places using it, and
+//! the template is printed unless `--output` names a file to write.
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: i18n-extract [DIR...] [--output FILE]";
+
+fn main() -> ExitCode {
+    let mut dirs = Vec::new();
+    let mut output = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => match args.next() {
+                Some(file) => output = Some(file),
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') => return usage(),
+            _ => dirs.push(arg),
+        }
+    }
+
+    if dirs.is_empty() {
+        dirs.push("src".to_string());
+    }
+
+    let mut references = Vec::new();
+    for dir in &dirs {
+        match i18n::extract::extract_dir(dir) {
+            Ok(found) => references.extend(found),
+            Err(e) => {
+                eprintln!("{dir}: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let template = i18n::extract::template(&references);
+    match output {
+        Some(file) => {
+            if let Err(e) = fs::write(&file, template) {
+                eprintln!("{file}: {e}");
+                return ExitCode::from(2);
+            }
+        }
+        None => print!("{template}"),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
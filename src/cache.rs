@@ -0,0 +1,245 @@
+use crate::{Args, ERROR_LOCK, LanguageIdentifier, instance};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Usage of the formatted-message cache
+///
+/// Counters start over whenever the catalog is reloaded.
+///
+/// # Fields
+/// * `hits` - Lookups answered from the cache
+/// * `misses` - Lookups that formatted the message and stored it
+/// * `len` - Number of messages currently cached
+/// * `capacity` - Maximum number of cached messages (0 when the cache is disabled)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Returns the share of lookups answered from the cache
+    ///
+    /// # Return
+    /// Returns a ratio between 0 and 1 (0 before the first lookup)
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let stats = i18n::CacheStats { hits: 3, misses: 1, ..Default::default() };
+    /// assert_eq!(stats.hit_rate(), 0.75);
+    /// ```
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / lookups as f64
+    }
+}
+
+/// Reports how well the formatted-message cache of the global instance works
+///
+/// The cache is enabled by setting `cache` (or `I18N_CACHE`) to its size.
+///
+/// # Return
+/// Returns the hit and miss counts, and how full the cache is
+///
+/// # Examples
+/// ```
+///
+/// let stats = i18n::cache_stats();
+/// println!("{:.0}% of lookups cached", stats.hit_rate() * 100.0);
+/// ```
+pub fn cache_stats() -> CacheStats {
+    instance().catalog.read().expect(ERROR_LOCK).cache().stats()
+}
+
+/// Identifies a formatted message: the requested locale, the key, and a hash of the arguments
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    locale: LanguageIdentifier,
+    key: String,
+    args: u64,
+    isolating: Option<bool>,
+}
+
+/// A cached message, and whether it was found in the requested locale itself
+pub(crate) type Cached = (String, bool);
+
+/// Bounded cache of formatted messages, evicting the least recently used one
+pub(crate) struct Cache {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+/// # Fields
+/// - `entries`: Cached messages with the tick of their last use
+/// - `order`: Keys by the tick of their last use, oldest first
+/// - `tick`: Counter ordering uses
+/// - `hits`: Lookups answered from the cache
+/// - `misses`: Lookups of uncached messages
+#[derive(Default)]
+struct State {
+    entries: HashMap<CacheKey, (Cached, u64)>,
+    order: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl Cache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self { capacity, state: Mutex::new(State::default()) }
+    }
+
+    /// Returns the cache key of a lookup, or `None` if it cannot be cached
+    ///
+    /// Nothing is cached when the cache is disabled or an argument is a
+    /// custom Fluent type, which cannot be hashed.
+    pub(crate) fn key(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&Args>,
+        isolating: Option<bool>,
+    ) -> Option<CacheKey> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        for (name, value) in args.into_iter().flat_map(|args| args.iter()) {
+            name.hash(&mut hasher);
+            match value {
+                FluentValue::String(text) => (0u8, text).hash(&mut hasher),
+                FluentValue::Number(number) => (1u8, number.value.to_bits(), &number.options).hash(&mut hasher),
+                FluentValue::None => 2u8.hash(&mut hasher),
+                FluentValue::Error => 3u8.hash(&mut hasher),
+                FluentValue::Custom(_) => return None,
+            }
+        }
+
+        Some(CacheKey { locale: locale.clone(), key: key.to_string(), args: hasher.finish(), isolating })
+    }
+
+    /// Returns the cached message of `key`, marking it as recently used
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<Cached> {
+        let mut state = self.state.lock().expect(ERROR_LOCK);
+        let state = &mut *state;
+        state.tick += 1;
+
+        let Some((cached, used)) = state.entries.get_mut(key) else {
+            state.misses += 1;
+            return None;
+        };
+
+        state.hits += 1;
+        state.order.remove(used);
+        state.order.insert(state.tick, key.clone());
+        *used = state.tick;
+
+        Some(cached.clone())
+    }
+
+    /// Stores a message, evicting the least recently used one if the cache is full
+    pub(crate) fn insert(&self, key: CacheKey, cached: Cached) {
+        let mut state = self.state.lock().expect(ERROR_LOCK);
+        let state = &mut *state;
+        state.tick += 1;
+
+        if let Some((_, used)) = state.entries.insert(key.clone(), (cached, state.tick)) {
+            state.order.remove(&used);
+        } else if state.entries.len() > self.capacity
+            && let Some((_, oldest)) = state.order.pop_first()
+        {
+            state.entries.remove(&oldest);
+        }
+
+        state.order.insert(state.tick, key);
+    }
+
+    /// Forgets every cached message, e.g. after a function changed how messages format
+    pub(crate) fn clear(&self) {
+        let mut state = self.state.lock().expect(ERROR_LOCK);
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        let state = self.state.lock().expect(ERROR_LOCK);
+
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            len: state.entries.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(cache: &Cache, key: &str) -> CacheKey {
+        cache.key(&"en-US".parse().unwrap(), key, None, None).unwrap()
+    }
+
+    fn cached(text: &str) -> Cached {
+        (text.to_string(), true)
+    }
+
+    #[test]
+    fn least_recently_used_message_is_evicted() {
+        let cache = Cache::new(2);
+        let (a, b, c) = (key(&cache, "a"), key(&cache, "b"), key(&cache, "c"));
+
+        cache.insert(a.clone(), cached("A"));
+        cache.insert(b.clone(), cached("B"));
+        assert_eq!(cache.get(&a), Some(cached("A")));
+
+        cache.insert(c.clone(), cached("C"));
+        assert_eq!(cache.get(&b), None);
+        assert_eq!(cache.get(&a), Some(cached("A")));
+        assert_eq!(cache.get(&c), Some(cached("C")));
+        assert_eq!(cache.stats(), CacheStats { hits: 3, misses: 1, len: 2, capacity: 2 });
+    }
+
+    #[test]
+    fn reinserting_refreshes_without_evicting() {
+        let cache = Cache::new(2);
+        let (a, b, c) = (key(&cache, "a"), key(&cache, "b"), key(&cache, "c"));
+
+        cache.insert(a.clone(), cached("A"));
+        cache.insert(b.clone(), cached("B"));
+        cache.insert(a.clone(), cached("A2"));
+        assert_eq!(cache.stats().len, 2);
+
+        cache.insert(c.clone(), cached("C"));
+        assert_eq!(cache.get(&a), Some(cached("A2")));
+        assert_eq!(cache.get(&b), None);
+    }
+
+    #[test]
+    fn keys_depend_on_arguments() {
+        let cache = Cache::new(1);
+        let locale = "en-US".parse().unwrap();
+        let mut bob = Args::new();
+        bob.set("name", "Bob");
+        let mut ann = Args::new();
+        ann.set("name", "Ann");
+        let mut bob_again = Args::new();
+        bob_again.set("name", "Bob");
+
+        assert_ne!(cache.key(&locale, "greeting", Some(&bob), None), cache.key(&locale, "greeting", Some(&ann), None));
+        assert_eq!(cache.key(&locale, "greeting", Some(&bob), None), cache.key(&locale, "greeting", Some(&bob_again), None));
+        assert_eq!(Cache::new(0).key(&locale, "greeting", None, None), None);
+    }
+}
@@ -0,0 +1,554 @@
+use crate::cache::Cache;
+use crate::diagnostics::{self, Diagnostics, Messages, SyntaxError};
+use crate::{function, negotiate, ordinal, pseudo};
+use crate::source::{DirSource, LocaleSource, layered};
+use crate::{Args, I18nConfig, I18nError, attr_key};
+use fluent_templates::FluentBundle;
+use fluent_syntax::ast::{Entry, Expression, Identifier, InlineExpression, Message, Pattern, PatternElement, Resource};
+use fluent_syntax::serializer;
+use fluent_templates::fluent_bundle::{FluentArgs, FluentError, FluentResource, FluentValue};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, OnceLock};
+use unic_langid::LanguageIdentifier;
+
+pub(crate) type Bundle = FluentBundle<Arc<FluentResource>>;
+
+/// Translation bundles loaded from the configured source
+///
+/// # Fields
+/// - `config`: Settings the catalog was built from, used to build locales later
+/// - `source`: Backend providing the FTL files of each locale
+/// - `libraries`: Catalogs registered by libraries, shadowed by `source`
+/// - `overrides`: Override directories, shadowing `source`
+/// - `available`: Locales provided by the source, sorted
+/// - `locales`: Translations of each locale, built on first use when loading lazily
+/// - `diagnostics`: Problems found while loading, gathered on first use
+/// - `cache`: Recently formatted messages, empty after loading
+pub(crate) struct Catalog {
+    config: I18nConfig,
+    source: Arc<dyn LocaleSource>,
+    libraries: Vec<Arc<dyn LocaleSource>>,
+    overrides: Vec<Arc<dyn LocaleSource>>,
+    available: Vec<LanguageIdentifier>,
+    locales: HashMap<LanguageIdentifier, OnceLock<Translations>>,
+    diagnostics: OnceLock<Diagnostics>,
+    cache: Cache,
+}
+
+/// Translations of one locale
+///
+/// # Fields
+/// - `bundle`: The Fluent bundle, without bidi isolation
+/// - `isolated`: The same bundle with bidi isolation, sharing the resources
+/// - `resources`: The parsed FTL files, kept to rebuild the bundles
+/// - `keys`: Message IDs defined for the locale
+/// - `placeholders`: Placeholders used by each message
+/// - `statics`: Formatted text of each message and attribute without placeables
+/// - `syntax_errors`: FTL syntax errors found in the locale's files
+struct Translations {
+    bundle: Bundle,
+    isolated: Bundle,
+    resources: Vec<Arc<FluentResource>>,
+    keys: BTreeSet<String>,
+    placeholders: Messages,
+    statics: HashMap<String, Arc<str>>,
+    syntax_errors: Vec<SyntaxError>,
+}
+
+impl Catalog {
+    /// Builds a bundle for every locale of the configured source
+    ///
+    /// Messages of `libraries` are added first, so the configured source
+    /// overrides them, and the override directories last, so they shadow
+    /// both. Only locales of the configured source are loaded. With `lazy`,
+    /// only the list of locales is read here, and each locale is built on
+    /// first use.
+    pub(crate) fn load(config: &I18nConfig, libraries: &[Arc<dyn LocaleSource>]) -> Result<Self, I18nError> {
+        let source = source(config)?;
+        let overrides = config.overrides
+            .iter()
+            .map(|dir| dir_source(dir))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut available = source.locales()?;
+        available.sort_by_key(|locale| locale.to_string());
+
+        let catalog = Self {
+            config: config.clone(),
+            source,
+            libraries: libraries.to_vec(),
+            overrides,
+            locales: available.iter().map(|locale| (locale.clone(), OnceLock::new())).collect(),
+            available,
+            diagnostics: OnceLock::new(),
+            cache: Cache::new(config.cache),
+        };
+
+        if !config.lazy {
+            for locale in &catalog.available {
+                let _ = catalog.locales[locale].set(catalog.build(locale)?);
+            }
+        }
+
+        Ok(catalog)
+    }
+
+    /// Builds a catalog without any locale, used when loading failed with `error` under `lenient_init`
+    pub(crate) fn empty(config: &I18nConfig, error: &I18nError) -> Self {
+        Self {
+            config: config.clone(),
+            source: Arc::new(DirSource::new(&config.dir)),
+            libraries: Vec::new(),
+            overrides: Vec::new(),
+            available: Vec::new(),
+            locales: HashMap::new(),
+            diagnostics: OnceLock::from(Diagnostics { load_error: Some(error.to_string()), ..Default::default() }),
+            cache: Cache::new(config.cache),
+        }
+    }
+
+    /// Returns the translations of `locale`, building them on first use
+    ///
+    /// A locale whose files cannot be read on first use is left empty, so
+    /// lookups fall back to the next locale of the chain.
+    fn translations(&self, locale: &LanguageIdentifier) -> Option<&Translations> {
+        let translations = self.locales.get(locale)?;
+
+        Some(translations.get_or_init(|| self.build(locale).unwrap_or_else(|_| self.build_empty(locale))))
+    }
+
+    /// Reads and parses the files of `locale`, and builds its bundles
+    fn build(&self, locale: &LanguageIdentifier) -> Result<Translations, I18nError> {
+        let mut messages = Messages::new();
+        let mut resources = Vec::new();
+        let mut syntax_errors = Vec::new();
+
+        let files = [layered(&self.libraries, locale)?, self.source.files(locale)?, layered(&self.overrides, locale)?];
+
+        for (file, ftl) in files.into_iter().flatten() {
+            // Entries that parsed are kept, and the errors reported as diagnostics
+            let resource = FluentResource::try_new(ftl).unwrap_or_else(|(resource, errors)| {
+                syntax_errors.extend(diagnostics::syntax_errors(&file, resource.source(), &errors));
+                resource
+            });
+            for entry in resource.entries() {
+                if let Entry::Message(message) = entry {
+                    messages.insert(message.id.name.to_string(), diagnostics::placeholders(message));
+                }
+            }
+            resources.push(Arc::new(resource));
+        }
+
+        let isolated = bundle(&self.config, locale, &resources, true);
+        let bundle = bundle(&self.config, locale, &resources, false);
+        let keys = messages.keys().cloned().collect();
+        let statics = self.render_static(&bundle, &keys);
+
+        Ok(Translations {
+            bundle,
+            isolated,
+            resources,
+            keys,
+            placeholders: messages,
+            statics,
+            syntax_errors,
+        })
+    }
+
+    /// Returns translations without any message, for a locale whose files could not be read
+    fn build_empty(&self, locale: &LanguageIdentifier) -> Translations {
+        Translations {
+            bundle: bundle(&self.config, locale, &[], false),
+            isolated: bundle(&self.config, locale, &[], true),
+            resources: Vec::new(),
+            keys: BTreeSet::new(),
+            placeholders: Messages::new(),
+            statics: HashMap::new(),
+            syntax_errors: Vec::new(),
+        }
+    }
+
+    /// Formats the messages and attributes of `bundle` that consist of text only
+    ///
+    /// Their text depends neither on arguments nor on functions, so it is
+    /// formatted once here instead of on every lookup.
+    fn render_static(&self, bundle: &Bundle, keys: &BTreeSet<String>) -> HashMap<String, Arc<str>> {
+        let is_static = |pattern: &Pattern<&str>| {
+            pattern.elements.iter().all(|element| matches!(element, PatternElement::TextElement { .. }))
+        };
+
+        let mut statics = HashMap::new();
+        for id in keys {
+            let Some(message) = bundle.get_message(id) else {
+                continue;
+            };
+
+            if let Some(value) = message.value().filter(|value| is_static(value)) {
+                statics.insert(id.clone(), self.format(bundle, value, None, &mut Vec::new()).into());
+            }
+
+            for attr in message.attributes().filter(|attr| is_static(attr.value())) {
+                statics.insert(attr_key(id, attr.id()), self.format(bundle, attr.value(), None, &mut Vec::new()).into());
+            }
+        }
+
+        statics
+    }
+
+    /// Returns the memoized text of `key` (or `message.attribute`) in exactly one locale
+    ///
+    /// Only messages without placeables are memoized, so `None` does not mean
+    /// the message is missing.
+    pub(crate) fn shared(&self, locale: &LanguageIdentifier, key: &str) -> Option<Arc<str>> {
+        self.translations(locale)?.statics.get(key).cloned()
+    }
+
+    /// Rebuilds the bundles built so far with the registered functions
+    ///
+    /// Bundles built later install the functions themselves. Rebuilding them,
+    /// rather than adding to them, lets a registered function replace a
+    /// built-in one such as `ORDINAL`.
+    pub(crate) fn install_functions(&mut self) {
+        for (locale, translations) in &mut self.locales {
+            let Some(translations) = translations.get_mut() else {
+                continue;
+            };
+
+            translations.bundle = bundle(&self.config, locale, &translations.resources, false);
+            translations.isolated = bundle(&self.config, locale, &translations.resources, true);
+        }
+
+        self.cache.clear();
+    }
+
+    pub(crate) fn available(&self) -> &[LanguageIdentifier] {
+        &self.available
+    }
+
+    pub(crate) fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Returns the problems found in the translations, building every locale not built yet
+    pub(crate) fn diagnostics(&self) -> &Diagnostics {
+        self.diagnostics.get_or_init(|| {
+            let syntax_errors = self.available
+                .iter()
+                .filter_map(|locale| self.translations(locale))
+                .flat_map(|translations| translations.syntax_errors.iter().cloned())
+                .collect();
+
+            // Translations are checked against the installed locale matching the default one
+            let mut diagnostics = Diagnostics { syntax_errors, ..Default::default() };
+            let default = self.config.locale
+                .parse::<LanguageIdentifier>()
+                .ok()
+                .and_then(|locale| negotiate::matching(&[locale], &self.available).first().copied())
+                .and_then(|default| Some((default, self.translations(default)?)));
+
+            if let Some((default, expected)) = default {
+                for locale in self.available.iter().filter(|locale| *locale != default) {
+                    if let Some(translations) = self.translations(locale) {
+                        diagnostics::compare(locale, &expected.placeholders, &translations.placeholders, &mut diagnostics.mismatches);
+                    }
+                }
+            }
+
+            diagnostics
+        })
+    }
+
+    /// Returns the message IDs defined for `locale`, sorted
+    pub(crate) fn keys(&self, locale: &LanguageIdentifier) -> Option<&BTreeSet<String>> {
+        self.translations(locale).map(|translations| &translations.keys)
+    }
+
+    /// Returns whether `key` (or `message.attribute`) is defined in exactly one locale
+    pub(crate) fn contains(&self, locale: &LanguageIdentifier, key: &str) -> bool {
+        let Some(bundle) = self.bundle(locale, None) else {
+            return false;
+        };
+
+        match key.split_once('.') {
+            Some((message, attr)) => bundle.get_message(message).is_some_and(|m| m.get_attribute(attr).is_some()),
+            None => bundle.get_message(key).is_some_and(|m| m.value().is_some()),
+        }
+    }
+
+    /// Returns the bundle of `locale`, with or without bidi isolation
+    fn bundle(&self, locale: &LanguageIdentifier, isolating: Option<bool>) -> Option<&Bundle> {
+        let translations = self.translations(locale)?;
+
+        if isolating.unwrap_or(self.config.use_isolating) {
+            Some(&translations.isolated)
+        } else {
+            Some(&translations.bundle)
+        }
+    }
+
+    /// Looks up `key` (or `message.attribute`) in exactly one locale
+    ///
+    /// `isolating` overrides the configured bidi isolation for this lookup.
+    /// Returns `None` if the message is missing or fails to format.
+    pub(crate) fn lookup_single(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&Args>,
+        isolating: Option<bool>,
+    ) -> Option<String> {
+        self.format_single(locale, key, args, isolating)?.ok()
+    }
+
+    /// Formats `key` (or `message.attribute`) in exactly one locale
+    ///
+    /// Returns `None` if the message is missing, and the formatting errors if
+    /// it exists but fails to format.
+    pub(crate) fn format_single(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&Args>,
+        isolating: Option<bool>,
+    ) -> Option<Result<String, Vec<FluentError>>> {
+        if let Some(text) = self.translations(locale).and_then(|translations| translations.statics.get(key)) {
+            return Some(Ok(text.to_string()));
+        }
+
+        let bundle = self.bundle(locale, isolating)?;
+
+        let pattern = match key.split_once('.') {
+            Some((message, attr)) => bundle.get_message(message)?.get_attribute(attr)?.value(),
+            None => bundle.get_message(key)?.value()?,
+        };
+
+        let mut errors = Vec::new();
+        let value = self.format(bundle, pattern, args, &mut errors);
+
+        Some(if errors.is_empty() { Ok(value) } else { Err(errors) })
+    }
+
+    /// Formats the term `id` (without its `-`), or one of its attributes, in exactly one locale
+    ///
+    /// Returns `None` if the term is missing or fails to format.
+    pub(crate) fn format_term(&self, locale: &LanguageIdentifier, id: &str, attr: Option<&str>) -> Option<String> {
+        let bundle = self.bundle(locale, None)?;
+
+        let reference = InlineExpression::TermReference {
+            id: Identifier { name: id },
+            attribute: attr.map(|name| Identifier { name }),
+            arguments: None,
+        };
+        let pattern = Pattern {
+            elements: vec![PatternElement::Placeable { expression: Expression::Inline(reference) }],
+        };
+
+        let mut errors = Vec::new();
+        let value = self.format(bundle, &pattern, None, &mut errors);
+
+        errors.is_empty().then_some(value)
+    }
+
+    /// Formats every message and attribute of exactly one locale, without arguments, with `render`
+    ///
+    /// `render` receives the pattern and a function formatting any pattern of
+    /// the locale, such as one variant of a select. Attributes are keyed
+    /// `message.attribute`. Formatting errors are ignored, so placeables
+    /// referring to arguments render as `{$name}`.
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    pub(crate) fn render_all<F, T>(&self, locale: &LanguageIdentifier, render: F) -> Vec<(String, T)>
+    where
+        F: Fn(&Pattern<&str>, &dyn Fn(&Pattern<&str>) -> String) -> T,
+    {
+        let Some(bundle) = self.bundle(locale, None) else {
+            return Vec::new();
+        };
+
+        let format = |pattern: &Pattern<&str>| self.format(bundle, pattern, None, &mut Vec::new());
+        let mut messages = Vec::new();
+        for id in self.keys(locale).into_iter().flatten() {
+            let Some(message) = bundle.get_message(id) else {
+                continue;
+            };
+
+            if let Some(value) = message.value() {
+                messages.push((id.clone(), render(value, &format)));
+            }
+
+            for attr in message.attributes() {
+                messages.push((attr_key(id, attr.id()), render(attr.value(), &format)));
+            }
+        }
+
+        messages
+    }
+
+    /// Returns the FTL source of every message and attribute of exactly one locale
+    ///
+    /// Attributes are keyed `message.attribute`. Multiline patterns are
+    /// returned without their indentation.
+    #[cfg_attr(not(any(feature = "csv", feature = "xliff")), allow(dead_code))]
+    pub(crate) fn patterns(&self, locale: &LanguageIdentifier) -> Vec<(String, String)> {
+        self.convert(locale, |_, _, pattern| pattern_source(pattern))
+    }
+
+    /// Converts every message and attribute of exactly one locale with `convert`
+    ///
+    /// `convert` receives the key, the locale's bundle, without bidi isolation,
+    /// to resolve what it cannot convert, and the pattern. Attributes are keyed
+    /// `message.attribute`.
+    #[cfg_attr(not(any(feature = "arb", feature = "csv", feature = "mobile", feature = "xliff")), allow(dead_code))]
+    pub(crate) fn convert<F, T>(&self, locale: &LanguageIdentifier, convert: F) -> Vec<(String, T)>
+    where
+        F: Fn(&str, &Bundle, &Pattern<&str>) -> T,
+    {
+        let Some(bundle) = self.bundle(locale, Some(false)) else {
+            return Vec::new();
+        };
+
+        let mut converted = Vec::new();
+        for id in self.keys(locale).into_iter().flatten() {
+            let Some(message) = bundle.get_message(id) else {
+                continue;
+            };
+
+            if let Some(value) = message.value() {
+                converted.push((id.clone(), convert(id, bundle, value)));
+            }
+
+            for attr in message.attributes() {
+                let key = attr_key(id, attr.id());
+                let value = convert(&key, bundle, attr.value());
+                converted.push((key, value));
+            }
+        }
+
+        converted
+    }
+
+    /// Converts `key` (or `message.attribute`) of exactly one locale with `convert`
+    ///
+    /// `convert` receives the locale's bundle, without bidi isolation, and the
+    /// pattern. Returns `None` if the message is missing.
+    pub(crate) fn convert_single<F, T>(&self, locale: &LanguageIdentifier, key: &str, convert: F) -> Option<T>
+    where
+        F: FnOnce(&Bundle, &Pattern<&str>) -> T,
+    {
+        let bundle = self.bundle(locale, Some(false))?;
+
+        let pattern = match key.split_once('.') {
+            Some((message, attr)) => bundle.get_message(message)?.get_attribute(attr)?.value(),
+            None => bundle.get_message(key)?.value()?,
+        };
+
+        Some(convert(bundle, pattern))
+    }
+
+    /// Formats every message and attribute of exactly one locale with arguments
+    ///
+    /// `args` is called with the message ID and the name of every placeholder
+    /// the message uses. Attributes are keyed `message.attribute`.
+    pub(crate) fn render_with<F>(&self, locale: &LanguageIdentifier, args: F) -> Vec<(String, Result<String, Vec<FluentError>>)>
+    where
+        F: Fn(&str, &str) -> FluentValue<'static>,
+    {
+        let (Some(bundle), Some(translations)) = (self.bundle(locale, None), self.translations(locale)) else {
+            return Vec::new();
+        };
+
+        let mut rendered = Vec::new();
+        for (id, placeholders) in &translations.placeholders {
+            let Some(message) = bundle.get_message(id) else {
+                continue;
+            };
+
+            let args: FluentArgs = placeholders
+                .iter()
+                .map(|name| (name.as_str(), args(id, name)))
+                .collect();
+
+            let patterns = message.value()
+                .map(|value| (id.clone(), value))
+                .into_iter()
+                .chain(message.attributes().map(|attr| (attr_key(id, attr.id()), attr.value())));
+
+            for (key, pattern) in patterns {
+                let mut errors = Vec::new();
+                let value = self.format(bundle, pattern, Some(&args), &mut errors);
+                rendered.push((key, if errors.is_empty() { Ok(value) } else { Err(errors) }));
+            }
+        }
+
+        rendered
+    }
+
+    fn format(&self, bundle: &Bundle, pattern: &Pattern<&str>, args: Option<&FluentArgs>, errors: &mut Vec<FluentError>) -> String {
+        let value = bundle.format_pattern(pattern, args, errors).into_owned();
+
+        if self.config.pseudo { pseudo::wrap(value) } else { value }
+    }
+}
+
+/// Serializes a pattern back to FTL, as written after `key = `
+#[cfg_attr(not(any(feature = "csv", feature = "tms", feature = "xliff")), allow(dead_code))]
+pub(crate) fn pattern_source(pattern: &Pattern<&str>) -> String {
+    let message = Message {
+        id: Identifier { name: "x" },
+        value: Some(pattern.clone()),
+        attributes: Vec::new(),
+        comment: None,
+    };
+
+    let ftl = serializer::serialize(&Resource { body: vec![Entry::Message(message)] });
+    let pattern = ftl.strip_prefix("x =").unwrap_or(&ftl);
+    let pattern = pattern.strip_prefix([' ', '\n']).unwrap_or(pattern);
+
+    pattern
+        .trim_end()
+        .lines()
+        .map(|line| line.strip_prefix("    ").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Builds the bundle of `locale` from its parsed resources
+///
+/// Later resources shadow messages of earlier ones with the same key.
+fn bundle(config: &I18nConfig, locale: &LanguageIdentifier, resources: &[Arc<FluentResource>], isolating: bool) -> Bundle {
+    let mut bundle = Bundle::new_concurrent(vec![locale.clone()]);
+    bundle.set_use_isolating(isolating);
+    if config.pseudo {
+        bundle.set_transform(Some(pseudo::transform));
+    }
+    function::install(&mut bundle);
+    ordinal::install(&mut bundle, locale);
+
+    for resource in resources {
+        bundle.add_resource_overriding(Arc::clone(resource));
+    }
+
+    bundle
+}
+
+fn source(config: &I18nConfig) -> Result<Arc<dyn LocaleSource>, I18nError> {
+    match &config.source {
+        Some(source) => Ok(Arc::clone(source)),
+        None => dir_source(&config.dir),
+    }
+}
+
+/// Reads `dir`, or the compiled catalog it names with the `compile` feature
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn dir_source(dir: &str) -> Result<Arc<dyn LocaleSource>, I18nError> {
+    #[cfg(feature = "compile")]
+    if std::path::Path::new(dir).is_file() {
+        return Ok(Arc::new(crate::CompiledSource::open(dir)?));
+    }
+
+    Ok(Arc::new(DirSource::new(dir)))
+}
+
+/// Browsers have no filesystem, so no locale is installed unless a `source` is configured
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn dir_source(_dir: &str) -> Result<Arc<dyn LocaleSource>, I18nError> {
+    Ok(Arc::new(crate::source::NoSource))
+}
@@ -0,0 +1,139 @@
+//! Validation of locale directories, as run by the `i18n-check` binary
+//!
+//! ```no_run
+//! let locale = "en-US".parse().unwrap();
+//! let report = i18n::check::check_dir("./assets/locales/", &locale).unwrap();
+//!
+//! if !report.is_ok() {
+//!     eprintln!("{report}");
+//! }
+//! ```
+
+use crate::diagnostics::{self, Messages};
+use crate::source::DirSource;
+use crate::{I18nError, LanguageIdentifier, LocaleSource};
+use fluent_syntax::ast::Entry;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+pub use crate::diagnostics::{PlaceholderMismatch, SyntaxError};
+
+/// Problems found in a locale directory
+///
+/// # Fields
+/// * `syntax_errors` - FTL syntax errors, in file order
+/// * `missing` - Messages of the default locale absent from other locales
+/// * `mismatches` - Messages whose placeholders differ from the default locale
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub syntax_errors: Vec<SyntaxError>,
+    pub missing: Vec<MissingKeys>,
+    pub mismatches: Vec<PlaceholderMismatch>,
+}
+
+/// Messages of the default locale a locale does not define
+///
+/// # Fields
+/// * `locale` - The incomplete locale
+/// * `keys` - The missing message IDs, sorted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingKeys {
+    pub locale: LanguageIdentifier,
+    pub keys: Vec<String>,
+}
+
+impl Report {
+    /// Returns whether no problem was found
+    pub fn is_ok(&self) -> bool {
+        self.syntax_errors.is_empty() && self.missing.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for error in &self.syntax_errors {
+            writeln!(f, "{error}")?;
+        }
+
+        for missing in &self.missing {
+            writeln!(f, "{}: missing {}", missing.locale, missing.keys.join(", "))?;
+        }
+
+        for mismatch in &self.mismatches {
+            writeln!(f, "{mismatch}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates every FTL file of a locale directory
+///
+/// Each locale is compared against `default`: messages it lacks are reported
+/// as missing, and messages using other placeholders as mismatches. Files
+/// with syntax errors are still checked for the entries that parsed.
+///
+/// # Parameters
+/// * `dir` - Directory containing one sub-directory of FTL files per locale
+/// * `default` - The locale other locales are compared against
+///
+/// # Return
+/// Returns the problems found, or an error if the directory cannot be read or
+/// has no sub-directory for `default`
+pub fn check_dir<P>(dir: P, default: &LanguageIdentifier) -> Result<Report, I18nError>
+where
+    P: AsRef<Path>,
+{
+    let source = DirSource::new(dir.as_ref());
+    let mut locales = source.locales()?;
+    locales.sort_by_key(|locale| locale.to_string());
+
+    if !locales.contains(default) {
+        return Err(I18nError::Loader(format!("{}: no {default} directory", dir.as_ref().display())));
+    }
+
+    let mut report = Report::default();
+    let mut catalogs = BTreeMap::new();
+    for locale in &locales {
+        let mut messages = Messages::new();
+
+        for (path, ftl) in source.files(locale)? {
+            parse_file(&path, &ftl, &mut messages, &mut report.syntax_errors);
+        }
+
+        catalogs.insert(locale.to_string(), (locale, messages));
+    }
+
+    let reference = &catalogs[&default.to_string()].1;
+    for (locale, messages) in catalogs.values().filter(|(locale, _)| *locale != default) {
+        let keys: Vec<String> = reference
+            .keys()
+            .filter(|key| !messages.contains_key(*key))
+            .cloned()
+            .collect();
+
+        if !keys.is_empty() {
+            report.missing.push(MissingKeys { locale: (*locale).clone(), keys });
+        }
+
+        diagnostics::compare(locale, reference, messages, &mut report.mismatches);
+    }
+
+    Ok(report)
+}
+
+/// Records the placeholders of every message of the file at `path`, and its syntax errors
+fn parse_file(path: &Path, source: &str, messages: &mut Messages, errors: &mut Vec<SyntaxError>) {
+    let resource = fluent_syntax::parser::parse(source).unwrap_or_else(|(resource, parse_errors)| {
+        errors.extend(diagnostics::syntax_errors(path, source, &parse_errors));
+        resource
+    });
+
+    for entry in resource.body {
+        if let Entry::Message(message) = entry {
+            // Later files shadow messages of earlier ones, as in the loader
+            messages.insert(message.id.name.to_string(), diagnostics::placeholders(&message));
+        }
+    }
+}
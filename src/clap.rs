@@ -0,0 +1,157 @@
+//! Clap integration localizing help texts and errors
+//!
+//! Help texts are looked up by command path and argument ID, so one FTL file
+//! drives the `--help` output of every language:
+//!
+//! ```ftl
+//! mytool-about = Synchronizes your files
+//! mytool-verbose-help = Print every file as it is copied
+//! mytool-push-about = Upload local changes
+//! clap-error-unknown-argument = unexpected argument { $invalid-arg }
+//! ```
+//!
+//! ```no_run
+//! use clap::{Arg, ArgAction, Command};
+//!
+//! let command = Command::new("mytool")
+//!     .arg(Arg::new("verbose").long("verbose").action(ArgAction::SetTrue))
+//!     .subcommand(Command::new("push"));
+//!
+//! let matches = i18n::clap::localize(command)
+//!     .try_get_matches()
+//!     .unwrap_or_else(|e| i18n::clap::localize_error(e).exit());
+//! ```
+
+use crate::{get_opt, new};
+use ::clap::error::{ContextValue, Error, ErrorKind};
+use ::clap::Command;
+
+/// Prefix of the keys of localized error messages
+const ERROR_PREFIX: &str = "clap-error";
+
+/// Replaces the help texts of a command and its subcommands with translations
+///
+/// Keys are made of the command path, the command's name followed by the
+/// names of its subcommands joined with `-`:
+/// - `{path}-about` and `{path}-long-about` for the command description
+/// - `{path}-{id}-help` and `{path}-{id}-long-help` for the argument `id`
+///
+/// Texts without a translation keep their value, and the built-in `help` and
+/// `version` arguments and `help` subcommand can be translated the same way.
+/// Calls `Command::build`, so arguments added afterwards are not localized.
+///
+/// # Parameters
+/// * `command` - The command to localize
+///
+/// # Return
+/// Returns the command with translated help texts in the active locale
+///
+/// # Examples
+/// ```
+///
+/// // mytool-verbose-help = Print every file as it is copied
+/// let command = clap::Command::new("mytool")
+///     .arg(clap::Arg::new("verbose").long("verbose"));
+///
+/// let command = i18n::clap::localize(command);
+/// ```
+pub fn localize(mut command: Command) -> Command {
+    command.build();
+
+    let path = command.get_name().to_string();
+    localize_at(command, &path)
+}
+
+/// Replaces the message of a parsing error with its translation
+///
+/// The key is `clap-error-` followed by the error kind in kebab case (e.g.
+/// `clap-error-unknown-argument`, `clap-error-missing-required-argument`, or
+/// `clap-error-invalid-value`), and the error's context is passed as
+/// arguments in kebab case too, such as `$invalid-arg`, `$invalid-value`,
+/// `$valid-value`, and `$suggested-arg`. Errors without a translation, and
+/// requests to display help or the version, are kept as they are.
+///
+/// # Parameters
+/// * `error` - The error returned by clap
+///
+/// # Return
+/// Returns the error with its message translated in the active locale
+///
+/// # Examples
+/// ```
+///
+/// let error = clap::Command::new("mytool")
+///     .try_get_matches_from(["mytool", "--force"])
+///     .unwrap_err();
+///
+/// let error = i18n::clap::localize_error(error);
+/// ```
+pub fn localize_error(error: Error) -> Error {
+    let kind = error.kind();
+    if matches!(kind, ErrorKind::DisplayHelp | ErrorKind::DisplayVersion | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand) {
+        return error;
+    }
+
+    let mut builder = new(format!("{ERROR_PREFIX}-{}", kebab_case(&format!("{kind:?}"))));
+    for (context, value) in error.context() {
+        let name = kebab_case(&format!("{context:?}"));
+        builder = match value {
+            ContextValue::Number(number) => builder.set_args(name, *number),
+            value => builder.set_args(name, value.to_string()),
+        };
+    }
+
+    match builder.build_opt() {
+        Some(message) => Error::raw(kind, format!("{message}\n")),
+        None => error,
+    }
+}
+
+fn localize_at(mut command: Command, path: &str) -> Command {
+    if let Some(about) = get_opt(format!("{path}-about")) {
+        command = command.about(about);
+    }
+    if let Some(long_about) = get_opt(format!("{path}-long-about")) {
+        command = command.long_about(long_about);
+    }
+
+    let ids: Vec<String> = command.get_arguments().map(|arg| arg.get_id().to_string()).collect();
+    for id in ids {
+        let help = get_opt(format!("{path}-{id}-help"));
+        let long_help = get_opt(format!("{path}-{id}-long-help"));
+
+        if help.is_some() || long_help.is_some() {
+            command = command.mut_arg(id, |mut arg| {
+                if let Some(help) = help {
+                    arg = arg.help(help);
+                }
+                if let Some(long_help) = long_help {
+                    arg = arg.long_help(long_help);
+                }
+
+                arg
+            });
+        }
+    }
+
+    let names: Vec<String> = command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()).collect();
+    for name in names {
+        let path = format!("{path}-{name}");
+        command = command.mut_subcommand(name, |subcommand| localize_at(subcommand, &path));
+    }
+
+    command
+}
+
+/// Converts a Rust variant name to kebab case (`UnknownArgument` is "unknown-argument")
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            kebab.push('-');
+        }
+        kebab.push(c.to_ascii_lowercase());
+    }
+
+    kebab
+}
@@ -0,0 +1,100 @@
+//! Build script helpers generating key constants from FTL files
+//!
+//! ```no_run
+//! // In build.rs
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("keys.rs");
+//! i18n::codegen::write_keys("./assets/locales/en-US/", out).unwrap();
+//! ```
+//!
+//! The generated module is then included in the application:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/keys.rs"));
+//!
+//! let message = i18n::get(keys::WELCOME_MESSAGE);
+//! ```
+
+use fluent_syntax::ast::Entry;
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Generates a `keys` module with one `const` per message ID
+///
+/// # Parameters
+/// * `dir` - Directory containing the FTL files of a single locale
+///
+/// # Return
+/// Returns the Rust source of the module, with constants sorted by name
+///
+/// # Examples
+/// ```
+///
+/// let source = i18n::codegen::generate_keys("./assets/locales/en-US/").unwrap();
+/// // Contains `pub const WELCOME_MESSAGE: &str = "welcome_message";`
+/// ```
+pub fn generate_keys<P>(dir: P) -> io::Result<String>
+where
+    P: AsRef<Path>,
+{
+    let mut source = String::from("pub mod keys {\n");
+
+    for key in message_ids(dir.as_ref())? {
+        let _ = writeln!(source, "    pub const {}: &str = {key:?};", const_name(&key));
+    }
+
+    source.push_str("}\n");
+    Ok(source)
+}
+
+/// Generates the `keys` module and writes it to `out`
+///
+/// Also prints `cargo:rerun-if-changed` for `dir`, so a build script using it
+/// reruns whenever the FTL files change.
+///
+/// # Parameters
+/// * `dir` - Directory containing the FTL files of a single locale
+/// * `out` - Path of the generated Rust file
+///
+/// # Return
+/// Returns an error if the directory cannot be read or the file cannot be written
+pub fn write_keys<P, Q>(dir: P, out: Q) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    println!("cargo:rerun-if-changed={}", dir.as_ref().display());
+
+    fs::write(out, generate_keys(dir)?)
+}
+
+fn message_ids(dir: &Path) -> io::Result<BTreeSet<String>> {
+    let mut ids = BTreeSet::new();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "ftl") {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)?;
+        let resource = fluent_syntax::parser::parse(source.as_str())
+            .unwrap_or_else(|(resource, _)| resource);
+
+        for entry in resource.body {
+            if let Entry::Message(message) = entry {
+                ids.insert(message.id.name.to_string());
+            }
+        }
+    }
+
+    Ok(ids)
+}
+
+fn const_name(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
@@ -0,0 +1,234 @@
+use crate::diagnostics;
+use crate::source::{DirSource, loader_error};
+use crate::{I18nError, LanguageIdentifier, LocaleSource};
+use fluent_syntax::ast::{Entry, Resource};
+use fluent_syntax::{parser, serializer};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// First bytes of a compiled catalog, followed by the format version
+const MAGIC: &[u8] = b"I18NCAT\0";
+const VERSION: u8 = 1;
+
+/// Compiles a locale directory into a single binary catalog
+///
+/// Every file is parsed and checked, and written back without comments or
+/// formatting, so loading the catalog reads one file instead of walking the
+/// directory and its FTL is smaller and faster to parse. Fluent bundles can
+/// only be built from FTL text, which is why the catalog stores validated FTL
+/// rather than the parsed syntax tree. JSON and YAML files are converted when
+/// their features are enabled, like `DirSource` does.
+///
+/// Point `I18N_DIR` (or `dir`) at the written file to load it instead of the
+/// directory, or read it with `CompiledSource`.
+///
+/// # Parameters
+/// * `dir` - Directory containing one sub-directory of FTL files per locale
+///
+/// # Return
+/// Returns the compiled catalog, or an error if a file cannot be read or has
+/// a syntax error
+///
+/// # Examples
+/// ```
+///
+/// let catalog = i18n::compile_catalog("./assets/locales/").unwrap();
+/// std::fs::write("target/locales.bin", catalog).unwrap();
+///
+/// i18n::init_with(i18n::I18nConfig {
+///     dir: "target/locales.bin".to_string(),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(i18n::get("hello"), "Hello");  // Read from the compiled catalog
+/// ```
+pub fn compile_catalog<P>(dir: P) -> Result<Vec<u8>, I18nError>
+where
+    P: AsRef<Path>,
+{
+    let source = DirSource::new(dir.as_ref());
+    let mut locales = source.locales()?;
+    locales.sort_by_key(|locale| locale.to_string());
+
+    let mut catalog = MAGIC.to_vec();
+    catalog.push(VERSION);
+    write_len(&mut catalog, locales.len());
+
+    for locale in &locales {
+        let files = source.files(locale)?;
+
+        write_str(&mut catalog, &locale.to_string());
+        write_len(&mut catalog, files.len());
+
+        for (file, ftl) in files {
+            write_str(&mut catalog, &file.to_string_lossy());
+            write_str(&mut catalog, &minify(&file, &ftl)?);
+        }
+    }
+
+    Ok(catalog)
+}
+
+/// Parses `ftl` and serializes it again without comments
+fn minify(file: &Path, ftl: &str) -> Result<String, I18nError> {
+    let resource = parser::parse(ftl).map_err(|(_, errors)| {
+        let errors: Vec<String> = diagnostics::syntax_errors(file, ftl, &errors).map(|e| e.to_string()).collect();
+        I18nError::Loader(errors.join("; "))
+    })?;
+
+    let body = resource.body
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Entry::Message(mut message) => {
+                message.comment = None;
+                Some(Entry::Message(message))
+            }
+            Entry::Term(mut term) => {
+                term.comment = None;
+                Some(Entry::Term(term))
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(serializer::serialize(&Resource { body }))
+}
+
+/// Reads the translations of a catalog written by `compile_catalog`
+///
+/// Used automatically when `I18N_DIR` (or `dir`) names a file instead of a
+/// directory, and usable as a custom `source` otherwise.
+///
+/// # Examples
+/// ```
+///
+/// std::fs::write("target/source.bin", i18n::compile_catalog("./assets/locales/").unwrap()).unwrap();
+///
+/// let source = i18n::CompiledSource::open("target/source.bin").unwrap();
+/// i18n::init_with(i18n::I18nConfig {
+///     source: Some(std::sync::Arc::new(source)),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledSource {
+    locales: Vec<(LanguageIdentifier, Vec<(PathBuf, String)>)>,
+}
+
+impl CompiledSource {
+    /// Reads a compiled catalog from a file
+    ///
+    /// # Parameters
+    /// * `path` - The file written with the output of `compile_catalog`
+    ///
+    /// # Return
+    /// Returns the source, or an error if the file cannot be read or is not a
+    /// compiled catalog
+    pub fn open<P>(path: P) -> Result<Self, I18nError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| loader_error(path, e))?;
+
+        Self::from_bytes(&bytes).map_err(|e| match e {
+            I18nError::Loader(reason) => I18nError::Loader(format!("{}: {reason}", path.display())),
+            e => e,
+        })
+    }
+
+    /// Reads a compiled catalog from memory, e.g. embedded with `include_bytes!`
+    ///
+    /// # Parameters
+    /// * `bytes` - The output of `compile_catalog`
+    ///
+    /// # Return
+    /// Returns the source, or an error if the bytes are not a compiled catalog
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, I18nError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(I18nError::Loader("not a compiled catalog".to_string()));
+        }
+
+        let version = reader.take(1)?[0];
+        if version != VERSION {
+            return Err(I18nError::Loader(format!("unsupported compiled catalog version {version}")));
+        }
+
+        let mut locales = Vec::new();
+        for _ in 0..reader.count()? {
+            let name = reader.str()?;
+            let locale = name.parse().map_err(|_| I18nError::InvalidLocale(name.clone()))?;
+
+            let mut files = Vec::new();
+            for _ in 0..reader.count()? {
+                files.push((PathBuf::from(reader.str()?), reader.str()?));
+            }
+
+            locales.push((locale, files));
+        }
+
+        Ok(Self { locales })
+    }
+}
+
+impl LocaleSource for CompiledSource {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        Ok(self.locales.iter().map(|(locale, _)| locale.clone()).collect())
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        Ok(self.files(locale)?.into_iter().map(|(_, ftl)| ftl).collect())
+    }
+
+    fn files(&self, locale: &LanguageIdentifier) -> Result<Vec<(PathBuf, String)>, I18nError> {
+        let files = self.locales
+            .iter()
+            .filter(|(l, _)| l == locale)
+            .flat_map(|(_, files)| files.iter().cloned())
+            .collect();
+
+        Ok(files)
+    }
+}
+
+fn write_len(catalog: &mut Vec<u8>, len: usize) {
+    catalog.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn write_str(catalog: &mut Vec<u8>, text: &str) {
+    write_len(catalog, text.len());
+    catalog.extend_from_slice(text.as_bytes());
+}
+
+/// Reads the length-prefixed fields of a compiled catalog
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], I18nError> {
+        if self.bytes.len() < len {
+            return Err(I18nError::Loader("truncated compiled catalog".to_string()));
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+
+    fn count(&mut self) -> Result<usize, I18nError> {
+        let bytes = self.take(4)?;
+
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+
+    fn str(&mut self) -> Result<String, I18nError> {
+        let len = self.count()?;
+
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| I18nError::Loader("invalid UTF-8 in compiled catalog".to_string()))
+    }
+}
@@ -0,0 +1,206 @@
+#[cfg(feature = "sys-locale")]
+use crate::LanguageIdentifier;
+use crate::{LocaleSource, MissingKeyPolicy};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+const DEFAULT_LANG: &str = "en-US";
+const DEFAULT_DIR: &str = "./assets/locales/";
+
+/// Settings used to build the translation loader
+///
+/// # Fields
+/// * `locale` - The language identifier used for lookups (e.g., "en-US")
+/// * `fallback` - Ordered language identifiers tried when a key is missing in `locale`
+/// * `dir` - Directory containing one sub-directory of FTL files per locale
+/// * `overrides` - Directories laid out like `dir` whose messages shadow it, later ones winning
+/// * `tenants` - Directory containing one override directory per tenant, used by `tenant`
+/// * `aliases` - Locale identifiers mapped to installed ones (e.g., "no" to "nb-NO")
+/// * `use_isolating` - Whether to wrap placeables in Unicode bidi isolation marks
+/// * `source` - Custom backend providing the FTL sources, read instead of `dir`
+/// * `missing` - What lookups return when a message is missing
+/// * `pseudo` - Whether to pseudo-localize every message (e.g. "[!!! Ḩéļļö !!!]")
+/// * `strict` - Whether lookups panic on missing messages and locales and on messages that fail to format
+/// * `suggest` - Whether misses name the closest existing key (e.g. "did you mean `welcome-message`?")
+/// * `lenient_init` - Whether a catalog that fails to load (e.g. a missing `dir`) is replaced by an empty one, returning keys as-is
+/// * `lazy` - Whether each locale is loaded on first use instead of at startup
+/// * `cache` - Number of formatted messages kept in an LRU cache, keyed by locale, key, and arguments (0 disables it)
+///
+/// # Examples
+/// ```
+///
+/// let config = i18n::I18nConfig {
+///     locale: "es-MX".to_string(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct I18nConfig {
+    pub locale: String,
+    pub fallback: Vec<String>,
+    pub dir: String,
+    pub overrides: Vec<String>,
+    pub tenants: Option<String>,
+    pub aliases: HashMap<String, String>,
+    pub use_isolating: bool,
+    pub source: Option<Arc<dyn LocaleSource>>,
+    pub missing: MissingKeyPolicy,
+    pub pseudo: bool,
+    pub strict: bool,
+    pub suggest: bool,
+    pub lenient_init: bool,
+    pub lazy: bool,
+    pub cache: usize,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            locale: DEFAULT_LANG.to_string(),
+            fallback: vec![DEFAULT_LANG.to_string()],
+            dir: DEFAULT_DIR.to_string(),
+            overrides: Vec::new(),
+            tenants: None,
+            aliases: HashMap::new(),
+            use_isolating: false,
+            source: None,
+            missing: MissingKeyPolicy::default(),
+            pseudo: false,
+            strict: false,
+            suggest: false,
+            lenient_init: false,
+            lazy: false,
+            cache: 0,
+        }
+    }
+}
+
+impl I18nConfig {
+    /// Reads the configuration from environment variables
+    ///
+    /// # Environment Variables
+    /// - `I18N_ID`: The language identifier (e.g., "en-US"), or a comma-separated
+    ///   preference list (e.g., "es-MX,es,en-US") whose later entries lead the
+    ///   fallback chain; with the `sys-locale` feature, the operating system's
+    ///   locale is used when unset
+    /// - `I18N_FALLBACK`: Comma-separated fallback chain (e.g., "fr,en-US"), tried after the extra `I18N_ID` entries
+    /// - `I18N_DIR`: Directory containing locale files
+    /// - `I18N_OVERRIDES`: Comma-separated override directories shadowing `I18N_DIR`
+    /// - `I18N_TENANTS`: Directory containing one override directory per tenant
+    /// - `I18N_ALIASES`: Comma-separated aliases (e.g., "no=nb-NO,zh=zh-Hans-CN")
+    /// - `I18N_MISSING`: Missing-key policy: "placeholder", "key", "empty", or "panic"
+    /// - `I18N_ISOLATING`: Set to "1" or "true" to wrap placeables in bidi isolation marks
+    /// - `I18N_PSEUDO`: Set to "1" or "true" to pseudo-localize every message
+    /// - `I18N_STRICT`: Set to "1" or "true" to panic on missing or broken translations
+    /// - `I18N_SUGGEST`: Set to "1" or "true" to suggest the closest key when a lookup misses
+    /// - `I18N_LENIENT_INIT`: Set to "1" or "true" to start without translations when loading fails
+    /// - `I18N_LAZY`: Set to "1" or "true" to load each locale on first use
+    /// - `I18N_CACHE`: Number of formatted messages to cache (e.g. "1000")
+    ///
+    /// # Return
+    /// Returns a configuration with unset variables left at their defaults. On
+    /// `wasm32-unknown-unknown`, which has no environment, every variable is
+    /// unset.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let mut preferences = var("I18N_ID")
+            .ok()
+            .or_else(system_locale)
+            .map(|locales| split_chain(&locales))
+            .unwrap_or_default()
+            .into_iter();
+
+        let locale = preferences.next().unwrap_or(defaults.locale);
+        let mut fallback: Vec<String> = preferences.collect();
+        for locale in var("I18N_FALLBACK").map(|chain| split_chain(&chain)).unwrap_or(defaults.fallback) {
+            if !fallback.contains(&locale) {
+                fallback.push(locale);
+            }
+        }
+
+        Self {
+            locale,
+            fallback,
+            dir: var("I18N_DIR").unwrap_or(defaults.dir),
+            overrides: var("I18N_OVERRIDES")
+                .map(|dirs| split_chain(&dirs))
+                .unwrap_or(defaults.overrides),
+            tenants: var("I18N_TENANTS").ok().or(defaults.tenants),
+            aliases: var("I18N_ALIASES")
+                .map(|aliases| split_aliases(&aliases))
+                .unwrap_or(defaults.aliases),
+            missing: var("I18N_MISSING")
+                .ok()
+                .and_then(|name| MissingKeyPolicy::from_name(&name))
+                .unwrap_or(defaults.missing),
+            use_isolating: var("I18N_ISOLATING")
+                .map(|flag| is_set(&flag))
+                .unwrap_or(defaults.use_isolating),
+            pseudo: var("I18N_PSEUDO")
+                .map(|flag| is_set(&flag))
+                .unwrap_or(defaults.pseudo),
+            strict: var("I18N_STRICT")
+                .map(|flag| is_set(&flag))
+                .unwrap_or(defaults.strict),
+            suggest: var("I18N_SUGGEST")
+                .map(|flag| is_set(&flag))
+                .unwrap_or(defaults.suggest),
+            lenient_init: var("I18N_LENIENT_INIT")
+                .map(|flag| is_set(&flag))
+                .unwrap_or(defaults.lenient_init),
+            lazy: var("I18N_LAZY")
+                .map(|flag| is_set(&flag))
+                .unwrap_or(defaults.lazy),
+            cache: var("I18N_CACHE")
+                .ok()
+                .and_then(|size| size.trim().parse().ok())
+                .unwrap_or(defaults.cache),
+            ..defaults
+        }
+    }
+}
+
+/// Returns the locale of the operating system or user, if it is a valid language identifier
+#[cfg(feature = "sys-locale")]
+fn system_locale() -> Option<String> {
+    sys_locale::get_locale().filter(|locale| locale.parse::<LanguageIdentifier>().is_ok())
+}
+
+#[cfg(not(feature = "sys-locale"))]
+fn system_locale() -> Option<String> {
+    None
+}
+
+/// Reads an environment variable
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn var(name: &str) -> Result<String, env::VarError> {
+    env::var(name)
+}
+
+/// Browsers have no environment, so every variable is unset
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn var(_name: &str) -> Result<String, env::VarError> {
+    Err(env::VarError::NotPresent)
+}
+
+fn is_set(flag: &str) -> bool {
+    matches!(flag.trim(), "1" | "true")
+}
+
+fn split_aliases(aliases: &str) -> HashMap<String, String> {
+    split_chain(aliases)
+        .iter()
+        .filter_map(|alias| alias.split_once('='))
+        .map(|(alias, locale)| (alias.trim().to_string(), locale.trim().to_string()))
+        .collect()
+}
+
+fn split_chain(chain: &str) -> Vec<String> {
+    chain
+        .split(',')
+        .map(str::trim)
+        .filter(|locale| !locale.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,92 @@
+use crate::{ERROR_LOCK, LanguageIdentifier, instance, negotiate};
+use std::collections::{BTreeSet, HashMap};
+
+/// Translation completeness of a locale
+///
+/// Keys are compared against the messages of the default locale (the
+/// configured `locale`).
+///
+/// # Fields
+/// * `total` - Number of messages in the default locale
+/// * `translated` - Number of those messages the locale defines
+/// * `missing` - Messages of the default locale the locale lacks, sorted
+/// * `extra` - Messages the locale defines that the default locale lacks, sorted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageStats {
+    pub total: usize,
+    pub translated: usize,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+impl CoverageStats {
+    /// Returns the share of the default locale's messages that are translated
+    ///
+    /// # Return
+    /// Returns a percentage between 0 and 100 (100 when there is nothing to translate)
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let stats = i18n::CoverageStats { total: 4, translated: 3, ..Default::default() };
+    /// assert_eq!(stats.percent(), 75.0);
+    /// ```
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+
+        self.translated as f64 * 100.0 / self.total as f64
+    }
+}
+
+/// Reports how completely each installed locale is translated
+///
+/// Every locale's message set is compared against the default locale, listing
+/// missing and extra keys so teams can track completeness programmatically
+/// (e.g. in a CI job or an admin dashboard).
+///
+/// # Return
+/// Returns the statistics of every installed locale, including the default one
+///
+/// # Examples
+/// ```
+///
+/// let coverage = i18n::coverage();
+/// let es = &coverage[&"es-MX".parse().unwrap()];
+/// assert_eq!(es.missing, ["farewell"]);
+/// ```
+pub fn coverage() -> HashMap<LanguageIdentifier, CoverageStats> {
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let default = i18n.config.locale.parse::<LanguageIdentifier>().ok();
+    let reference = default
+        .as_ref()
+        .and_then(|locale| {
+            negotiate::matching(&[locale], catalog.available())
+                .first()
+                .and_then(|locale| catalog.keys(locale))
+        })
+        .cloned()
+        .unwrap_or_default();
+
+    catalog.available()
+        .iter()
+        .map(|locale| {
+            let keys = catalog.keys(locale).cloned().unwrap_or_default();
+            (locale.clone(), compare(&reference, &keys))
+        })
+        .collect()
+}
+
+fn compare(reference: &BTreeSet<String>, keys: &BTreeSet<String>) -> CoverageStats {
+    let missing: Vec<String> = reference.difference(keys).cloned().collect();
+
+    CoverageStats {
+        total: reference.len(),
+        translated: reference.len() - missing.len(),
+        missing,
+        extra: keys.difference(reference).cloned().collect(),
+    }
+}
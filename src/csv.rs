@@ -0,0 +1,273 @@
+use crate::ftl::{self, Messages};
+use crate::keys;
+use crate::{ERROR_LOCK, I18nError, LanguageIdentifier, instance, negotiate};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+/// Byte order mark, which makes Excel read the file as UTF-8
+const BOM: char = '\u{feff}';
+
+/// First characters that make spreadsheet applications evaluate a cell as a formula
+const FORMULA: [char; 4] = ['=', '+', '-', '@'];
+
+/// Exports every installed locale as a CSV spreadsheet
+///
+/// Each message and attribute gets a row, keyed by message ID or
+/// `message.attribute` in the first column, with a column per locale holding
+/// its translation. The default locale comes first, followed by the other
+/// locales, and cells of untranslated keys are left empty for translators to
+/// fill in. Texts are kept as FTL, including placeables such as `{ $name }` and
+/// selectors, so `import_csv` turns the edited sheet back into FTL without
+/// loss. Cells starting with `=`, `+`, `-`, or `@` are prefixed with `'` so
+/// spreadsheet applications show them as text instead of running them as
+/// formulas. The file starts with a byte order mark so Excel detects UTF-8.
+///
+/// # Return
+/// Returns the CSV document, with a header row naming the locales
+///
+/// # Examples
+/// ```
+///
+/// let csv = i18n::export_csv();
+/// assert!(csv.starts_with("\u{feff}key,en-US,es-MX\r\n"));
+/// assert!(csv.contains("greeting,\"Hello, { $name }!\",\"¡Hola, { $name }!\"\r\n"));
+/// assert!(csv.contains("hello,Hello,Hola\r\n"));
+/// ```
+pub fn export_csv() -> String {
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let default = i18n
+        .config
+        .locale
+        .parse::<LanguageIdentifier>()
+        .ok()
+        .and_then(|default| negotiate::matching(&[i18n.alias(&default)], catalog.available()).first().copied());
+
+    let mut locales: Vec<&LanguageIdentifier> = default.into_iter().collect();
+    locales.extend(catalog.available().iter().filter(|locale| Some(*locale) != default));
+
+    let mut rows: BTreeMap<String, HashMap<usize, String>> = BTreeMap::new();
+    for (column, locale) in locales.iter().enumerate() {
+        for (key, text) in catalog.patterns(locale) {
+            rows.entry(key).or_default().insert(column, text);
+        }
+    }
+
+    let mut csv = String::from(BOM);
+    let header: Vec<String> = locales.iter().map(ToString::to_string).collect();
+    write_row(&mut csv, "key", header.iter().map(String::as_str));
+
+    for (key, cells) in &rows {
+        write_row(&mut csv, key, (0..locales.len()).map(|column| cells.get(&column).map_or("", String::as_str)));
+    }
+
+    csv
+}
+
+/// Converts an edited CSV spreadsheet back to FTL, one source per locale
+///
+/// The header row names the locale of every column after the first, which
+/// holds the message ID or `message.attribute` of each row, so sheets written
+/// by `export_csv` come back as they were, with the `'` guarding formula
+/// characters removed. Empty cells are skipped, so those keys fall back as
+/// usual.
+///
+/// # Parameters
+/// * `csv` - The CSV document
+///
+/// # Return
+/// Returns the FTL source of every locale column, in column order, or an
+/// error if the document is malformed, a header is not a valid language
+/// identifier, or a cell is not a valid FTL pattern
+///
+/// # Examples
+/// ```
+///
+/// let csv = "key,en-US,fr-FR\ngreeting,\"Hello, { $name }!\",\"Bonjour, { $name } !\"\n";
+/// let imported = i18n::import_csv(csv).unwrap();
+///
+/// assert_eq!(imported[1].0.to_string(), "fr-FR");
+/// assert_eq!(imported[1].1, "greeting = Bonjour, { $name } !\n");
+/// ```
+pub fn import_csv(csv: &str) -> Result<Vec<(LanguageIdentifier, String)>, I18nError> {
+    let mut rows = parse(csv.strip_prefix(BOM).unwrap_or(csv))?.into_iter();
+
+    let (_, header) = rows.next().unwrap_or_default();
+    let locales = header
+        .iter()
+        .skip(1)
+        .map(|locale| locale.trim().parse::<LanguageIdentifier>().map_err(|_| I18nError::Loader(format!("CSV: invalid locale column \"{locale}\""))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut messages: Vec<Messages> = locales.iter().map(|_| Messages::default()).collect();
+    for (number, row) in rows {
+        let Some(key) = row.first().map(|key| key.trim()).filter(|key| !key.is_empty()) else {
+            continue;
+        };
+
+        if !key.split('.').all(keys::is_identifier) {
+            return Err(I18nError::Loader(format!("CSV row {}: invalid key \"{key}\"", number)));
+        }
+
+        for (column, text) in row.iter().skip(1).enumerate().take(locales.len()) {
+            let text = unguard(text.trim());
+            if text.is_empty() {
+                continue;
+            }
+
+            ftl::check(key, text).map_err(|e| I18nError::Loader(format!("CSV row {number}, {}: {e}", locales[column])))?;
+            messages[column].insert(key, text.to_string());
+        }
+    }
+
+    Ok(locales.into_iter().zip(messages.into_iter().map(Messages::finish)).collect())
+}
+
+/// Appends a row, quoting the cells that need it
+fn write_row<'a>(csv: &mut String, key: &str, cells: impl Iterator<Item = &'a str>) {
+    csv.push_str(&quote(key));
+    for cell in cells {
+        csv.push(',');
+        csv.push_str(&quote(cell));
+    }
+
+    csv.push_str("\r\n");
+}
+
+fn quote(cell: &str) -> String {
+    let cell = guard(cell);
+
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.into_owned()
+    }
+}
+
+/// Prefixes a cell that would be evaluated as a formula with `'`
+///
+/// Cells already starting with `'` before a formula character get another
+/// one, so `unguard` restores them as they were.
+fn guard(cell: &str) -> Cow<'_, str> {
+    if cell.trim_start_matches('\'').starts_with(FORMULA) {
+        Cow::Owned(format!("'{cell}"))
+    } else {
+        Cow::Borrowed(cell)
+    }
+}
+
+/// Removes the `'` that `guard` put before a cell
+fn unguard(cell: &str) -> &str {
+    match cell.strip_prefix('\'') {
+        Some(rest) if rest.trim_start_matches('\'').starts_with(FORMULA) => rest,
+        _ => cell,
+    }
+}
+
+/// Splits a CSV document into rows of cells, as written by spreadsheet applications
+///
+/// Quoted cells can hold commas, line breaks, and doubled quotes. Empty lines
+/// are skipped, but still counted in the row numbers, which start at 1 as in
+/// spreadsheet applications.
+fn parse(csv: &str) -> Result<Vec<(usize, Vec<String>)>, I18nError> {
+    let mut rows = Vec::new();
+    let mut number = 1;
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                if row.iter().any(|cell| !cell.is_empty()) {
+                    rows.push((number, std::mem::take(&mut row)));
+                }
+                row.clear();
+                number += 1;
+            }
+            c => cell.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(I18nError::Loader("CSV: unterminated quoted cell".to_string()));
+    }
+
+    row.push(cell);
+    if row.iter().any(|cell| !cell.is_empty()) {
+        rows.push((number, row));
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    #[test]
+    fn every_locale_gets_a_column() {
+        let _guard = fixtures(&[
+            ("en-US", "hello = Hello\ngreeting = Hello, { $name }!\n"),
+            ("es-MX", "hello = Hola\n"),
+        ]);
+
+        assert_eq!(export_csv(), "\u{feff}key,en-US,es-MX\r\ngreeting,\"Hello, { $name }!\",\r\nhello,Hello,Hola\r\n");
+    }
+
+    #[test]
+    fn sheets_convert_back_to_ftl() {
+        let csv = "\u{feff}key,en-US,fr-FR\r\nhello,Hello,Bonjour\r\n\r\nbye,\"Bye, \"\"friend\"\"\",\r\n";
+        let imported = import_csv(csv).unwrap();
+
+        assert_eq!(imported, [
+            ("en-US".parse().unwrap(), "bye = Bye, \"friend\"\nhello = Hello\n".to_string()),
+            ("fr-FR".parse().unwrap(), "hello = Bonjour\n".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn formulas_are_exported_as_text() {
+        let _guard = fixtures(&[("en-US", "sum = =1+2\nlist = - one, - two\nmail = @home\nquoted = '+1\nplain = 'hi'\n")]);
+        let csv = export_csv();
+
+        assert_eq!(csv, "\u{feff}key,en-US\r\nlist,\"'- one, - two\"\r\nmail,'@home\r\nplain,'hi'\r\nquoted,''+1\r\nsum,'=1+2\r\n");
+        assert_eq!(import_csv(&csv).unwrap(), [(
+            "en-US".parse().unwrap(),
+            "list = - one, - two\nmail = @home\nplain = 'hi'\nquoted = '+1\nsum = =1+2\n".to_string(),
+        )]);
+    }
+
+    #[test]
+    fn quoted_cells_keep_line_breaks() {
+        let rows = parse("a,\"b\nc\"\r\nd,e").unwrap();
+
+        assert_eq!(rows, [(1, vec!["a".to_string(), "b\nc".to_string()]), (2, vec!["d".to_string(), "e".to_string()])]);
+        assert!(parse("a,\"b").is_err());
+    }
+
+    #[test]
+    fn invalid_headers_and_keys_are_reported() {
+        assert!(matches!(import_csv("key,!!\n"), Err(I18nError::Loader(reason)) if reason.contains("invalid locale column")));
+        assert!(matches!(import_csv("key,en\nnot a key,x\n"), Err(I18nError::Loader(reason)) if reason.contains("CSV row 2")));
+    }
+
+    #[test]
+    fn row_numbers_count_empty_lines_and_multiline_cells_once() {
+        let csv = "key,en\n\nhello,\"Hello\nthere\"\nnot a key,x\n";
+
+        assert!(matches!(import_csv(csv), Err(I18nError::Loader(reason)) if reason.starts_with("CSV row 4:")));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::format::icu_locale;
+use crate::locale;
+use chrono::{Datelike, TimeDelta, Timelike};
+use icu_datetime::DateTimeFormatter;
+use icu_datetime::fieldsets::{T, YMD};
+use icu_datetime::input::{Date, Time};
+use icu_decimal::input::Decimal;
+use icu_experimental::relativetime::options::Numeric;
+use icu_experimental::relativetime::{RelativeTimeFormatter, RelativeTimeFormatterOptions, RelativeTimeFormatterPreferences};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+
+/// Builds the relative time formatter of one unit and width
+type Constructor = fn(RelativeTimeFormatterPreferences, RelativeTimeFormatterOptions) -> Option<RelativeTimeFormatter>;
+
+macro_rules! widths {
+    ($($constructor:ident),*) => {
+        [$(|prefs, options| RelativeTimeFormatter::$constructor(prefs, options).ok()),*]
+    };
+}
+
+/// Units of relative times by length in seconds, with their formatters from the longest width to the narrowest
+const UNITS: [(i64, [Constructor; 3]); 7] = [
+    (1, widths!(try_new_long_second, try_new_short_second, try_new_narrow_second)),
+    (MINUTE, widths!(try_new_long_minute, try_new_short_minute, try_new_narrow_minute)),
+    (HOUR, widths!(try_new_long_hour, try_new_short_hour, try_new_narrow_hour)),
+    (DAY, widths!(try_new_long_day, try_new_short_day, try_new_narrow_day)),
+    (WEEK, widths!(try_new_long_week, try_new_short_week, try_new_narrow_week)),
+    (MONTH, widths!(try_new_long_month, try_new_short_month, try_new_narrow_month)),
+    (YEAR, widths!(try_new_long_year, try_new_short_year, try_new_narrow_year)),
+];
+
+/// Length of a formatted date or time
+///
+/// # Variants
+/// * `Long` - Spelled-out month ("January 15, 2025"), times with seconds
+/// * `Medium` - Abbreviated month ("Jan 15, 2025"), times with seconds
+/// * `Short` - Numeric ("1/15/25"), times without seconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DateStyle {
+    Long,
+    #[default]
+    Medium,
+    Short,
+}
+
+/// Formats a date for the active locale
+///
+/// Uses CLDR patterns and the locale's calendar (through ICU4X), since FTL
+/// alone can't format timestamps. Accepts any chrono date type, such as
+/// `NaiveDate` or `DateTime<Utc>`.
+///
+/// # Parameters
+/// * `date` - The date to format
+/// * `style` - The length of the output
+///
+/// # Return
+/// Returns the localized date, or an ISO 8601 date if it cannot be localized
+///
+/// # Examples
+/// ```
+///
+/// use i18n::DateStyle;
+///
+/// let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+/// assert_eq!(i18n::format_date(&date, DateStyle::Long), "January 15, 2025");
+/// assert_eq!(i18n::format_date(&date, DateStyle::Short), "1/15/25");
+/// ```
+pub fn format_date<D>(date: &D, style: DateStyle) -> String
+where
+    D: Datelike,
+{
+    let iso = || format!("{:04}-{:02}-{:02}", date.year(), date.month(), date.day());
+    let Ok(input) = Date::try_new_iso(date.year(), date.month() as u8, date.day() as u8) else {
+        return iso();
+    };
+
+    let fields = match style {
+        DateStyle::Long => YMD::long(),
+        DateStyle::Medium => YMD::medium(),
+        DateStyle::Short => YMD::short(),
+    };
+
+    match DateTimeFormatter::try_new((&icu_locale(&locale())).into(), fields) {
+        Ok(formatter) => formatter.format(&input).to_string(),
+        Err(_) => iso(),
+    }
+}
+
+/// Formats a time of day for the active locale
+///
+/// Follows the locale's hour cycle (12 or 24 hours). Accepts any chrono time
+/// type, such as `NaiveTime` or `DateTime<Utc>`.
+///
+/// # Parameters
+/// * `time` - The time to format
+/// * `style` - The length of the output
+///
+/// # Return
+/// Returns the localized time, or an ISO 8601 time if it cannot be localized
+///
+/// # Examples
+/// ```
+///
+/// use i18n::DateStyle;
+///
+/// let time = chrono::NaiveTime::from_hms_opt(16, 9, 35).unwrap();
+/// assert_eq!(i18n::format_time(&time, DateStyle::Medium), "4:09:35\u{202f}PM");
+/// assert_eq!(i18n::format_time(&time, DateStyle::Short), "4:09\u{202f}PM");
+/// ```
+pub fn format_time<D>(time: &D, style: DateStyle) -> String
+where
+    D: Timelike,
+{
+    let iso = || format!("{:02}:{:02}:{:02}", time.hour(), time.minute(), time.second());
+    let Ok(input) = Time::try_new(time.hour() as u8, time.minute() as u8, time.second() as u8, 0) else {
+        return iso();
+    };
+
+    let fields = match style {
+        DateStyle::Long => T::long(),
+        DateStyle::Medium => T::medium(),
+        DateStyle::Short => T::hm(),
+    };
+
+    match DateTimeFormatter::try_new((&icu_locale(&locale())).into(), fields) {
+        Ok(formatter) => formatter.format(&input).to_string(),
+        Err(_) => iso(),
+    }
+}
+
+/// Formats a time span relative to now, such as "3 minutes ago" or "in 2 days"
+///
+/// Negative spans are in the past and positive ones in the future. The span
+/// is expressed in the largest whole unit (seconds up to years), with the
+/// locale's plural forms, for feeds, notifications, and audit logs. Spans
+/// under a second are the locale's word for "now". Without data for the
+/// locale's long unit names, its shorter ones are used, and without any, the
+/// span is written as an ISO 8601 duration such as `-PT180S`.
+///
+/// # Parameters
+/// * `delta` - The signed span between the moment and now
+///
+/// # Return
+/// Returns the localized relative time
+///
+/// # Examples
+/// ```
+///
+/// use chrono::TimeDelta;
+///
+/// assert_eq!(i18n::format_relative(TimeDelta::minutes(-3)), "3 minutes ago");
+/// assert_eq!(i18n::format_relative(TimeDelta::days(2)), "in 2 days");
+/// assert_eq!(i18n::format_relative(TimeDelta::zero()), "now");
+/// ```
+pub fn format_relative(delta: TimeDelta) -> String {
+    let seconds = delta.num_seconds();
+    let (unit, constructors) = UNITS.iter().rev().find(|(unit, _)| seconds.abs() >= *unit).unwrap_or(&UNITS[0]);
+    let value = seconds / unit;
+
+    // "now" instead of "in 0 seconds"
+    let mut options = RelativeTimeFormatterOptions::default();
+    if value == 0 {
+        options.numeric = Numeric::Auto;
+    }
+
+    let prefs: RelativeTimeFormatterPreferences = (&icu_locale(&locale())).into();
+    match constructors.iter().find_map(|constructor| constructor(prefs, options)) {
+        Some(formatter) => formatter.format(Decimal::from(value)).to_string(),
+        None => delta.to_string(),
+    }
+}
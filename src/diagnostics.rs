@@ -0,0 +1,204 @@
+use crate::{ERROR_LOCK, LanguageIdentifier, instance};
+use fluent_syntax::ast::{CallArguments, Expression, InlineExpression, Message, Pattern, PatternElement};
+use fluent_syntax::parser::ParserError;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Placeholders used by each message of a locale
+pub(crate) type Messages = BTreeMap<String, BTreeSet<String>>;
+
+/// Problems found while loading the translations
+///
+/// Gathered every time the catalog is built, so they reflect the latest
+/// reload.
+///
+/// # Fields
+/// * `load_error` - Why the catalog failed to load, if `lenient_init` replaced it by an empty one
+/// * `syntax_errors` - FTL syntax errors, in load order; the entries around them are still loaded
+/// * `mismatches` - Messages whose placeholders differ from the default locale
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub load_error: Option<String>,
+    pub syntax_errors: Vec<SyntaxError>,
+    pub mismatches: Vec<PlaceholderMismatch>,
+}
+
+/// An FTL syntax error
+///
+/// # Fields
+/// * `file` - The file containing the error, or a name for sources without files
+/// * `line` - The 1-based line the error starts on
+/// * `message` - The parser's description of the error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub file: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+/// A message whose `{ $placeholders }` differ from the default locale
+///
+/// # Fields
+/// * `locale` - The locale of the translation
+/// * `key` - The message ID
+/// * `expected` - Placeholders used by the default locale, sorted
+/// * `found` - Placeholders used by the translation, sorted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderMismatch {
+    pub locale: LanguageIdentifier,
+    pub key: String,
+    pub expected: Vec<String>,
+    pub found: Vec<String>,
+}
+
+impl Diagnostics {
+    /// Returns whether no problem was found
+    pub fn is_ok(&self) -> bool {
+        self.load_error.is_none() && self.syntax_errors.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(error) = &self.load_error {
+            writeln!(f, "{error}; translations are disabled and lookups return their keys")?;
+        }
+
+        for error in &self.syntax_errors {
+            writeln!(f, "{error}")?;
+        }
+
+        for mismatch in &self.mismatches {
+            writeln!(f, "{mismatch}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file.display(), self.line, self.message)
+    }
+}
+
+impl Display for PlaceholderMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} uses {{{}}}, expected {{{}}}",
+            self.locale,
+            self.key,
+            self.found.join(", "),
+            self.expected.join(", ")
+        )
+    }
+}
+
+/// Returns the problems found while loading the translations
+///
+/// Files with syntax errors are still loaded, minus the broken entries, and
+/// each error is reported with its file and line. Every locale's version of a
+/// message is also checked against the default locale (`I18nConfig::locale`)
+/// and reported when it uses other `{ $placeholders }`. A catalog that
+/// `lenient_init` replaced by an empty one reports why it failed to load.
+/// Applications can log the diagnostics at startup or fail CI before broken
+/// translations reach users.
+///
+/// # Return
+/// Returns the diagnostics of the current catalog
+///
+/// # Examples
+/// ```
+///
+/// let diagnostics = i18n::diagnostics();
+///
+/// for error in &diagnostics.syntax_errors {
+///     eprintln!("{error}");  // Prints e.g. "./assets/locales/es-MX/main.ftl:3: Expected a token ..."
+/// }
+///
+/// for mismatch in &diagnostics.mismatches {
+///     eprintln!("{mismatch}");  // Prints e.g. "es-MX: greeting uses {nombre}, expected {name}"
+/// }
+/// ```
+pub fn diagnostics() -> Diagnostics {
+    instance().catalog.read().expect(ERROR_LOCK).diagnostics().clone()
+}
+
+/// Locates the parser errors of `source`, read from `file`
+pub(crate) fn syntax_errors<'a>(file: &'a Path, source: &'a str, errors: &'a [ParserError]) -> impl Iterator<Item = SyntaxError> + 'a {
+    errors.iter().map(move |error| SyntaxError {
+        file: file.to_path_buf(),
+        line: source[..error.pos.start].matches('\n').count() + 1,
+        message: error.to_string(),
+    })
+}
+
+/// Records in `mismatches` the messages of `locale` using other placeholders than `reference`
+pub(crate) fn compare(
+    locale: &LanguageIdentifier,
+    reference: &Messages,
+    messages: &Messages,
+    mismatches: &mut Vec<PlaceholderMismatch>,
+) {
+    for (key, found) in messages {
+        match reference.get(key) {
+            Some(expected) if expected != found => {
+                mismatches.push(PlaceholderMismatch {
+                    locale: locale.clone(),
+                    key: key.clone(),
+                    expected: expected.iter().cloned().collect(),
+                    found: found.iter().cloned().collect(),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the variables referenced by a message's value and attributes
+pub(crate) fn placeholders(message: &Message<&str>) -> BTreeSet<String> {
+    let mut vars = BTreeSet::new();
+    message.value.iter().for_each(|value| pattern_vars(value, &mut vars));
+    message.attributes.iter().for_each(|attr| pattern_vars(&attr.value, &mut vars));
+
+    vars
+}
+
+fn pattern_vars(pattern: &Pattern<&str>, vars: &mut BTreeSet<String>) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            expression_vars(expression, vars);
+        }
+    }
+}
+
+fn expression_vars(expression: &Expression<&str>, vars: &mut BTreeSet<String>) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            inline_vars(selector, vars);
+            variants.iter().for_each(|variant| pattern_vars(&variant.value, vars));
+        }
+        Expression::Inline(inline) => inline_vars(inline, vars),
+    }
+}
+
+fn inline_vars(inline: &InlineExpression<&str>, vars: &mut BTreeSet<String>) {
+    match inline {
+        InlineExpression::VariableReference { id } => {
+            vars.insert(id.name.to_string());
+        }
+        InlineExpression::FunctionReference { arguments, .. } => call_vars(arguments, vars),
+        InlineExpression::TermReference { arguments: Some(arguments), .. } => call_vars(arguments, vars),
+        InlineExpression::Placeable { expression } => expression_vars(expression, vars),
+        _ => {}
+    }
+}
+
+fn call_vars(arguments: &CallArguments<&str>, vars: &mut BTreeSet<String>) {
+    arguments.positional
+        .iter()
+        .chain(arguments.named.iter().map(|named| &named.value))
+        .for_each(|inline| inline_vars(inline, vars));
+}
@@ -0,0 +1,97 @@
+use crate::{LanguageIdentifier, locale};
+use std::fmt::{self, Display, Formatter};
+use unic_langid::CharacterDirection;
+
+/// Writing direction of a locale
+///
+/// Scripts written top to bottom (traditional Mongolian) report `Ltr`, the
+/// direction they use in horizontal layouts.
+///
+/// # Variants
+/// * `Ltr` - Left to right, as in English or Spanish
+/// * `Rtl` - Right to left, as in Arabic or Hebrew
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    /// Returns whether the direction is right to left
+    pub fn is_rtl(self) -> bool {
+        self == Self::Rtl
+    }
+
+    /// Returns the value of the HTML `dir` attribute, "ltr" or "rtl"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+        }
+    }
+}
+
+impl Display for TextDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&LanguageIdentifier> for TextDirection {
+    fn from(locale: &LanguageIdentifier) -> Self {
+        match locale.character_direction() {
+            CharacterDirection::RTL => Self::Rtl,
+            CharacterDirection::LTR | CharacterDirection::TTB => Self::Ltr,
+        }
+    }
+}
+
+/// Returns the writing direction of the active locale
+///
+/// Lets UI layers flip layouts without maintaining their own list of
+/// right-to-left languages.
+///
+/// # Return
+/// Returns `Rtl` for right-to-left locales, `Ltr` otherwise
+///
+/// # Examples
+/// ```
+///
+/// use i18n::TextDirection;
+///
+/// let dir = i18n::direction();
+/// assert_eq!(dir, TextDirection::Ltr);
+/// let html = format!("<html dir=\"{dir}\">");
+/// ```
+pub fn direction() -> TextDirection {
+    TextDirection::from(&locale())
+}
+
+/// Returns the writing direction of a locale
+///
+/// The locale does not need to be installed. An invalid language identifier
+/// is ignored and the active locale's direction is returned instead.
+///
+/// # Parameters
+/// * `locale` - The language identifier to inspect (e.g., "ar-EG")
+///
+/// # Return
+/// Returns `Rtl` for right-to-left locales, `Ltr` otherwise
+///
+/// # Examples
+/// ```
+///
+/// use i18n::TextDirection;
+///
+/// assert_eq!(i18n::direction_of("ar-EG"), TextDirection::Rtl);
+/// assert_eq!(i18n::direction_of("es-MX"), TextDirection::Ltr);
+/// ```
+pub fn direction_of<T>(locale: T) -> TextDirection
+where
+    T: ToString,
+{
+    match locale.to_string().parse::<LanguageIdentifier>() {
+        Ok(locale) => TextDirection::from(&locale),
+        Err(_) => direction(),
+    }
+}
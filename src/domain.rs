@@ -0,0 +1,365 @@
+use crate::{ERROR_LOCK, I18nBuilder, I18nConfig, I18nError, Inner, LanguageIdentifier, attr_key, instance, locale, new};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Catalogs by tenant name, for the tenants that have their own
+type Tenants = HashMap<Arc<str>, Arc<Inner>>;
+
+/// Named bundles registered by the application, each with its own catalog
+static BUNDLES: RwLock<Vec<(Arc<str>, Arc<Inner>)>> = RwLock::new(Vec::new());
+
+/// Tenant catalogs loaded so far
+static TENANTS: Lazy<RwLock<Tenants>> = Lazy::new(Default::default);
+
+/// An independent catalog selected by name
+///
+/// Created by `bundle` or `tenant`. Keys of one bundle never collide with the
+/// global catalog or with other bundles, so email copy, admin UI, and public
+/// UI translations can live in separate directories. Lookups use the active
+/// locale and the catalog's own fallback chain and missing-key policy.
+///
+/// # Fields
+/// * `name` - The name of the bundle or tenant
+/// * `i18n` - The catalog and its settings, or `None` for the global catalog
+#[derive(Clone)]
+pub struct Domain {
+    name: Arc<str>,
+    i18n: Option<Arc<Inner>>,
+}
+
+impl Domain {
+    /// Returns the name of the bundle or tenant
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn i18n(&self) -> &Inner {
+        self.i18n.as_deref().unwrap_or_else(|| instance())
+    }
+
+    /// Retrieves a translation for a key of this bundle
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string for the current locale
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// i18n::register_bundle("emails", i18n::I18nConfig {
+    ///     dir: "./assets/bundles/emails/".to_string(),
+    ///     ..Default::default()
+    /// }).unwrap();
+    ///
+    /// let hello = i18n::bundle("emails").get("hello");
+    /// assert_eq!(hello, "Hello from the mailroom");
+    /// assert_eq!(i18n::get("hello"), "Hello");
+    /// ```
+    pub fn get<T>(&self, key: T) -> String
+    where
+        T: ToString,
+    {
+        self.i18n().lookup(&locale(), &key.to_string(), None, None)
+    }
+
+    /// Retrieves a translation for a key of this bundle, if it exists
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string, or `None` if the message is missing
+    pub fn get_opt<T>(&self, key: T) -> Option<String>
+    where
+        T: ToString,
+    {
+        self.i18n().try_lookup(&locale(), &key.to_string(), None, None)
+    }
+
+    /// Retrieves a translation for a key of this bundle, or a default
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    /// * `default` - The text returned when the message is missing
+    ///
+    /// # Return
+    /// Returns the translated string, or `default` if the message is missing
+    pub fn get_or<T, U>(&self, key: T, default: U) -> String
+    where
+        T: ToString,
+        U: ToString,
+    {
+        self.get_opt(key).unwrap_or_else(|| default.to_string())
+    }
+
+    /// Retrieves an attribute of a translation of this bundle
+    ///
+    /// # Parameters
+    /// * `key` - The translation key of the message
+    /// * `attr` - The attribute name
+    ///
+    /// # Return
+    /// Returns the translated attribute for the current locale
+    pub fn get_attr<T, U>(&self, key: T, attr: U) -> String
+    where
+        T: ToString,
+        U: ToString,
+    {
+        self.get(attr_key(&key.to_string(), &attr.to_string()))
+    }
+
+    /// Creates a builder looking up a key of this bundle
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns a builder for parameterized translations
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let _ = i18n::register_bundle("emails", i18n::I18nConfig {
+    ///     dir: "./assets/bundles/emails/".to_string(),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let welcome = i18n::bundle("emails")
+    ///     .builder("welcome")
+    ///     .set_args("name", "Bob")
+    ///     .build();
+    /// assert_eq!(welcome, "Welcome aboard, Bob!");
+    /// ```
+    pub fn builder<T>(&self, key: T) -> I18nBuilder
+    where
+        T: ToString,
+    {
+        new(key).with_bundle(self.i18n.clone())
+    }
+
+    /// Lists the locales discovered in the bundle's directory (or custom source)
+    ///
+    /// # Return
+    /// Returns the installed language identifiers, sorted
+    pub fn available_locales(&self) -> Vec<LanguageIdentifier> {
+        self.i18n().available()
+    }
+
+    /// Rebuilds the bundle's translations from disk
+    ///
+    /// # Return
+    /// Returns an error if the bundle's directory cannot be loaded, keeping
+    /// the previous translations in place
+    pub fn reload(&self) -> Result<(), I18nError> {
+        self.i18n().reload()
+    }
+}
+
+impl fmt::Debug for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Domain").field("name", &self.name).finish()
+    }
+}
+
+/// Registers a named bundle loaded from its own configuration
+///
+/// The bundle is loaded immediately from `config.dir` (or `config.source`).
+/// Its `locale` is only validated: lookups follow the active locale, like the
+/// global catalog. Functions registered with `register_function` are
+/// available in every bundle.
+///
+/// # Parameters
+/// * `name` - The name used with `bundle` (e.g., "emails")
+/// * `config` - The settings used to build the bundle's loader
+///
+/// # Return
+/// Returns an error if the configuration is invalid, the directory cannot be
+/// loaded, or a bundle with the same name is already registered
+///
+/// # Examples
+/// ```
+///
+/// let config = i18n::I18nConfig {
+///     dir: "./assets/bundles/emails/".to_string(),
+///     ..Default::default()
+/// };
+///
+/// if let Err(e) = i18n::register_bundle("emails", config) {
+///     eprintln!("{e}");
+/// }
+/// ```
+pub fn register_bundle<T>(name: T, config: I18nConfig) -> Result<(), I18nError>
+where
+    T: ToString,
+{
+    let name = name.to_string();
+
+    let mut bundles = BUNDLES.write().expect(ERROR_LOCK);
+    if bundles.iter().any(|(n, _)| **n == name) {
+        return Err(I18nError::Bundle(name));
+    }
+
+    let i18n = Inner::new(&config, false)?;
+    bundles.push((name.into(), Arc::new(i18n)));
+
+    Ok(())
+}
+
+/// Returns the bundle registered under `name`
+///
+/// # Parameters
+/// * `name` - The name the bundle was registered under
+///
+/// # Return
+/// Returns a handle looking up keys in that bundle
+///
+/// # Panics
+/// Panics if no bundle was registered under `name`. Register every bundle at
+/// startup with `register_bundle`.
+///
+/// # Examples
+/// ```
+///
+/// let _ = i18n::register_bundle("emails", i18n::I18nConfig {
+///     dir: "./assets/bundles/emails/".to_string(),
+///     ..Default::default()
+/// });
+///
+/// i18n::set_locale("es-MX").unwrap();
+/// assert_eq!(i18n::bundle("emails").get("hello"), "Hola desde el correo");
+/// ```
+pub fn bundle<T>(name: T) -> Domain
+where
+    T: ToString,
+{
+    let name = name.to_string();
+
+    BUNDLES
+        .read()
+        .expect(ERROR_LOCK)
+        .iter()
+        .find(|(n, _)| **n == name)
+        .map(|(name, i18n)| Domain { name: Arc::clone(name), i18n: Some(Arc::clone(i18n)) })
+        .unwrap_or_else(|| panic!("Bundle is not registered: {name}"))
+}
+
+/// Returns the catalog of a tenant, loading it on first use
+///
+/// A tenant's catalog is the global one with the tenant's directory below
+/// `I18nConfig::tenants` layered on top, so it only needs the messages that
+/// differ. Catalogs are cached per tenant and rebuilt by `reload`. Tenants
+/// without a directory, or whose directory fails to load, use the global
+/// catalog; they are not cached, so unknown names don't pile up and a
+/// directory added later is picked up. Names that are not a single path
+/// component, such as `../admin` or `/etc`, never leave `tenants` and also
+/// use the global catalog.
+///
+/// # Parameters
+/// * `name` - The tenant's name, matching its directory (e.g., "acme")
+///
+/// # Return
+/// Returns a handle looking up keys in the tenant's catalog
+///
+/// # Examples
+/// ```
+///
+/// i18n::init_with(i18n::I18nConfig {
+///     tenants: Some("./assets/overrides/".to_string()),
+///     ..Default::default()
+/// });
+///
+/// let hello = i18n::tenant("acme").get("hello");
+/// assert_eq!(hello, "Welcome to Acme");
+/// assert_eq!(i18n::tenant("acme").get("farewell"), "Goodbye");
+/// assert_eq!(i18n::tenant("globex").get("hello"), "Hello");
+/// ```
+pub fn tenant<T>(name: T) -> Domain
+where
+    T: ToString,
+{
+    let name = name.to_string();
+
+    if let Some((name, i18n)) = TENANTS.read().expect(ERROR_LOCK).get_key_value(name.as_str()) {
+        return Domain { name: Arc::clone(name), i18n: Some(Arc::clone(i18n)) };
+    }
+
+    let name: Arc<str> = name.into();
+    let Some(i18n) = load_tenant(&name) else {
+        return Domain { name, i18n: None };
+    };
+
+    let i18n = TENANTS
+        .write()
+        .expect(ERROR_LOCK)
+        .entry(Arc::clone(&name))
+        .or_insert_with(|| Arc::new(i18n))
+        .clone();
+
+    Domain { name, i18n: Some(i18n) }
+}
+
+fn load_tenant(name: &str) -> Option<Inner> {
+    let global = instance();
+    let dir = tenant_dir(global.config.tenants.as_ref()?, name).filter(|dir| dir.is_dir())?;
+
+    let mut config = global.config.clone();
+    config.overrides.push(dir.to_string_lossy().into_owned());
+
+    Inner::new(&config, true).ok()
+}
+
+/// Returns the directory of the tenant `name` below `tenants`
+///
+/// Returns `None` unless `name` is a single normal path component, so it
+/// cannot point outside `tenants`.
+fn tenant_dir(tenants: &str, name: &str) -> Option<PathBuf> {
+    let mut components = Path::new(name).components();
+    let single = matches!((components.next(), components.next()), (Some(Component::Normal(_)), None));
+
+    (single && !name.contains(['/', '\\'])).then(|| Path::new(tenants).join(name))
+}
+
+/// Drops the cached tenant catalogs, so they are rebuilt on next use
+pub(crate) fn clear_tenants() {
+    TENANTS.write().expect(ERROR_LOCK).clear();
+}
+
+/// Returns every registered bundle's and loaded tenant's catalog
+pub(crate) fn registered() -> Vec<Arc<Inner>> {
+    let bundles = BUNDLES.read().expect(ERROR_LOCK);
+    let tenants = TENANTS.read().expect(ERROR_LOCK);
+
+    bundles
+        .iter()
+        .map(|(_, i18n)| Arc::clone(i18n))
+        .chain(tenants.values().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    #[test]
+    fn tenant_names_stay_below_the_tenants_directory() {
+        assert_eq!(tenant_dir("tenants", "acme"), Some(PathBuf::from("tenants/acme")));
+
+        for name in ["", ".", "..", "../admin", "acme/../../etc", "/etc", "a/b", "a\\b"] {
+            assert_eq!(tenant_dir("tenants", name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn unknown_tenants_are_not_cached() {
+        let _guard = fixtures(&[("en-US", "hello = Hello\n")]);
+
+        assert_eq!(tenant("unknown").get("hello"), "Hello");
+        assert!(!TENANTS.read().expect(ERROR_LOCK).contains_key("unknown"));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{LanguageIdentifier, missing};
+use fluent_templates::fluent_bundle::FluentError;
+use std::fmt;
+
+const ERROR_PARSING: &str = "Parsing language failed";
+const ERROR_BUILDING: &str = "Unable to build loader";
+const ERROR_INITIALIZED: &str = "Translations are already initialized";
+const ERROR_WATCHING: &str = "Unable to watch locale directory";
+const ERROR_FUNCTION: &str = "Function is already registered";
+const ERROR_BUNDLE: &str = "Bundle is already registered";
+const ERROR_MESSAGE: &str = "Missing translation";
+const ERROR_LOCALE: &str = "No installed locale matches";
+const ERROR_FORMATTING: &str = "Formatting translation failed";
+
+/// Errors raised while setting up or reconfiguring translations
+///
+/// # Variants
+/// * `InvalidLocale` - A language identifier could not be parsed
+/// * `Loader` - The locale directory could not be read or parsed
+/// * `AlreadyInitialized` - Initialization was attempted after the first lookup
+/// * `Watch` - The locale directory could not be watched for changes
+/// * `Function` - A custom Fluent function with the same name already exists
+/// * `Bundle` - A named bundle with the same name already exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I18nError {
+    InvalidLocale(String),
+    Loader(String),
+    AlreadyInitialized,
+    Watch(String),
+    Function(String),
+    Bundle(String),
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLocale(locale) => write!(f, "{ERROR_PARSING}: {locale}"),
+            Self::Loader(reason) => write!(f, "{ERROR_BUILDING}: {reason}"),
+            Self::AlreadyInitialized => f.write_str(ERROR_INITIALIZED),
+            Self::Watch(reason) => write!(f, "{ERROR_WATCHING}: {reason}"),
+            Self::Function(name) => write!(f, "{ERROR_FUNCTION}: {name}"),
+            Self::Bundle(name) => write!(f, "{ERROR_BUNDLE}: {name}"),
+        }
+    }
+}
+
+impl std::error::Error for I18nError {}
+
+/// Reasons a lookup through `try_get` failed
+///
+/// # Variants
+/// * `MissingMessage` - The key is defined neither in the locale nor in its fallback chain, with the closest existing key when `suggest` is enabled
+/// * `MissingLocale` - Neither the locale nor any locale of its fallback chain is installed
+/// * `FormattingError` - The message exists but failed to format, e.g. a placeable refers to a missing argument
+///
+/// # Examples
+/// ```
+///
+/// use i18n::LookupError;
+///
+/// match i18n::try_get("promo-banner") {
+///     Ok(banner) => println!("{banner}"),
+///     Err(LookupError::MissingMessage(..)) => {}  // Not localized, show nothing
+///     Err(e) => eprintln!("{e}"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LookupError {
+    MissingMessage(String, Option<String>),
+    MissingLocale(LanguageIdentifier),
+    FormattingError(String, Vec<FluentError>),
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMessage(key, suggestion) => {
+                write!(f, "{ERROR_MESSAGE}: {key}{}", missing::hint(suggestion.as_deref()))
+            }
+            Self::MissingLocale(locale) => write!(f, "{ERROR_LOCALE}: {locale}"),
+            Self::FormattingError(key, errors) => {
+                let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "{ERROR_FORMATTING}: {key}: {}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
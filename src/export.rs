@@ -0,0 +1,145 @@
+use crate::ftl;
+use crate::keys::CATEGORIES;
+use crate::{ERROR_LOCK, LanguageIdentifier, instance, locale};
+use fluent_syntax::ast::{Expression, Pattern, PatternElement, VariantKey};
+use serde_json::{Map, Value};
+
+/// Name of the argument `json_to_ftl` selects plural forms on
+const COUNT: &str = "count";
+
+/// Dumps every message of a locale as a JSON map
+///
+/// Lets backends hand the full catalog to a SPA or mobile client in one
+/// response. Messages missing from the locale are taken from its fallback
+/// chain, so the map holds what `get` would return. Attributes are keyed
+/// `message.attribute`, and placeables referring to arguments are kept as
+/// `{$name}` for the client to fill in. Messages that are a single plural
+/// select on `$count` are split into one key per CLDR category, in the
+/// i18next form `key_one`/`key_other` that `json_to_ftl` reads back. Other
+/// selects cannot be represented and are flattened to their default variant.
+/// An invalid language identifier is ignored and the active locale is used
+/// instead.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+///
+/// # Return
+/// Returns a flat JSON object mapping each key to its text, sorted by key
+///
+/// # Examples
+/// ```
+///
+/// let catalog = i18n::export_json("es-MX");
+/// assert_eq!(&catalog["hello"], "Hola");
+/// assert_eq!(&catalog["farewell"], "Goodbye");  // From the en-US fallback
+/// assert_eq!(&catalog["emails_one"], "Tienes un correo");
+/// assert_eq!(&catalog["emails_other"], "Tienes {$count} correos");
+/// ```
+pub fn export_json<T>(locale: T) -> Value
+where
+    T: ToString,
+{
+    let locale: LanguageIdentifier = locale.to_string().parse().unwrap_or_else(|_| self::locale());
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    // Walk the chain from the last fallback so better matches overwrite
+    let mut messages = Map::new();
+    for l in i18n.chain(&catalog, &locale).into_iter().rev() {
+        for (key, forms) in catalog.render_all(l, forms) {
+            // A better match replaces the message whether or not it is plural
+            messages.remove(&key);
+            for category in CATEGORIES {
+                messages.remove(&format!("{key}_{category}"));
+            }
+
+            for (suffix, text) in forms {
+                messages.insert(format!("{key}{suffix}"), Value::String(text));
+            }
+        }
+    }
+
+    Value::Object(messages)
+}
+
+/// Renders a pattern as its text, or as one `_category` suffixed form per variant if it is a single plural select on `$count`
+fn forms(pattern: &Pattern<&str>, format: &dyn Fn(&Pattern<&str>) -> String) -> Vec<(String, String)> {
+    if let [PatternElement::Placeable { expression: Expression::Select { selector, variants } }] = pattern.elements.as_slice()
+        && ftl::variable(selector) == Some(COUNT)
+        && variants.iter().all(|variant| matches!(variant.key, VariantKey::Identifier { name } if CATEGORIES.contains(&name)))
+        && variants.iter().any(|variant| matches!(variant.key, VariantKey::Identifier { name: "other" }))
+    {
+        return variants
+            .iter()
+            .filter_map(|variant| match variant.key {
+                VariantKey::Identifier { name } => Some((format!("_{name}"), format(&variant.value))),
+                VariantKey::NumberLiteral { .. } => None,
+            })
+            .collect();
+    }
+
+    vec![(String::new(), format(pattern))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    #[test]
+    fn export_fills_in_the_fallback_chain() {
+        let _guard = fixtures(&[
+            ("en-US", "hello = Hello\nbye = Goodbye\nbutton = Log in\n    .title = Click { $name }\n"),
+            ("es-MX", "hello = Hola\n"),
+        ]);
+
+        assert_eq!(
+            export_json("es-MX"),
+            serde_json::json!({ "bye": "Goodbye", "button": "Log in", "button.title": "Click {$name}", "hello": "Hola" })
+        );
+    }
+
+    #[test]
+    fn plurals_on_count_are_exported_as_i18next_keys() {
+        let ftl = "\
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+zero = { $count ->
+    [0] No emails
+   *[other] { $count } emails
+}
+places = { $position ->
+    [one] First
+   *[other] Later
+}
+";
+        let _guard = fixtures(&[("en-US", ftl)]);
+        let json = export_json("en-US");
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "emails_one": "One email",
+                "emails_other": "{$count} emails",
+                "places": "Later",
+                "zero": "{$count} emails",
+            })
+        );
+        assert_eq!(
+            crate::json_to_ftl(&json.to_string()).unwrap(),
+            "emails = { $count ->\n    [one] One email\n   *[other] { $count } emails\n}\nplaces = Later\nzero = { $count } emails\n"
+        );
+    }
+
+    #[test]
+    fn a_better_match_replaces_every_plural_form() {
+        let _guard = fixtures(&[
+            ("en-US", "emails = { $count ->\n    [one] One email\n   *[other] { $count } emails\n}\n"),
+            ("es-MX", "emails = Correos\n"),
+        ]);
+
+        assert_eq!(export_json("es-MX"), serde_json::json!({ "emails": "Correos" }));
+    }
+}
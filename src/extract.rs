@@ -0,0 +1,601 @@
+//! Extraction of the translation keys referenced in Rust source, as run by the
+//! `i18n-extract` binary
+//!
+//! ```no_run
+//! let references = i18n::extract::extract_dir("./src/").unwrap();
+//! let template = i18n::extract::template(&references);
+//!
+//! std::fs::write("./assets/template.ftl", template).unwrap();
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// `i18n` functions whose first argument is a key
+const FUNCTIONS: [&str; 6] = ["get", "get_opt", "get_or", "try_get", "new", "get_attr"];
+
+/// Macros whose first argument is a key, followed by `name = value` arguments
+const MACROS: [&str; 2] = ["t", "t_static"];
+
+/// Builder methods whose first argument is an argument name
+const SETTERS: [&str; 3] = ["set_arg", "set_args", "set_value"];
+
+/// A translation key referenced in source code
+///
+/// # Fields
+/// * `key` - The message ID, or `message.attribute` for `get_attr`
+/// * `args` - Names of the arguments passed with the key, sorted
+/// * `file` - The file referencing the key
+/// * `line` - The 1-based line of the reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub key: String,
+    pub args: Vec<String>,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// A token of Rust source, as far as extraction needs to tell them apart
+#[derive(Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+    Other,
+}
+
+/// Extracts the translation keys referenced in every Rust file below a directory
+///
+/// `target` and hidden directories are skipped.
+///
+/// # Parameters
+/// * `dir` - Directory of Rust sources (e.g., "./src/")
+///
+/// # Return
+/// Returns the references of every file, sorted by file and line, or an
+/// error if the directory cannot be read
+pub fn extract_dir<P>(dir: P) -> io::Result<Vec<Reference>>
+where
+    P: AsRef<Path>,
+{
+    let mut paths = Vec::new();
+    collect_sources(dir.as_ref(), &mut paths)?;
+    paths.sort();
+
+    let mut references = Vec::new();
+    for path in paths {
+        let source = fs::read_to_string(&path)?;
+        references.extend(extract_source(&path, &source));
+    }
+
+    Ok(references)
+}
+
+/// Extracts the translation keys referenced in a Rust source file
+///
+/// Keys are found in calls to `i18n::get`, `i18n::get_opt`, `i18n::get_or`,
+/// `i18n::try_get`, `i18n::get_attr`, and `i18n::new`, and in `t!` and
+/// `t_static!` invocations, when given as string literals. Arguments are
+/// taken from the macros' `name = value` pairs and from `set_arg` calls
+/// chained to `i18n::new`. Comments are ignored, and keys built at runtime
+/// cannot be found.
+///
+/// # Parameters
+/// * `file` - The path reported in the references
+/// * `source` - The Rust source
+///
+/// # Return
+/// Returns the references, in source order
+///
+/// # Examples
+/// ```
+///
+/// use std::path::Path;
+///
+/// let source = r#"
+///     let hello = i18n::get("hello");
+///     let greeting = i18n::t!("greeting", name = user.name);
+/// "#;
+///
+/// let references = i18n::extract::extract_source(Path::new("src/main.rs"), source);
+/// assert_eq!(&references[1].key, "greeting");
+/// assert_eq!(references[1].args, ["name"]);
+/// assert_eq!(references[1].line, 3);
+/// ```
+pub fn extract_source(file: &Path, source: &str) -> Vec<Reference> {
+    let tokens = tokenize(source);
+    let mut references = Vec::new();
+
+    for i in 0..tokens.len() {
+        let reference = |key: String, args: Vec<String>| Reference { key, args, file: file.to_path_buf(), line: tokens[i].1 };
+
+        match ident(&tokens, i) {
+            Some("i18n") if punct(&tokens, i + 1, ':') && punct(&tokens, i + 2, ':') => {
+                let Some(function) = ident(&tokens, i + 3).filter(|function| FUNCTIONS.contains(function)) else {
+                    continue;
+                };
+
+                if !punct(&tokens, i + 4, '(') {
+                    continue;
+                }
+
+                let Some(key) = string(&tokens, i + 5) else {
+                    continue;
+                };
+
+                match function {
+                    "get_attr" => {
+                        if punct(&tokens, i + 6, ',')
+                            && let Some(attr) = string(&tokens, i + 7)
+                        {
+                            references.push(reference(format!("{key}.{attr}"), Vec::new()));
+                        }
+                    }
+                    "new" => references.push(reference(key.to_string(), chained_args(&tokens, i + 4))),
+                    _ => references.push(reference(key.to_string(), Vec::new())),
+                }
+            }
+            Some(name) if MACROS.contains(&name) && punct(&tokens, i + 1, '!') => {
+                if !matches!(tokens.get(i + 2), Some((Token::Punct('(' | '[' | '{'), _))) {
+                    continue;
+                }
+
+                if let Some(key) = string(&tokens, i + 3) {
+                    references.push(reference(key.to_string(), macro_args(&tokens, i + 2)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    references
+}
+
+/// Writes a template FTL file listing every referenced key
+///
+/// Each message is preceded by a comment with the places referencing it,
+/// and its value lists the arguments it is given, so the template shows
+/// translators what to write. `message.attribute` keys become attributes.
+/// Comparing the template's keys with a locale's reveals missing and unused
+/// messages.
+///
+/// # Parameters
+/// * `references` - The references found by `extract_dir` or `extract_source`
+///
+/// # Return
+/// Returns the FTL source, with messages sorted by ID
+///
+/// # Examples
+/// ```
+///
+/// use std::path::Path;
+///
+/// let references = i18n::extract::extract_source(Path::new("src/main.rs"), r#"i18n::t!("greeting", name = "Bob")"#);
+/// let template = i18n::extract::template(&references);
+/// assert_eq!(template, "# src/main.rs:1\ngreeting = { $name }\n");
+/// ```
+pub fn template(references: &[Reference]) -> String {
+    #[derive(Default)]
+    struct Message<'a> {
+        value: Option<BTreeSet<&'a str>>,
+        attributes: BTreeMap<&'a str, BTreeSet<&'a str>>,
+        locations: Vec<String>,
+    }
+
+    let mut messages: BTreeMap<&str, Message> = BTreeMap::new();
+    for reference in references {
+        let (id, attr) = match reference.key.split_once('.') {
+            Some((id, attr)) => (id, Some(attr)),
+            None => (reference.key.as_str(), None),
+        };
+
+        let message = messages.entry(id).or_default();
+        let args = match attr {
+            Some(attr) => message.attributes.entry(attr).or_default(),
+            None => message.value.get_or_insert_default(),
+        };
+
+        args.extend(reference.args.iter().map(String::as_str));
+
+        let location = format!("{}:{}", reference.file.display(), reference.line);
+        if !message.locations.contains(&location) {
+            message.locations.push(location);
+        }
+    }
+
+    let pattern = |args: &BTreeSet<&str>| {
+        if args.is_empty() {
+            "{\"\"}".to_string()
+        } else {
+            args.iter().map(|arg| format!("{{ ${arg} }}")).collect::<Vec<_>>().join(" ")
+        }
+    };
+
+    let mut ftl = String::new();
+    for (id, message) in messages {
+        if !ftl.is_empty() {
+            ftl.push('\n');
+        }
+
+        let _ = writeln!(ftl, "# {}", message.locations.join(", "));
+        match &message.value {
+            Some(args) => {
+                let _ = writeln!(ftl, "{id} = {}", pattern(args));
+            }
+            None => {
+                let _ = writeln!(ftl, "{id} =");
+            }
+        }
+
+        for (attr, args) in &message.attributes {
+            let _ = writeln!(ftl, "    .{attr} = {}", pattern(args));
+        }
+    }
+
+    ftl
+}
+
+/// Collects every `.rs` file below `dir`, skipping `target` and hidden directories
+fn collect_sources(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                collect_sources(&path, paths)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn ident(tokens: &[(Token, usize)], i: usize) -> Option<&str> {
+    match tokens.get(i) {
+        Some((Token::Ident(ident), _)) => Some(ident),
+        _ => None,
+    }
+}
+
+fn string(tokens: &[(Token, usize)], i: usize) -> Option<&str> {
+    match tokens.get(i) {
+        Some((Token::Str(value), _)) => Some(value),
+        _ => None,
+    }
+}
+
+fn punct(tokens: &[(Token, usize)], i: usize, c: char) -> bool {
+    tokens.get(i).is_some_and(|(token, _)| *token == Token::Punct(c))
+}
+
+/// Returns the index after the group opening at `open`
+fn skip_group(tokens: &[(Token, usize)], open: usize) -> usize {
+    let mut depth = 0usize;
+
+    for (i, (token, _)) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('(' | '[' | '{') => depth += 1,
+            Token::Punct(')' | ']' | '}') => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tokens.len()
+}
+
+/// Returns the argument names of `set_arg` calls chained after the call opening at `open`
+fn chained_args(tokens: &[(Token, usize)], open: usize) -> Vec<String> {
+    let mut args = BTreeSet::new();
+    let mut i = skip_group(tokens, open);
+
+    while punct(tokens, i, '.') && ident(tokens, i + 1).is_some() && punct(tokens, i + 2, '(') {
+        if ident(tokens, i + 1).is_some_and(|method| SETTERS.contains(&method))
+            && let Some(name) = string(tokens, i + 3)
+        {
+            args.insert(name.to_string());
+        }
+
+        i = skip_group(tokens, i + 2);
+    }
+
+    args.into_iter().collect()
+}
+
+/// Returns the names of the `name = value` arguments of the macro group opening at `open`
+fn macro_args(tokens: &[(Token, usize)], open: usize) -> Vec<String> {
+    let mut args = BTreeSet::new();
+    let end = skip_group(tokens, open);
+    let mut depth = 0usize;
+
+    for i in open..end {
+        match &tokens[i].0 {
+            Token::Punct('(' | '[' | '{') => depth += 1,
+            Token::Punct(')' | ']' | '}') => depth = depth.saturating_sub(1),
+            Token::Punct(',') if depth == 1 => {
+                // `==` is a comparison inside a value, not an argument
+                if let Some(name) = ident(tokens, i + 1)
+                    && punct(tokens, i + 2, '=')
+                    && !punct(tokens, i + 3, '=')
+                {
+                    args.insert(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    args.into_iter().collect()
+}
+
+/// Splits Rust source into tokens with their line, skipping comments
+fn tokenize(source: &str) -> Vec<(Token, usize)> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = line;
+
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let mut depth = 0;
+                while i < chars.len() {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('/', Some('*')) => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        ('*', Some('/')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        ('\n', _) => {
+                            line += 1;
+                            i += 1;
+                        }
+                        _ => i += 1,
+                    }
+                }
+                continue;
+            }
+            '"' => {
+                let (value, end) = quoted(&chars, i + 1, &mut line);
+                tokens.push((Token::Str(value), start));
+                i = end;
+                continue;
+            }
+            '\'' => {
+                // Character literals, unlike lifetimes, close after one character or escape
+                let end = match chars.get(i + 1) {
+                    Some('\\') => chars.get(i + 3..).and_then(|rest| rest.iter().position(|c| *c == '\'')).map(|p| i + 4 + p),
+                    Some(_) if chars.get(i + 2) == Some(&'\'') => Some(i + 3),
+                    _ => None,
+                };
+
+                match end {
+                    Some(end) => {
+                        tokens.push((Token::Other, start));
+                        i = end;
+                    }
+                    None => {
+                        tokens.push((Token::Punct('\''), start));
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let begin = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[begin..i].iter().collect();
+
+                match (ident.as_str(), chars.get(i)) {
+                    ("r" | "br" | "cr", Some('"' | '#')) if raw_start(&chars, i) => {
+                        let (value, end) = raw(&chars, i, &mut line);
+                        tokens.push((if ident == "r" { Token::Str(value) } else { Token::Other }, start));
+                        i = end;
+                    }
+                    ("b" | "c", Some('"')) => {
+                        let (_, end) = quoted(&chars, i + 1, &mut line);
+                        tokens.push((Token::Other, start));
+                        i = end;
+                    }
+                    _ => tokens.push((Token::Ident(ident), start)),
+                }
+                continue;
+            }
+            c if c.is_ascii_digit() => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push((Token::Other, start));
+                continue;
+            }
+            c => tokens.push((Token::Punct(c), start)),
+        }
+
+        i += 1;
+    }
+
+    tokens
+}
+
+/// Reads a string literal from after its opening quote, returning its value and end
+fn quoted(chars: &[char], mut i: usize, line: &mut usize) -> (String, usize) {
+    let mut value = String::new();
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return (value, i + 1),
+            '\\' => {
+                i += 1;
+                match chars.get(i) {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some('0') => value.push('\0'),
+                    // A line continuation skips the line break and the indentation after it
+                    Some('\n') => {
+                        *line += 1;
+                        while chars.get(i + 1).is_some_and(|c| c.is_whitespace()) {
+                            i += 1;
+                            if chars[i] == '\n' {
+                                *line += 1;
+                            }
+                        }
+                    }
+                    Some('u') if chars.get(i + 1) == Some(&'{') => {
+                        let end = chars[i..].iter().position(|c| *c == '}').map_or(chars.len(), |p| i + p);
+                        let hex: String = chars[i + 2..end].iter().collect();
+                        value.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+                        i = end;
+                    }
+                    Some(c) => value.push(*c),
+                    None => {}
+                }
+            }
+            '\n' => {
+                *line += 1;
+                value.push('\n');
+            }
+            c => value.push(c),
+        }
+
+        i += 1;
+    }
+
+    (value, i)
+}
+
+/// Returns whether a raw string starts at `i`, after its `r` prefix
+fn raw_start(chars: &[char], i: usize) -> bool {
+    chars[i..].iter().find(|c| **c != '#') == Some(&'"')
+}
+
+/// Reads a raw string literal from after its `r` prefix, returning its value and end
+fn raw(chars: &[char], mut i: usize, line: &mut usize) -> (String, usize) {
+    let hashes = chars[i..].iter().take_while(|c| **c == '#').count();
+    i += hashes + 1;
+
+    let mut value = String::new();
+    while i < chars.len() {
+        if chars[i] == '"' && chars[i + 1..].iter().take(hashes).filter(|c| **c == '#').count() == hashes {
+            return (value, i + 1 + hashes);
+        }
+
+        if chars[i] == '\n' {
+            *line += 1;
+        }
+
+        value.push(chars[i]);
+        i += 1;
+    }
+
+    (value, i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(source: &str) -> Vec<(String, Vec<String>, usize)> {
+        extract_source(Path::new("src/lib.rs"), source).into_iter().map(|r| (r.key, r.args, r.line)).collect()
+    }
+
+    #[test]
+    fn tokenize_skips_comments_and_reads_literals() {
+        let tokens: Vec<Token> = tokenize("a // \"b\"\n/* /* \"c\" */ */ \"d\\n\" r#\"e\"f\"# 'x' 'a b\"c\" \"\\u{e9}\"").into_iter().map(|(token, _)| token).collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident("a".to_string()),
+                Token::Str("d\n".to_string()),
+                Token::Str("e\"f".to_string()),
+                Token::Other,
+                Token::Punct('\''),
+                Token::Ident("a".to_string()),
+                Token::Other,
+                Token::Str("\u{e9}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_counts_lines_inside_literals() {
+        let lines: Vec<usize> = tokenize("\"a\nb\"\nr\"c\n\"\n/*\n*/ d").into_iter().map(|(_, line)| line).collect();
+
+        assert_eq!(lines, vec![1, 3, 6]);
+    }
+
+    #[test]
+    fn functions_and_attributes_are_extracted() {
+        let source = "i18n::get(\"hello\");\ni18n::get_attr(\"login-button\", \"title\");\ni18n::get(name);\nother::get(\"skipped\");";
+
+        assert_eq!(
+            keys(source),
+            vec![("hello".to_string(), vec![], 1), ("login-button.title".to_string(), vec![], 2)]
+        );
+    }
+
+    #[test]
+    fn chained_and_macro_arguments_are_sorted() {
+        let source = r#"
+            i18n::new("welcome").set_arg("name", user.name()).set_value("count", 3).build();
+            i18n::t!("greeting", name = "Bob", admin = a == b, nested = f(x, y = 1));
+            // i18n::t!("commented")
+        "#;
+
+        assert_eq!(
+            keys(source),
+            vec![
+                ("welcome".to_string(), vec!["count".to_string(), "name".to_string()], 2),
+                ("greeting".to_string(), vec!["admin".to_string(), "name".to_string(), "nested".to_string()], 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn template_merges_references_of_a_message() {
+        let source = "i18n::t!(\"greeting\", name = n);\ni18n::get_attr(\"greeting\", \"title\");\ni18n::t!(\"greeting\", count = c);\ni18n::get(\"bye\");";
+        let references = extract_source(Path::new("src/lib.rs"), source);
+
+        assert_eq!(
+            template(&references),
+            "# src/lib.rs:4\nbye = {\"\"}\n\n# src/lib.rs:1, src/lib.rs:2, src/lib.rs:3\ngreeting = { $count } { $name }\n    .title = {\"\"}\n"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use crate::catalog::Catalog;
+use crate::{ERROR_LOCK, I18nConfig, I18nError, LanguageIdentifier, LocaleSource, domain, instance, library, parse_locale, try_init};
+use std::sync::Arc;
+
+/// FTL sources given as string literals, for tests
+#[derive(Debug)]
+struct Fixtures {
+    resources: Vec<(LanguageIdentifier, String)>,
+}
+
+impl LocaleSource for Fixtures {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        let mut locales: Vec<LanguageIdentifier> = Vec::new();
+        for (locale, _) in &self.resources {
+            if !locales.contains(locale) {
+                locales.push(locale.clone());
+            }
+        }
+
+        Ok(locales)
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        let messages = self.resources
+            .iter()
+            .filter(|(l, _)| l == locale)
+            .map(|(_, ftl)| ftl.clone())
+            .collect();
+
+        Ok(messages)
+    }
+}
+
+/// Loads translations from string literals, without touching the filesystem
+///
+/// Makes unit tests of code calling `get` hermetic and fast. The first locale
+/// becomes the active locale, and the fallback chain when this call
+/// initializes the translations. If they were already initialized, only their
+/// catalog and active locale are replaced: the fallback chain, aliases, and
+/// other settings stay, and `reload` reads the original source again. Every
+/// test can install the fixtures it needs; tests running in parallel share
+/// them, so they should agree.
+///
+/// # Parameters
+/// * `resources` - Pairs of language identifier and FTL source; several
+///   sources of one locale are merged, later ones winning
+///
+/// # Panics
+/// Panics if a language identifier is invalid
+///
+/// # Examples
+/// ```
+///
+/// i18n::test_loader(&[
+///     ("en-US", "hello = Hello"),
+///     ("es-MX", "hello = Hola"),
+/// ]);
+///
+/// let hello = i18n::get("hello");
+/// assert_eq!(hello, "Hello");
+/// assert_eq!(i18n::new("hello").locale("es-MX").build(), "Hola");
+/// ```
+pub fn test_loader(resources: &[(&str, &str)]) {
+    load(resources).unwrap_or_else(|e| panic!("{e}"))
+}
+
+fn load(resources: &[(&str, &str)]) -> Result<(), I18nError> {
+    let resources = resources
+        .iter()
+        .map(|(locale, ftl)| Ok((parse_locale(locale)?, ftl.to_string())))
+        .collect::<Result<Vec<_>, I18nError>>()?;
+    let locale = resources.first().map_or_else(|| I18nConfig::default().locale, |(locale, _)| locale.to_string());
+
+    let config = I18nConfig {
+        locale: locale.clone(),
+        fallback: vec![locale.clone()],
+        source: Some(Arc::new(Fixtures { resources })),
+        ..Default::default()
+    };
+
+    match try_init(config.clone()) {
+        Err(I18nError::AlreadyInitialized) => {
+            let i18n = instance();
+            *i18n.catalog.write().expect(ERROR_LOCK) = Catalog::load(&config, &library::sources(true))?;
+            *i18n.locales.write().expect(ERROR_LOCK) = parse_locale(&locale)?;
+            domain::clear_tenants();
+
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Serializes the unit tests sharing the global instance
+#[cfg(test)]
+static FIXTURES: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Installs fixtures for a unit test, holding the global instance until the guard drops
+#[cfg(test)]
+pub(crate) fn fixtures(resources: &[(&str, &str)]) -> std::sync::MutexGuard<'static, ()> {
+    let guard = FIXTURES.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    test_loader(resources);
+
+    guard
+}
@@ -0,0 +1,201 @@
+use crate::{LanguageIdentifier, locale};
+use icu_decimal::DecimalFormatter;
+use icu_decimal::input::{Decimal, FloatPrecision};
+use icu_experimental::dimension::currency::CurrencyType;
+use icu_experimental::dimension::currency::formatter::{CurrencyFormatter, CurrencyFormatterPreferences};
+use icu_list::ListFormatter;
+use icu_list::options::{ListFormatterOptions, ListLength};
+use icu_locale_core::Locale;
+
+/// How the currency of a formatted amount is shown
+///
+/// # Variants
+/// * `Symbol` - The locale's symbol, disambiguated where needed ("US$19.99" in es-MX)
+/// * `Narrow` - The shortest symbol, possibly ambiguous ("$19.99")
+/// * `Code` - The ISO 4217 code ("USD 19.99")
+/// * `Name` - The spelled-out, pluralized name ("19.99 US dollars")
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CurrencyStyle {
+    #[default]
+    Symbol,
+    Narrow,
+    Code,
+    Name,
+}
+
+/// Which conjunction joins the last items of a formatted list
+///
+/// # Variants
+/// * `And` - A conjunction ("apples, pears, and plums")
+/// * `Or` - A disjunction ("apples, pears, or plums")
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ListStyle {
+    #[default]
+    And,
+    Or,
+}
+
+/// Converts a language identifier into an ICU4X locale
+pub(crate) fn icu_locale(locale: &LanguageIdentifier) -> Locale {
+    locale.to_string().parse().unwrap_or(Locale::UNKNOWN)
+}
+
+/// Formats a number with the grouping and decimal separators of the active locale
+///
+/// Uses CLDR data (through ICU4X), so applications don't hand-roll
+/// "1.234.567,89" vs "1,234,567.89" logic. Digits after the decimal separator
+/// are kept as written; non-finite numbers are formatted as Rust would.
+///
+/// # Parameters
+/// * `number` - The number to format
+///
+/// # Return
+/// Returns the localized number
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::format_number(1234567.89), "1,234,567.89");
+/// assert_eq!(i18n::format_number(42), "42");
+/// ```
+pub fn format_number<T>(number: T) -> String
+where
+    T: Into<f64>,
+{
+    let number = number.into();
+    let Ok(decimal) = Decimal::try_from_f64(number, FloatPrecision::RoundTrip) else {
+        return number.to_string();
+    };
+
+    match DecimalFormatter::try_new((&icu_locale(&locale())).into(), Default::default()) {
+        Ok(formatter) => formatter.format_to_string(&decimal),
+        Err(_) => decimal.to_string(),
+    }
+}
+
+/// Formats a monetary amount for the active locale
+///
+/// The symbol position, separators, and number of decimals follow CLDR data
+/// for the locale and currency (e.g. "€19.99" in en-US, "19,99 €" in de-DE).
+///
+/// # Parameters
+/// * `amount` - The amount to format
+/// * `currency` - The ISO 4217 currency code (e.g., "EUR")
+///
+/// # Return
+/// Returns the localized amount, or the amount followed by `currency` if the
+/// code is malformed
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::format_currency(19.99, "EUR"), "€19.99");
+/// assert_eq!(i18n::format_currency(1500, "JPY"), "¥1,500");
+/// ```
+pub fn format_currency<T, U>(amount: T, currency: U) -> String
+where
+    T: Into<f64>,
+    U: AsRef<str>,
+{
+    format_currency_with(amount, currency, CurrencyStyle::Symbol)
+}
+
+/// Formats a monetary amount for the active locale, choosing how the currency is shown
+///
+/// # Parameters
+/// * `amount` - The amount to format
+/// * `currency` - The ISO 4217 currency code (e.g., "USD")
+/// * `style` - Whether to show a symbol, a narrow symbol, the code, or the name
+///
+/// # Return
+/// Returns the localized amount, or the amount followed by `currency` if the
+/// code is malformed
+///
+/// # Examples
+/// ```
+///
+/// use i18n::CurrencyStyle;
+///
+/// i18n::set_locale("es-MX").unwrap();
+/// assert_eq!(i18n::format_currency_with(19.99, "USD", CurrencyStyle::Symbol), "US$19.99");
+/// assert_eq!(i18n::format_currency_with(19.99, "USD", CurrencyStyle::Narrow), "$19.99");
+/// assert_eq!(i18n::format_currency_with(19.99, "USD", CurrencyStyle::Name), "19.99 dólares estadounidenses");
+/// ```
+pub fn format_currency_with<T, U>(amount: T, currency: U, style: CurrencyStyle) -> String
+where
+    T: Into<f64>,
+    U: AsRef<str>,
+{
+    let amount = amount.into();
+    let currency = currency.as_ref();
+    let fallback = || format!("{} {currency}", format_number(amount));
+
+    let (Ok(code), Ok(decimal)) = (
+        CurrencyType::try_from_str(&currency.to_ascii_uppercase()),
+        Decimal::try_from_f64(amount, FloatPrecision::RoundTrip),
+    ) else {
+        return fallback();
+    };
+
+    let prefs: CurrencyFormatterPreferences = (&icu_locale(&locale())).into();
+    let formatter = match style {
+        CurrencyStyle::Symbol => CurrencyFormatter::try_new_symbol(prefs, code, Default::default()),
+        CurrencyStyle::Narrow => CurrencyFormatter::try_new_symbol_narrow(prefs, code, Default::default()),
+        CurrencyStyle::Code => CurrencyFormatter::try_new_code(prefs, code, Default::default()),
+        CurrencyStyle::Name => CurrencyFormatter::try_new_name(prefs, code),
+    };
+
+    match formatter {
+        Ok(formatter) => formatter.format_fixed_decimal(&decimal).to_string(),
+        Err(_) => fallback(),
+    }
+}
+
+/// Joins items into an "and" list for the active locale
+///
+/// # Parameters
+/// * `items` - The items to join, in order
+///
+/// # Return
+/// Returns the items joined with the locale's separators and conjunction
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::format_list(&["apples", "pears", "plums"]), "apples, pears, and plums");
+/// ```
+pub fn format_list<T: AsRef<str>>(items: &[T]) -> String {
+    format_list_with(items, ListStyle::And)
+}
+
+/// Joins items into a list for the active locale, choosing the conjunction
+///
+/// # Parameters
+/// * `items` - The items to join, in order
+/// * `style` - Whether the list reads as "and" or "or"
+///
+/// # Return
+/// Returns the items joined with the locale's separators and conjunction
+///
+/// # Examples
+/// ```
+///
+/// use i18n::ListStyle;
+///
+/// i18n::set_locale("es-MX").unwrap();
+/// assert_eq!(i18n::format_list_with(&["manzanas", "peras"], ListStyle::Or), "manzanas o peras");
+/// ```
+pub fn format_list_with<T: AsRef<str>>(items: &[T], style: ListStyle) -> String {
+    let prefs = (&icu_locale(&locale())).into();
+    let options = ListFormatterOptions::default().with_length(ListLength::Wide);
+    let formatter = match style {
+        ListStyle::And => ListFormatter::try_new_and(prefs, options),
+        ListStyle::Or => ListFormatter::try_new_or(prefs, options),
+    };
+
+    let items = items.iter().map(AsRef::as_ref);
+    match formatter {
+        Ok(formatter) => formatter.format_to_string(items),
+        Err(_) => items.collect::<Vec<_>>().join(", "),
+    }
+}
@@ -0,0 +1,149 @@
+use fluent_syntax::ast::InlineExpression;
+use std::collections::BTreeMap;
+
+/// Messages by ID, with their value and attributes
+type Entries = BTreeMap<String, (Option<String>, Vec<(String, String)>)>;
+
+/// FTL messages assembled from patterns keyed by message ID or `message.attribute`
+///
+/// Patterns are FTL without the indentation of their continuation lines.
+#[cfg_attr(not(any(feature = "arb", feature = "csv", feature = "sqlx", feature = "tms", feature = "xliff")), allow(dead_code))]
+#[derive(Debug, Default)]
+pub(crate) struct Messages {
+    messages: Entries,
+}
+
+#[cfg_attr(not(any(feature = "arb", feature = "csv", feature = "sqlx", feature = "tms", feature = "xliff")), allow(dead_code))]
+impl Messages {
+    /// Sets the value of a message, or one of its attributes
+    pub(crate) fn insert(&mut self, key: &str, pattern: String) {
+        let message = match key.split_once('.') {
+            Some((id, attr)) => {
+                let message = self.messages.entry(id.to_string()).or_default();
+                message.1.retain(|(a, _)| a != attr);
+                message.1.push((attr.to_string(), pattern));
+                return;
+            }
+            None => self.messages.entry(key.to_string()).or_default(),
+        };
+
+        message.0 = Some(pattern);
+    }
+
+    /// Writes the messages as FTL, sorted by ID
+    pub(crate) fn finish(self) -> String {
+        let mut ftl = String::new();
+
+        for (id, (value, attributes)) in self.messages {
+            match value {
+                Some(value) => ftl.push_str(&format!("{id} = {}\n", indent(&value, "    "))),
+                None => ftl.push_str(&format!("{id} =\n")),
+            }
+
+            for (attr, value) in attributes {
+                ftl.push_str(&format!("    .{attr} = {}\n", indent(&value, "        ")));
+            }
+        }
+
+        ftl
+    }
+}
+
+/// Checks that a pattern read from a translation tool is valid FTL
+///
+/// Returns the first syntax error otherwise.
+#[cfg_attr(not(any(feature = "csv", feature = "sqlx", feature = "tms", feature = "xliff")), allow(dead_code))]
+pub(crate) fn check(key: &str, pattern: &str) -> Result<(), String> {
+    let id = key.split_once('.').map_or(key, |(id, _)| id);
+    let ftl = format!("{id} = {}\n", indent(pattern, "    "));
+
+    match fluent_syntax::parser::parse(ftl.as_str()) {
+        Err((_, errors)) if !errors.is_empty() => Err(errors[0].to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Indents the continuation lines of an FTL pattern
+#[cfg_attr(not(any(feature = "arb", feature = "csv", feature = "sqlx", feature = "tms", feature = "xliff")), allow(dead_code))]
+pub(crate) fn indent(pattern: &str, indent: &str) -> String {
+    pattern.replace('\n', &format!("\n{indent}"))
+}
+
+/// The text of an FTL pattern, built from literal text and variables
+///
+/// Literal text is escaped so it reads back unchanged: braces become string
+/// literals, and characters that would start a variant, an attribute, or be
+/// dropped as indentation at the beginning of a line are quoted.
+#[cfg_attr(not(any(feature = "arb", feature = "gettext", feature = "json", feature = "yaml")), allow(dead_code))]
+#[derive(Debug)]
+pub(crate) struct Pattern {
+    text: String,
+    indent: String,
+    line_start: bool,
+}
+
+#[cfg_attr(not(any(feature = "arb", feature = "gettext", feature = "json", feature = "yaml")), allow(dead_code))]
+impl Pattern {
+    /// Starts a pattern whose continuation lines are indented with `indent`
+    pub(crate) fn new(indent: &str) -> Self {
+        Self { text: String::new(), indent: indent.to_string(), line_start: true }
+    }
+
+    /// Appends literal text
+    pub(crate) fn text(&mut self, text: &str) {
+        for c in text.chars() {
+            match c {
+                '\n' => {
+                    self.text.push('\n');
+                    self.text.push_str(&self.indent);
+                    self.line_start = true;
+                    continue;
+                }
+                '{' | '}' => self.quote(c),
+                ' ' | '[' | '*' | '.' if self.line_start => self.quote(c),
+                _ => self.text.push(c),
+            }
+
+            self.line_start = false;
+        }
+    }
+
+    /// Appends a `{ $name }` placeable
+    pub(crate) fn variable(&mut self, name: &str) {
+        self.text.push_str("{ $");
+        self.text.push_str(name);
+        self.text.push_str(" }");
+        self.line_start = false;
+    }
+
+    /// Appends FTL written by the caller, such as a select expression
+    #[cfg_attr(not(feature = "arb"), allow(dead_code))]
+    pub(crate) fn raw(&mut self, ftl: &str) {
+        self.text.push_str(ftl);
+        self.line_start = false;
+    }
+
+    /// Returns the pattern, quoting empty ones so the message stays valid
+    pub(crate) fn finish(self) -> String {
+        if self.text.is_empty() { "{\"\"}".to_string() } else { self.text }
+    }
+
+    fn quote(&mut self, c: char) {
+        self.text.push_str("{\"");
+        self.text.push(c);
+        self.text.push_str("\"}");
+    }
+}
+
+/// Returns the variable an expression refers to, as in `$count` or `NUMBER($count)`
+#[cfg_attr(not(any(feature = "arb", feature = "json", feature = "mobile")), allow(dead_code))]
+pub(crate) fn variable<'a>(expression: &InlineExpression<&'a str>) -> Option<&'a str> {
+    match expression {
+        InlineExpression::VariableReference { id } => Some(id.name),
+        InlineExpression::FunctionReference { id, arguments } if id.name == "NUMBER" => match arguments.positional.as_slice() {
+            [InlineExpression::VariableReference { id }] => Some(id.name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
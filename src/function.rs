@@ -0,0 +1,89 @@
+use crate::catalog::Bundle;
+use crate::{ERROR_LOCK, I18N, I18nError, domain};
+use fluent_templates::fluent_bundle::{FluentArgs, FluentValue};
+use std::sync::{Arc, RwLock};
+
+pub(crate) type Function = Arc<dyn for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync>;
+
+/// Functions registered by the application, installed in every bundle
+static FUNCTIONS: RwLock<Vec<(String, Function)>> = RwLock::new(Vec::new());
+
+/// Registers a custom function callable from FTL messages
+///
+/// The function receives the positional and named arguments of the call and
+/// is available in every locale, including after reloads. It can be
+/// registered before or after translations are initialized. Useful for
+/// dynamic selectors:
+///
+/// ```ftl
+/// download = { PLATFORM() ->
+///     [windows] Download for Windows
+///    *[other] Download for { PLATFORM() }
+/// }
+/// ```
+///
+/// # Parameters
+/// * `name` - The name used in FTL, conventionally upper case
+/// * `function` - The implementation, returning `FluentValue::Error` on bad input
+///
+/// A registered function takes precedence over a built-in one of the same
+/// name, such as `ORDINAL`, in every locale, so applications can replace it.
+/// Registering a name twice is an error instead, as the first function may
+/// be relied upon elsewhere.
+///
+/// # Return
+/// Returns an error if a function with the same name is already registered
+///
+/// # Examples
+/// ```
+///
+/// i18n::register_function("PLATFORM", |_positional, _named| {
+///     std::env::consts::OS.into()
+/// }).unwrap();
+/// assert!(i18n::register_function("PLATFORM", |_positional, _named| "linux".into()).is_err());
+///
+/// // Replaces the built-in ORDINAL, also in translations loaded before
+/// i18n::test_loader(&[("en-US", "podium = You came { ORDINAL($place) }")]);
+/// i18n::register_function("ORDINAL", |positional, _named| match positional {
+///     [i18n::FluentValue::Number(number)] => format!("#{}", number.value).into(),
+///     _ => i18n::FluentValue::Error,
+/// }).unwrap();
+///
+/// assert_eq!(i18n::new("podium").set_args("place", 2).build(), "You came #2");
+/// ```
+pub fn register_function<T, F>(name: T, function: F) -> Result<(), I18nError>
+where
+    T: ToString,
+    F: for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync + 'static,
+{
+    let name = name.to_string();
+    let function: Function = Arc::new(function);
+
+    {
+        let mut functions = FUNCTIONS.write().expect(ERROR_LOCK);
+        if functions.iter().any(|(n, _)| *n == name) {
+            return Err(I18nError::Function(name));
+        }
+
+        functions.push((name, function));
+    }
+
+    // Bundles built later pick the function up from the registry
+    if let Some(i18n) = I18N.get() {
+        i18n.catalog.write().expect(ERROR_LOCK).install_functions();
+    }
+
+    for i18n in domain::registered() {
+        i18n.catalog.write().expect(ERROR_LOCK).install_functions();
+    }
+
+    Ok(())
+}
+
+/// Adds every registered function to `bundle`, before the built-in ones
+pub(crate) fn install(bundle: &mut Bundle) {
+    for (name, function) in FUNCTIONS.read().expect(ERROR_LOCK).iter() {
+        let function = Arc::clone(function);
+        let _ = bundle.add_function(name, move |positional, named| function(positional, named));
+    }
+}
@@ -0,0 +1,910 @@
+use crate::ftl::Pattern;
+use crate::keys;
+use crate::plural::{self, PluralCategory};
+use crate::source::{collect_files, loader_error};
+use crate::{I18nError, LanguageIdentifier, LocaleSource};
+use intl_pluralrules::PluralRuleType;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the argument selecting the plural form
+const COUNT: &str = "count";
+
+/// Separator between the context and the message ID in MO files
+const CONTEXT: char = '\u{4}';
+
+/// CLDR plural categories, in the order their variants are written
+const CATEGORIES: [PluralCategory; 6] = [
+    PluralCategory::Zero,
+    PluralCategory::One,
+    PluralCategory::Two,
+    PluralCategory::Few,
+    PluralCategory::Many,
+    PluralCategory::Other,
+];
+
+/// Numbers `Plural-Forms` is evaluated for, from 0
+const SAMPLES: u64 = 1000;
+
+/// Bound below which numbers may get an exact variant such as `[0]`
+///
+/// Plural rules repeat every hundred numbers at most, so a form picked for
+/// higher numbers than its CLDR category's usual form is not an exception.
+const EXCEPTIONS: u64 = 100;
+
+/// Operators of `Plural-Forms` expressions, two-character ones first
+const OPERATORS: [&str; 18] = ["||", "&&", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!", "?", ":", "(", ")"];
+
+/// Binary operators of `Plural-Forms` expressions, from the loosest binding
+const LEVELS: [&[&str]; 6] = [&["||"], &["&&"], &["==", "!="], &["<=", ">=", "<", ">"], &["+", "-"], &["*", "/", "%"]];
+
+/// A translated gettext entry
+#[derive(Debug, Default)]
+struct Entry {
+    context: Option<String>,
+    id: Option<String>,
+    plural: bool,
+    strings: Vec<String>,
+    fuzzy: bool,
+}
+
+/// Token of a `Plural-Forms` expression
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    N,
+    Number(u64),
+    Operator(&'static str),
+}
+
+/// A parsed `plural=` expression, computing the form index of a number `n`
+#[derive(Debug)]
+enum Rule {
+    N,
+    Number(u64),
+    Not(Box<Rule>),
+    Binary(&'static str, Box<Rule>, Box<Rule>),
+    Choice(Box<Rule>, Box<Rule>, Box<Rule>),
+}
+
+/// Recursive descent parser of `Plural-Forms` expressions, following C precedence
+struct Parser<'t> {
+    tokens: &'t [Token],
+    position: usize,
+}
+
+/// Field of a PO entry a string continuation line belongs to
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    Plural,
+    String(usize),
+}
+
+/// Source reading gettext catalogs from one sub-directory per locale
+///
+/// Every `.po` and `.mo` file below a locale's directory (including the usual
+/// `LC_MESSAGES` sub-directory) is converted with `po_to_ftl` or `mo_to_ftl`.
+/// Directory names may use gettext's `es_MX` or `es_MX.UTF-8` spelling.
+///
+/// # Examples
+/// ```no_run
+///
+/// use i18n::{GettextSource, I18nConfig};
+/// use std::sync::Arc;
+///
+/// i18n::init_with(I18nConfig {
+///     source: Some(Arc::new(GettextSource::new("./po/"))),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct GettextSource {
+    dir: PathBuf,
+}
+
+impl GettextSource {
+    /// Creates a source reading from `dir`
+    ///
+    /// # Parameters
+    /// * `dir` - Directory containing one sub-directory of PO or MO files per locale
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { dir: dir.into() }
+    }
+
+    /// Lists the locale sub-directories along with their parsed identifiers
+    fn entries(&self) -> Result<Vec<(LanguageIdentifier, PathBuf)>, I18nError> {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(&self.dir).map_err(|e| loader_error(&self.dir, e))? {
+            let entry = entry.map_err(|e| loader_error(&self.dir, e))?;
+            if !entry.path().is_dir() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let locale = name
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .replace('_', "-")
+                .parse()
+                .map_err(|_| I18nError::InvalidLocale(name.clone()))?;
+
+            entries.push((locale, entry.path()));
+        }
+
+        Ok(entries)
+    }
+}
+
+impl LocaleSource for GettextSource {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        Ok(self.entries()?.into_iter().map(|(locale, _)| locale).collect())
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        Ok(self.files(locale)?.into_iter().map(|(_, ftl)| ftl).collect())
+    }
+
+    fn files(&self, locale: &LanguageIdentifier) -> Result<Vec<(PathBuf, String)>, I18nError> {
+        let mut paths = Vec::new();
+        for (_, dir) in self.entries()?.into_iter().filter(|(l, _)| l == locale) {
+            collect_files(&dir, &["po", "mo"], &mut paths)?;
+        }
+
+        paths.sort();
+        paths.into_iter().map(|path| Ok((path.clone(), convert(&path, locale)?))).collect()
+    }
+}
+
+/// Converts a PO or MO file, whose plural rules default to those of `locale`
+fn convert(path: &Path, locale: &LanguageIdentifier) -> Result<String, I18nError> {
+    let bytes = fs::read(path).map_err(|e| loader_error(path, e))?;
+
+    let entries = if path.extension().is_some_and(|ext| ext == "mo") {
+        mo_entries(&bytes)
+    } else {
+        String::from_utf8(bytes)
+            .map_err(|_| "not valid UTF-8".to_string())
+            .and_then(|po| po_entries(&po))
+    };
+
+    entries
+        .and_then(|entries| ftl(&entries, Some(locale)))
+        .map_err(|reason| I18nError::Loader(format!("{}: {reason}", path.display())))
+}
+
+/// Converts a gettext PO catalog to FTL
+///
+/// Each translated entry becomes a message. A `msgid` that is a valid Fluent
+/// identifier is kept as the message ID; other source texts are turned into
+/// one (`"Hello, world!"` becomes `hello-world`), prefixed by the `msgctxt` if
+/// there is one. Placeholders are converted: `%(name)s` becomes `{ $name }`,
+/// and positional ones such as `%s` or `%2$d` become `{ $arg1 }`, `{ $arg2 }`,
+/// and so on. Untranslated, fuzzy, and obsolete entries and the header are
+/// skipped, as are entries whose ID was already used.
+///
+/// Plural entries select their form on `$count`, which also takes the place
+/// of each form's first positional integer placeholder (the `%d`). The
+/// header's `Plural-Forms` expression is evaluated against the CLDR rules of
+/// its `Language` to find the category of every form; numbers that gettext
+/// treats apart from the rest of their category, such as zero in Latvian, get
+/// an exact variant like `[0]`.
+///
+/// # Parameters
+/// * `po` - The contents of a `.po` file
+///
+/// # Return
+/// Returns the FTL source, or an error pointing at the first malformed line,
+/// or if plural entries come without a `Plural-Forms` and `Language` header
+/// whose forms map onto the CLDR categories
+///
+/// # Examples
+/// ```
+///
+/// let po = r#"
+/// msgid ""
+/// msgstr ""
+/// "Language: es\n"
+/// "Plural-Forms: nplurals=2; plural=(n != 1);\n"
+///
+/// msgid "greeting"
+/// msgstr "¡Hola, %(name)s!"
+///
+/// msgid "One email"
+/// msgid_plural "%d emails"
+/// msgstr[0] "Un correo"
+/// msgstr[1] "%d correos"
+/// "#;
+///
+/// let ftl = i18n::po_to_ftl(po).unwrap();
+/// assert_eq!(ftl, "\
+/// greeting = ¡Hola, { $name }!
+/// one-email = { $count ->
+///     [one] Un correo
+///    *[other] { $count } correos
+/// }
+/// ");
+/// ```
+pub fn po_to_ftl(po: &str) -> Result<String, I18nError> {
+    po_entries(po).and_then(|entries| ftl(&entries, None)).map_err(I18nError::Loader)
+}
+
+/// Converts a compiled gettext MO catalog to FTL
+///
+/// Entries are mapped like `po_to_ftl` does. MO files carry no flags, so
+/// fuzzy entries compiled into them are kept.
+///
+/// # Parameters
+/// * `mo` - The contents of a `.mo` file
+///
+/// # Return
+/// Returns the FTL source, or an error if the file is truncated, not an MO
+/// catalog, or has plural rules that do not map onto the CLDR categories
+pub fn mo_to_ftl(mo: &[u8]) -> Result<String, I18nError> {
+    mo_entries(mo).and_then(|entries| ftl(&entries, None)).map_err(I18nError::Loader)
+}
+
+fn po_entries(po: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    let mut entry = Entry::default();
+    let mut field = None;
+
+    for (number, line) in po.lines().enumerate() {
+        let line = line.trim();
+        let error = |reason: &str| format!("line {}: {reason}", number + 1);
+
+        if line.is_empty() || line.starts_with("#~") {
+            continue;
+        }
+
+        // Comments and keywords after a translation start the next entry
+        let starts_entry = line.starts_with('#') || line.starts_with("msgctxt") || line.starts_with("msgid ");
+        if starts_entry && !entry.strings.is_empty() {
+            entries.push(std::mem::take(&mut entry));
+        }
+
+        if let Some(flags) = line.strip_prefix("#,") {
+            entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            continue;
+        }
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let (keyword, value) = match line.split_once(char::is_whitespace) {
+            Some((keyword, value)) if !line.starts_with('"') => (keyword, value.trim_start()),
+            _ => ("", line),
+        };
+        let value = unquote(value).ok_or_else(|| error("expected a quoted string"))?;
+
+        field = match keyword {
+            "" => field,
+            "msgctxt" => Some(Field::Context),
+            "msgid" => Some(Field::Id),
+            "msgid_plural" => Some(Field::Plural),
+            "msgstr" => Some(Field::String(0)),
+            keyword => {
+                let index = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse().ok())
+                    .ok_or_else(|| error(&format!("unknown keyword {keyword}")))?;
+
+                Some(Field::String(index))
+            }
+        };
+
+        let target = match field.ok_or_else(|| error("string outside of an entry"))? {
+            Field::Context => entry.context.get_or_insert_default(),
+            Field::Id => entry.id.get_or_insert_default(),
+            Field::Plural => {
+                entry.plural = true;
+                continue;
+            }
+            Field::String(index) => {
+                if entry.strings.len() <= index {
+                    entry.strings.resize(index + 1, String::new());
+                }
+
+                &mut entry.strings[index]
+            }
+        };
+
+        target.push_str(&value);
+    }
+
+    entries.push(entry);
+
+    Ok(entries)
+}
+
+/// Parses a quoted PO string, resolving its escape sequences
+fn unquote(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+
+        unquoted.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            c @ ('"' | '\\') => c,
+            _ => return None,
+        });
+    }
+
+    Some(unquoted)
+}
+
+fn mo_entries(mo: &[u8]) -> Result<Vec<Entry>, String> {
+    let truncated = || "truncated MO file".to_string();
+
+    let word = |offset: usize, big_endian: bool| -> Result<usize, String> {
+        let end = offset.checked_add(4).ok_or_else(truncated)?;
+        let bytes: [u8; 4] = mo.get(offset..end).ok_or_else(truncated)?.try_into().map_err(|_| truncated())?;
+        let word = if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) };
+
+        usize::try_from(word).map_err(|_| truncated())
+    };
+
+    let big_endian = match word(0, false)? {
+        0x950412de => false,
+        0xde120495 => true,
+        _ => return Err("not an MO file".to_string()),
+    };
+
+    let string = |table: usize, index: usize| -> Result<String, String> {
+        let descriptor = index.checked_mul(8).and_then(|i| i.checked_add(table)).ok_or_else(truncated)?;
+        let length = word(descriptor, big_endian)?;
+        let offset = word(descriptor.checked_add(4).ok_or_else(truncated)?, big_endian)?;
+        let end = offset.checked_add(length).ok_or_else(truncated)?;
+        let bytes = mo.get(offset..end).ok_or_else(truncated)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| "not valid UTF-8".to_string())
+    };
+
+    let count = word(8, big_endian)?;
+    let originals = word(12, big_endian)?;
+    let translations = word(16, big_endian)?;
+
+    let mut entries = Vec::new();
+    for index in 0..count {
+        let original = string(originals, index)?;
+        let (context, original) = match original.split_once(CONTEXT) {
+            Some((context, original)) => (Some(context.to_string()), original.to_string()),
+            None => (None, original),
+        };
+
+        let mut ids = original.split('\0');
+        entries.push(Entry {
+            context,
+            id: ids.next().map(str::to_string),
+            plural: ids.next().is_some(),
+            strings: string(translations, index)?.split('\0').map(str::to_string).collect(),
+            fuzzy: false,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Writes the translated entries as FTL messages
+///
+/// Plural forms are mapped with the rules of the header's `Language`, or of
+/// `locale` if it names none.
+fn ftl(entries: &[Entry], locale: Option<&LanguageIdentifier>) -> Result<String, String> {
+    let mut seen = HashSet::new();
+    let mut ftl = String::new();
+
+    let plural = entries.iter().any(|entry| entry.plural && entry.strings.len() > 1 && !entry.fuzzy);
+    let variants = if plural { plural_variants(entries, locale)? } else { Vec::new() };
+
+    for entry in entries {
+        let Some(source) = entry.id.as_deref().filter(|id| !id.is_empty()) else {
+            continue;
+        };
+
+        if entry.fuzzy || entry.strings.is_empty() || entry.strings.iter().any(String::is_empty) {
+            continue;
+        }
+
+        let id = match &entry.context {
+            Some(context) => keys::identifier(&format!("{context}-{source}")),
+            None => keys::identifier(source),
+        };
+
+        let Some(id) = id.filter(|id| seen.insert(id.clone())) else {
+            continue;
+        };
+
+        if !entry.plural || entry.strings.len() == 1 {
+            ftl.push_str(&format!("{id} = {}\n", pattern(&entry.strings[0], "    ", false)));
+            continue;
+        }
+
+        // Entries lacking a form are untranslated
+        let Some(forms) = variants
+            .iter()
+            .map(|(key, form)| entry.strings.get(*form).map(|string| (key, string)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+
+        ftl.push_str(&format!("{id} = {{ ${COUNT} ->\n"));
+        for (i, (key, string)) in forms.iter().enumerate() {
+            let marker = if i == forms.len() - 1 { "   *" } else { "    " };
+            ftl.push_str(&format!("{marker}[{key}] {}\n", pattern(string, "        ", true)));
+        }
+        ftl.push_str("}\n");
+    }
+
+    Ok(ftl)
+}
+
+/// Returns the variant key and gettext form of every plural variant, the default last
+///
+/// `Plural-Forms` is evaluated for the numbers up to `SAMPLES`. Each CLDR
+/// category the locale uses gets the form of its highest number, and lower
+/// numbers whose form differs get an exact variant. Categories split between
+/// forms beyond `EXCEPTIONS` make the catalog ambiguous.
+fn plural_variants(entries: &[Entry], locale: Option<&LanguageIdentifier>) -> Result<Vec<(String, usize)>, String> {
+    let header = entries
+        .iter()
+        .find(|entry| entry.id.as_deref() == Some("") && entry.context.is_none())
+        .and_then(|entry| entry.strings.first())
+        .map_or("", String::as_str);
+
+    let field = |name: &str| {
+        header
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    };
+
+    let language = field("Language").and_then(|language| language.replace('_', "-").parse().ok());
+    let locale = language.as_ref().or(locale).ok_or("plural entries need a Language header")?;
+
+    let forms = field("Plural-Forms").ok_or("plural entries need a Plural-Forms header")?;
+    let (mut count, mut rule) = (None, None);
+    for part in forms.split(';') {
+        match part.split_once('=').map(|(key, value)| (key.trim(), value.trim())) {
+            Some(("nplurals", value)) => count = value.parse::<u64>().ok(),
+            Some(("plural", value)) => rule = Rule::parse(value),
+            _ => {}
+        }
+    }
+
+    let invalid = || format!("invalid Plural-Forms: {forms}");
+    let (count, rule) = count.zip(rule).ok_or_else(invalid)?;
+
+    let mut samples = Vec::new();
+    for n in 0..=SAMPLES {
+        let form = rule.evaluate(n).filter(|form| *form < count).ok_or_else(invalid)?;
+        samples.push((plural::select(locale, n, PluralRuleType::CARDINAL), form as usize, n));
+    }
+
+    let mut exact = Vec::new();
+    let mut categories = Vec::new();
+    for category in CATEGORIES {
+        let numbers: Vec<_> = samples.iter().filter(|(c, _, _)| *c == category).collect();
+        let Some((_, usual, _)) = numbers.last() else {
+            continue;
+        };
+
+        for (_, form, n) in numbers.iter().filter(|(_, form, _)| form != usual) {
+            if *n >= EXCEPTIONS {
+                return Err(format!("Plural-Forms does not match the {category} category of {locale}"));
+            }
+
+            exact.push((n.to_string(), *form));
+        }
+
+        categories.push((category.to_string(), *usual));
+    }
+
+    Ok(exact.into_iter().chain(categories).collect())
+}
+
+/// Converts a gettext string to an FTL pattern, translating its placeholders
+///
+/// In plural forms, the first positional integer placeholder is the number
+/// the form was selected for, and becomes `$count`.
+fn pattern(text: &str, indent: &'static str, plural: bool) -> String {
+    let mut pattern = Pattern::new(indent);
+    let mut position = 0;
+    let mut counted = !plural;
+    let mut rest = text;
+
+    while let Some(start) = rest.find('%') {
+        pattern.text(&rest[..start]);
+        rest = &rest[start..];
+
+        match placeholder(rest) {
+            Some((length, name)) => {
+                let count = !counted && !rest[1..].starts_with('(') && matches!(rest.as_bytes()[length - 1], b'd' | b'i' | b'u');
+                let name = name.unwrap_or_else(|| {
+                    position += 1;
+                    format!("arg{position}")
+                });
+                let name = if count {
+                    counted = true;
+                    COUNT.to_string()
+                } else {
+                    name
+                };
+
+                pattern.variable(&name);
+                rest = &rest[length..];
+            }
+            None if rest.starts_with("%%") => {
+                pattern.text("%");
+                rest = &rest[2..];
+            }
+            None => {
+                pattern.text("%");
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    pattern.text(rest);
+    pattern.finish()
+}
+
+impl Rule {
+    /// Parses the `plural=` expression of a `Plural-Forms` header
+    fn parse(source: &str) -> Option<Self> {
+        let tokens = tokens(source)?;
+        let mut parser = Parser { tokens: &tokens, position: 0 };
+        let rule = parser.choice()?;
+
+        (parser.position == tokens.len()).then_some(rule)
+    }
+
+    /// Returns the form index of `n`, or `None` on arithmetic errors such as a
+    /// division by zero
+    fn evaluate(&self, n: u64) -> Option<u64> {
+        let boolean = |value: bool| Some(u64::from(value));
+
+        match self {
+            Self::N => Some(n),
+            Self::Number(number) => Some(*number),
+            Self::Not(rule) => boolean(rule.evaluate(n)? == 0),
+            Self::Choice(condition, then, otherwise) => {
+                if condition.evaluate(n)? != 0 { then.evaluate(n) } else { otherwise.evaluate(n) }
+            }
+            Self::Binary(operator, left, right) => {
+                let (left, right) = (left.evaluate(n)?, right.evaluate(n)?);
+
+                match *operator {
+                    "||" => boolean(left != 0 || right != 0),
+                    "&&" => boolean(left != 0 && right != 0),
+                    "==" => boolean(left == right),
+                    "!=" => boolean(left != right),
+                    "<=" => boolean(left <= right),
+                    ">=" => boolean(left >= right),
+                    "<" => boolean(left < right),
+                    ">" => boolean(left > right),
+                    "+" => left.checked_add(right),
+                    "-" => left.checked_sub(right),
+                    "*" => left.checked_mul(right),
+                    "/" => left.checked_div(right),
+                    _ => left.checked_rem(right),
+                }
+            }
+        }
+    }
+}
+
+impl Parser<'_> {
+    /// Consumes `operator` if it is the next token
+    fn eat(&mut self, operator: &str) -> bool {
+        let found = matches!(self.tokens.get(self.position), Some(Token::Operator(next)) if *next == operator);
+        self.position += usize::from(found);
+
+        found
+    }
+
+    /// Parses a `condition ? then : otherwise` expression, or a tighter one
+    fn choice(&mut self) -> Option<Rule> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Some(condition);
+        }
+
+        let then = self.choice()?;
+        if !self.eat(":") {
+            return None;
+        }
+
+        let otherwise = self.choice()?;
+        Some(Rule::Choice(Box::new(condition), Box::new(then), Box::new(otherwise)))
+    }
+
+    /// Parses the binary operators of `LEVELS[level]`, left-associatively, and tighter ones
+    fn binary(&mut self, level: usize) -> Option<Rule> {
+        let Some(operators) = LEVELS.get(level) else {
+            return self.unary();
+        };
+
+        let mut left = self.binary(level + 1)?;
+        while let Some(operator) = operators.iter().find(|operator| self.eat(operator)) {
+            left = Rule::Binary(operator, Box::new(left), Box::new(self.binary(level + 1)?));
+        }
+
+        Some(left)
+    }
+
+    /// Parses a negation, a parenthesized expression, `n`, or a number
+    fn unary(&mut self) -> Option<Rule> {
+        if self.eat("!") {
+            return Some(Rule::Not(Box::new(self.unary()?)));
+        }
+
+        if self.eat("(") {
+            let rule = self.choice()?;
+            return self.eat(")").then_some(rule);
+        }
+
+        let token = *self.tokens.get(self.position)?;
+        self.position += 1;
+
+        match token {
+            Token::N => Some(Rule::N),
+            Token::Number(number) => Some(Rule::Number(number)),
+            Token::Operator(_) => None,
+        }
+    }
+}
+
+/// Splits a `Plural-Forms` expression into tokens
+fn tokens(source: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source.trim_start();
+
+    while !rest.is_empty() {
+        if let Some(operator) = OPERATORS.iter().find(|operator| rest.starts_with(**operator)) {
+            tokens.push(Token::Operator(operator));
+            rest = &rest[operator.len()..];
+        } else if let Some(after) = rest.strip_prefix('n') {
+            tokens.push(Token::N);
+            rest = after;
+        } else {
+            let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            tokens.push(Token::Number(rest[..digits].parse().ok()?));
+            rest = &rest[digits..];
+        }
+
+        rest = rest.trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Parses a printf placeholder at the start of `text`
+///
+/// Returns its length and, for `%(name)s` and `%2$s` placeholders, the
+/// argument name; positional placeholders such as `%s` have none.
+fn placeholder(text: &str) -> Option<(usize, Option<String>)> {
+    let bytes = text.as_bytes();
+    let mut end = 1;
+    let mut name = None;
+
+    if bytes.get(end) == Some(&b'(') {
+        let close = text[end..].find(')')? + end;
+        let id = &text[end + 1..close];
+        if !keys::is_identifier(id) {
+            return None;
+        }
+
+        name = Some(id.to_string());
+        end = close + 1;
+    } else {
+        let digits = bytes[end..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits > 0 && bytes.get(end + digits) == Some(&b'$') {
+            name = Some(format!("arg{}", &text[end..end + digits]));
+            end += digits + 1;
+        }
+    }
+
+    // Flags, width, precision, and length modifiers
+    end += bytes[end..]
+        .iter()
+        .take_while(|b| b"-+#0123456789.lhqjzt".contains(b))
+        .count();
+
+    match bytes.get(end) {
+        Some(b's' | b'd' | b'i' | b'u' | b'f' | b'F' | b'e' | b'E' | b'g' | b'G' | b'x' | b'X' | b'o' | b'c') => {
+            Some((end + 1, name))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a little-endian MO file from `(original, translation)` pairs
+    fn mo(entries: &[(&str, &str)]) -> Vec<u8> {
+        let table = 28;
+        let strings = table + 16 * entries.len();
+        let mut header = Vec::new();
+        let mut descriptors = Vec::new();
+        let mut data = Vec::new();
+
+        for word in [0x950412de, 0, entries.len(), table, table + 8 * entries.len(), 0, 0] {
+            header.extend_from_slice(&(word as u32).to_le_bytes());
+        }
+
+        let mut add = |text: &str, descriptors: &mut Vec<u8>| {
+            descriptors.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            descriptors.extend_from_slice(&((strings + data.len()) as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+            data.push(0);
+        };
+
+        let mut translations = Vec::new();
+        for (original, _) in entries {
+            add(original, &mut descriptors);
+        }
+        for (_, translation) in entries {
+            add(translation, &mut translations);
+        }
+
+        [header, descriptors, translations, data].concat()
+    }
+
+    /// Returns a PO header for `language` with the `plural` rule of `count` forms
+    fn header(language: &str, count: usize, plural: &str) -> String {
+        format!("msgid \"\"\nmsgstr \"\"\n\"Language: {language}\\n\"\n\"Plural-Forms: nplurals={count}; plural={plural};\\n\"\n")
+    }
+
+    #[test]
+    fn plural_numbers_are_the_count() {
+        let po = header("en", 2, "n != 1") + r#"
+msgid "files"
+msgid_plural "files"
+msgstr[0] "%s has %d file"
+msgstr[1] "%s has %d files"
+"#;
+
+        assert_eq!(po_to_ftl(&po).unwrap(), "\
+files = { $count ->
+    [one] { $arg1 } has { $count } file
+   *[other] { $arg1 } has { $count } files
+}
+");
+    }
+
+    #[test]
+    fn plural_forms_follow_the_header() {
+        let po = header("lv", 3, "(n%10==1 && n%100!=11 ? 0 : n != 0 ? 1 : 2)") + r#"
+msgid "file"
+msgid_plural "files"
+msgstr[0] "%d fails"
+msgstr[1] "%d faili"
+msgstr[2] "nav failu"
+"#;
+
+        assert_eq!(po_to_ftl(&po).unwrap(), "\
+file = { $count ->
+    [0] nav failu
+    [zero] { $count } faili
+    [one] { $count } fails
+   *[other] { $count } faili
+}
+");
+    }
+
+    #[test]
+    fn plural_forms_without_other_default_to_the_last_category() {
+        let rule = "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)";
+        let po = header("ru", 3, rule) + "msgid \"file\"\nmsgid_plural \"files\"\nmsgstr[0] \"a\"\nmsgstr[1] \"b\"\nmsgstr[2] \"c\"\n";
+
+        assert_eq!(po_to_ftl(&po).unwrap(), "file = { $count ->\n    [one] a\n    [few] b\n   *[many] c\n}\n");
+    }
+
+    #[test]
+    fn plural_forms_contradicting_cldr_are_refused() {
+        let po = header("en", 2, "n%10==1 ? 0 : 1") + "msgid \"file\"\nmsgid_plural \"files\"\nmsgstr[0] \"a\"\nmsgstr[1] \"b\"\n";
+
+        assert!(matches!(po_to_ftl(&po), Err(I18nError::Loader(reason)) if reason.contains("other category of en")));
+    }
+
+    #[test]
+    fn plural_entries_need_a_header() {
+        let po = "msgid \"file\"\nmsgid_plural \"files\"\nmsgstr[0] \"a\"\nmsgstr[1] \"b\"\n";
+
+        assert!(matches!(po_to_ftl(po), Err(I18nError::Loader(reason)) if reason.contains("Language")));
+    }
+
+    #[test]
+    fn plural_rules_evaluate_like_c() {
+        let rule = Rule::parse("n==0 ? 0 : n==1 ? 1 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 2 + 3 * !n").unwrap();
+
+        assert_eq!([0, 1, 2, 5, 11, 102].map(|n| rule.evaluate(n)), [0, 1, 2, 3, 4, 2].map(Some));
+        assert_eq!(Rule::parse("n/0").unwrap().evaluate(1), None);
+        assert!(Rule::parse("(n > 1").is_none());
+    }
+
+    #[test]
+    fn messages_keep_positional_numbers() {
+        let po = "msgid \"total\"\nmsgstr \"%d of %2$d\"\n";
+
+        assert_eq!(po_to_ftl(po).unwrap(), "total = { $arg1 } of { $arg2 }\n");
+    }
+
+    #[test]
+    fn po_entries_skip_fuzzy_and_untranslated() {
+        let po = r#"
+# Translator comment
+#, fuzzy
+msgid "draft"
+msgstr "Entwurf"
+
+msgctxt "menu"
+msgid "Open"
+msgstr "Öffnen"
+
+msgid "quote"
+msgstr ""
+"Sie sagte \"Hallo\"\t"
+"und ging."
+
+msgid "missing"
+msgstr ""
+
+#~ msgid "obsolete"
+#~ msgstr "Veraltet"
+
+msgid "Open"
+msgstr "Offen"
+"#;
+
+        assert_eq!(po_to_ftl(po).unwrap(), "menu-Open = Öffnen\nquote = Sie sagte \"Hallo\"\tund ging.\nOpen = Offen\n");
+    }
+
+    #[test]
+    fn malformed_po_lines_are_reported() {
+        let error = |po: &str| match po_to_ftl(po) {
+            Err(I18nError::Loader(reason)) => reason,
+            result => panic!("unexpected {result:?}"),
+        };
+
+        assert_eq!(error("msgid \"a\"\nmsgstr Hallo\n"), "line 2: expected a quoted string");
+        assert_eq!(error("msgid \"a\"\nmsgstr[x] \"Hallo\"\n"), "line 2: unknown keyword msgstr[x]");
+        assert_eq!(error("\"Hallo\"\n"), "line 1: string outside of an entry");
+    }
+
+    #[test]
+    fn mo_files_convert() {
+        let mo = mo(&[("greeting", "Hallo, %(name)s!"), ("menu\u{4}Open", "Öffnen")]);
+
+        assert_eq!(mo_to_ftl(&mo).unwrap(), "greeting = Hallo, { $name }!\nmenu-Open = Öffnen\n");
+    }
+
+    #[test]
+    fn mo_offsets_past_the_end_are_truncated() {
+        let mut mo = mo(&[("greeting", "Hallo")]);
+        mo[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert!(matches!(mo_to_ftl(&mo), Err(I18nError::Loader(reason)) if reason == "truncated MO file"));
+        assert!(matches!(mo_to_ftl(&mo[..20]), Err(I18nError::Loader(_))));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::LanguageIdentifier;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+thread_local! {
+    static THREAD_LOCALE: RefCell<Option<LanguageIdentifier>> = const { RefCell::new(None) };
+}
+
+/// Guard overriding the active locale of the current thread
+///
+/// Created by `locale_guard`. The previous locale of the thread is restored
+/// when the guard is dropped, so guards can be nested. The guard cannot be
+/// sent to another thread.
+#[derive(Debug)]
+#[must_use = "the locale is restored as soon as the guard is dropped"]
+pub struct LocaleGuard {
+    previous: Option<LanguageIdentifier>,
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for LocaleGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        THREAD_LOCALE.with(|l| *l.borrow_mut() = previous);
+    }
+}
+
+/// Overrides the active locale for the current thread until the guard drops
+///
+/// Useful for batch jobs rendering content in many languages in a loop. Other
+/// threads and the global locale are untouched. An invalid language identifier
+/// is ignored and the current locale stays active.
+///
+/// # Parameters
+/// * `locale` - The language identifier to use (e.g., "ja-JP")
+///
+/// # Return
+/// Returns a guard restoring the previous locale when dropped
+///
+/// # Examples
+/// ```
+///
+/// for (locale, hello) in [("en-US", "Hello"), ("es-MX", "Hola")] {
+///     let _g = i18n::locale_guard(locale);
+///     assert_eq!(i18n::get("hello"), hello);
+/// }
+/// ```
+pub fn locale_guard<T>(locale: T) -> LocaleGuard
+where
+    T: ToString,
+{
+    let locale = locale.to_string().parse().ok();
+    let previous = THREAD_LOCALE.with(|l| {
+        let mut current = l.borrow_mut();
+
+        match locale {
+            Some(locale) => current.replace(locale),
+            None => current.clone(),
+        }
+    });
+
+    LocaleGuard { previous, _thread: PhantomData }
+}
+
+/// Returns the locale set by a guard on the current thread, if any
+pub(crate) fn current() -> Option<LanguageIdentifier> {
+    THREAD_LOCALE.with(|l| l.borrow().clone())
+}
@@ -0,0 +1,194 @@
+use crate::{ERROR_LOCK, I18nBuilder, I18nConfig, I18nError, Inner, LanguageIdentifier, LookupError, new, parse_locale};
+use std::fmt;
+use std::sync::Arc;
+
+/// An independent set of translations
+///
+/// Holds its own catalog, locale, and settings instead of sharing the
+/// process-wide instance configured from the environment, so libraries and
+/// tests can keep as many as they need. Lookups use the handle's locale,
+/// ignoring the global locale and `locale_guard`. Cloning is cheap and shares
+/// the translations.
+///
+/// # Examples
+/// ```
+///
+/// use i18n::{I18n, I18nConfig};
+///
+/// let i18n = I18n::new(I18nConfig {
+///     locale: "es-MX".to_string(),
+///     ..Default::default()
+/// }).unwrap();
+///
+/// let hello = i18n.get("hello");
+/// assert_eq!(hello, "Hola");
+/// assert_eq!(i18n::get("hello"), "Hello");
+/// ```
+#[derive(Clone)]
+pub struct I18n {
+    inner: Arc<Inner>,
+}
+
+impl I18n {
+    /// Loads translations from an explicit configuration
+    ///
+    /// Catalogs registered with `register_embedded` are not merged in, so the
+    /// instance only contains what `config` points at.
+    ///
+    /// # Parameters
+    /// * `config` - The settings used to build the loader
+    ///
+    /// # Return
+    /// Returns an error if the configuration is invalid or the locale
+    /// directory cannot be loaded
+    pub fn new(config: I18nConfig) -> Result<Self, I18nError> {
+        Ok(Self { inner: Arc::new(Inner::new(&config, false)?) })
+    }
+
+    /// Returns the locale lookups of this instance use
+    pub fn locale(&self) -> LanguageIdentifier {
+        self.inner.locales.read().expect(ERROR_LOCK).clone()
+    }
+
+    /// Changes the locale lookups of this instance use
+    ///
+    /// # Parameters
+    /// * `locale` - The language identifier to switch to (e.g., "es-MX")
+    ///
+    /// # Return
+    /// Returns an error if `locale` is not a valid language identifier
+    pub fn set_locale<T>(&self, locale: T) -> Result<(), I18nError>
+    where
+        T: ToString,
+    {
+        *self.inner.locales.write().expect(ERROR_LOCK) = parse_locale(&locale.to_string())?;
+
+        Ok(())
+    }
+
+    /// Lists the locales of this instance
+    ///
+    /// # Return
+    /// Returns the installed language identifiers, sorted
+    pub fn available_locales(&self) -> Vec<LanguageIdentifier> {
+        self.inner.available()
+    }
+
+    /// Retrieves a translation for the given key
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string for this instance's locale
+    pub fn get<T>(&self, key: T) -> String
+    where
+        T: ToString,
+    {
+        self.inner.lookup(&self.locale(), &key.to_string(), None, None)
+    }
+
+    /// Retrieves a translation for the given key, if it exists
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string, or `None` if the message is missing
+    pub fn get_opt<T>(&self, key: T) -> Option<String>
+    where
+        T: ToString,
+    {
+        self.inner.try_lookup(&self.locale(), &key.to_string(), None, None)
+    }
+
+    /// Retrieves a translation for the given key, or a caller-supplied default
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    /// * `default` - The text returned when the message is missing
+    ///
+    /// # Return
+    /// Returns the translated string, or `default` if the message is missing
+    pub fn get_or<T, U>(&self, key: T, default: U) -> String
+    where
+        T: ToString,
+        U: ToString,
+    {
+        self.get_opt(key).unwrap_or_else(|| default.to_string())
+    }
+
+    /// Retrieves a translation for the given key, explaining any failure
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string, or the reason it could not be produced
+    pub fn try_get<T>(&self, key: T) -> Result<String, LookupError>
+    where
+        T: ToString,
+    {
+        self.inner.resolve(&self.locale(), &key.to_string(), None, None)
+    }
+
+    /// Checks whether a translation exists for the given key
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look for
+    ///
+    /// # Return
+    /// Returns `true` if the message exists in this instance's locale or its
+    /// fallback chain
+    pub fn has<T>(&self, key: T) -> bool
+    where
+        T: ToString,
+    {
+        self.inner.has(&self.locale(), &key.to_string())
+    }
+
+    /// Creates a builder looking up a key in this instance
+    ///
+    /// The builder targets this instance's current locale unless told
+    /// otherwise with `locale`.
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns a builder for parameterized translations
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let i18n = i18n::I18n::new(i18n::I18nConfig::default()).unwrap();
+    ///
+    /// let greeting = i18n.builder("greeting")
+    ///     .set_args("name", "Bob")
+    ///     .build();
+    /// assert_eq!(greeting, "Hello, Bob!");
+    /// ```
+    pub fn builder<T>(&self, key: T) -> I18nBuilder
+    where
+        T: ToString,
+    {
+        new(key)
+            .with_bundle(Some(Arc::clone(&self.inner)))
+            .with_locale(self.locale())
+    }
+
+    /// Rebuilds this instance's translations from disk
+    ///
+    /// # Return
+    /// Returns an error if the locale directory cannot be loaded, keeping the
+    /// previous translations in place
+    pub fn reload(&self) -> Result<(), I18nError> {
+        self.inner.reload()
+    }
+}
+
+impl fmt::Debug for I18n {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I18n").field("locale", &self.locale()).finish()
+    }
+}
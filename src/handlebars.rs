@@ -0,0 +1,87 @@
+//! Handlebars integration exposing translations to templates
+//!
+//! ```
+//! let mut handlebars = handlebars::Handlebars::new();
+//! i18n::handlebars::register(&mut handlebars);
+//!
+//! let data = std::collections::BTreeMap::from([("user", "Bob"), ("locale", "es-MX")]);
+//! let text = handlebars.render_template(r#"{{t "greeting" name=user}}"#, &data).unwrap();  // Returns "¡Hola, Bob!"
+//! ```
+
+use crate::I18nBuilder;
+use ::handlebars::{Context, Handlebars, Helper, HelperDef, JsonValue, RenderContext, RenderError, RenderErrorReason, ScopedJson};
+
+/// Name of the hash argument and context field selecting the locale
+const LOCALE: &str = "locale";
+
+/// Helper translating its first parameter, registered as `t` by `register`
+///
+/// Hash arguments are passed to the message (numbers stay numeric for plural
+/// selectors). The locale is taken from a `locale` hash argument, then a
+/// `locale` field of the render data, and defaults to the active locale. The
+/// output is escaped like any other expression, and the helper can be used as
+/// a subexpression.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Translate;
+
+impl HelperDef for Translate {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        ctx: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let key = h.param(0)
+            .and_then(|key| key.value().as_str())
+            .ok_or(RenderErrorReason::ParamNotFoundForIndex("t", 0))?;
+
+        let mut builder = crate::new(key);
+        for (name, value) in h.hash().iter().filter(|(name, _)| **name != LOCALE) {
+            builder = set_arg(builder, name, value.value());
+        }
+
+        let locale = h.hash_get(LOCALE)
+            .map(|locale| locale.value())
+            .or_else(|| ctx.data().get(LOCALE))
+            .and_then(JsonValue::as_str);
+
+        if let Some(locale) = locale {
+            builder = builder.locale(locale);
+        }
+
+        Ok(ScopedJson::Derived(JsonValue::String(builder.build())))
+    }
+}
+
+/// Registers the `t` helper with a Handlebars registry
+///
+/// `{{t "key" name=value}}` translates a key with hash arguments, in the
+/// locale given by a `locale` hash argument or render data field. Attributes
+/// are addressed as `"message.attribute"`.
+///
+/// # Parameters
+/// * `handlebars` - The registry to register the helper with
+///
+/// # Examples
+/// ```
+///
+/// let mut handlebars = handlebars::Handlebars::new();
+/// i18n::handlebars::register(&mut handlebars);
+///
+/// handlebars.register_template_string("emails", r#"{{t "emails" count=count}}"#).unwrap();
+/// ```
+pub fn register(handlebars: &mut Handlebars<'_>) {
+    handlebars.register_helper("t", Box::new(Translate));
+}
+
+fn set_arg(builder: I18nBuilder, name: &str, value: &JsonValue) -> I18nBuilder {
+    match value {
+        JsonValue::String(value) => builder.set_arg(name, value.clone()),
+        JsonValue::Number(number) => match number.as_f64() {
+            Some(number) => builder.set_arg(name, number),
+            None => builder.set_arg(name, number.to_string()),
+        },
+        value => builder.set_arg(name, value.to_string()),
+    }
+}
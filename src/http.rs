@@ -0,0 +1,150 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::reload;
+use crate::{I18nError, LanguageIdentifier, LocaleSource};
+use std::collections::HashMap;
+use std::sync::RwLock;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::task::JoinHandle;
+
+const ERROR_LOCK: &str = "Remote catalog lock poisoned";
+const PLACEHOLDER: &str = "{locale}";
+
+/// Source downloading one FTL bundle per locale over HTTP
+///
+/// The URL contains a `{locale}` placeholder replaced by each language
+/// identifier (e.g. `https://cdn.example.com/locales/{locale}.ftl`). Bundles
+/// are downloaded when the source is created and whenever `refresh` is awaited;
+/// `LocaleSource` reads only the downloaded snapshot.
+///
+/// # Examples
+/// ```ignore
+/// use i18n::{HttpSource, I18nConfig};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let url = "https://cdn.example.com/locales/{locale}.ftl";
+/// let source = Arc::new(HttpSource::new(url, ["en-US", "es-MX"]).await?);
+///
+/// i18n::init_with(I18nConfig {
+///     source: Some(source.clone()),
+///     ..Default::default()
+/// });
+///
+/// // Download and apply updated bundles every five minutes
+/// source.refresh_every(Duration::from_secs(300));
+/// ```
+#[derive(Debug)]
+pub struct HttpSource {
+    client: reqwest::Client,
+    url: String,
+    locales: Vec<LanguageIdentifier>,
+    bundles: RwLock<HashMap<LanguageIdentifier, String>>,
+}
+
+impl HttpSource {
+    /// Creates the source and downloads the bundle of every locale
+    ///
+    /// # Parameters
+    /// * `url` - Bundle URL containing a `{locale}` placeholder
+    /// * `locales` - Language identifiers of the bundles to download
+    ///
+    /// # Return
+    /// Returns an error if a locale is malformed or a bundle cannot be downloaded
+    pub async fn new<T, I, L>(url: T, locales: I) -> Result<Self, I18nError>
+    where
+        T: ToString,
+        I: IntoIterator<Item = L>,
+        L: ToString,
+    {
+        let locales = locales
+            .into_iter()
+            .map(|locale| {
+                let locale = locale.to_string();
+                locale.parse().map_err(|_| I18nError::InvalidLocale(locale))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let source = Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            locales,
+            bundles: RwLock::new(HashMap::new()),
+        };
+
+        source.refresh().await?;
+        Ok(source)
+    }
+
+    /// Downloads every bundle again
+    ///
+    /// Call `i18n::reload` afterwards to apply them. If any download fails,
+    /// the previous bundles are kept.
+    ///
+    /// # Return
+    /// Returns an error if a bundle cannot be downloaded
+    pub async fn refresh(&self) -> Result<(), I18nError> {
+        let mut bundles = HashMap::new();
+
+        for locale in &self.locales {
+            let url = self.url.replace(PLACEHOLDER, &locale.to_string());
+            let ftl = self.client
+                .get(&url)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| I18nError::Loader(e.to_string()))?
+                .text()
+                .await
+                .map_err(|e| I18nError::Loader(e.to_string()))?;
+
+            bundles.insert(locale.clone(), ftl);
+        }
+
+        *self.bundles.write().expect(ERROR_LOCK) = bundles;
+        Ok(())
+    }
+
+    /// Refreshes the bundles and reloads translations on a fixed interval
+    ///
+    /// Runs on the current tokio runtime until the returned task is aborted.
+    /// Failed refreshes keep the previous translations and are retried at the
+    /// next tick. Not available on WebAssembly, where `refresh` can be called
+    /// from a browser timer instead.
+    ///
+    /// # Parameters
+    /// * `interval` - Time between two refreshes
+    ///
+    /// # Return
+    /// Returns the handle of the spawned task
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn refresh_every(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let source = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.tick().await;
+
+            loop {
+                ticks.tick().await;
+
+                if source.refresh().await.is_ok() {
+                    let _ = reload();
+                }
+            }
+        })
+    }
+}
+
+impl LocaleSource for HttpSource {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        Ok(self.locales.clone())
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        Ok(self.bundles.read().expect(ERROR_LOCK).get(locale).cloned().into_iter().collect())
+    }
+}
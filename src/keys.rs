@@ -0,0 +1,166 @@
+//! Message identifiers, and the messages of JSON and YAML translation trees
+//!
+//! `i18n-macros` includes this file too, so `t_static!` checks keys against
+//! the same IDs the loader derives.
+
+// Each crate including this file uses a different part of it
+#![allow(dead_code)]
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+use serde_json::{Map, Value};
+#[cfg(any(feature = "json", feature = "yaml"))]
+use std::collections::HashSet;
+#[cfg(any(feature = "json", feature = "yaml"))]
+use unic_langid::LanguageIdentifier;
+
+/// CLDR plural categories, in the order FTL variants are written
+pub(crate) const CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// Returns whether `id` is a valid message or variable identifier
+pub(crate) fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Derives a message identifier from free text
+///
+/// Valid identifiers are kept. Other text is lowercased, with every run of
+/// characters other than ASCII letters and digits replaced by `-`, and
+/// prefixed with `msg-` if it does not start with a letter. Returns `None` for
+/// text without letters or digits.
+pub(crate) fn identifier(text: &str) -> Option<String> {
+    if is_identifier(text) {
+        return Some(text.to_string());
+    }
+
+    let mut id = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+
+    let id = id.trim_end_matches('-');
+    match id.chars().next() {
+        None => None,
+        Some(c) if c.is_ascii_alphabetic() => Some(id.to_string()),
+        Some(_) => Some(format!("msg-{id}")),
+    }
+}
+
+/// A message collected from a JSON or YAML tree
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Debug)]
+pub(crate) enum Message {
+    Text(String),
+    /// Forms by CLDR category, always including `other`
+    Plural(Vec<(&'static str, String)>),
+}
+
+/// Returns the messages of a translation tree, by message ID, in key order
+///
+/// Nested keys are joined with `-` and turned into identifiers, and keys whose
+/// identifier is already taken are skipped. Plurals are objects keyed by CLDR
+/// category or i18next `key_one`/`key_other` siblings, and are skipped without
+/// their `other` form. A single top-level key naming `locale`, as in Rails
+/// files, is skipped.
+#[cfg(any(feature = "json", feature = "yaml"))]
+pub(crate) fn messages(value: &Value, locale: Option<&LanguageIdentifier>) -> Vec<(String, Message)> {
+    let Value::Object(map) = value else {
+        return Vec::new();
+    };
+
+    let mut messages = Vec::new();
+    object("", root(map, locale).unwrap_or(map), &mut messages);
+
+    let mut seen = HashSet::new();
+    messages
+        .into_iter()
+        .filter(|(_, message)| !matches!(message, Message::Plural(forms) if !forms.iter().any(|(category, _)| *category == "other")))
+        .filter_map(|(key, message)| identifier(&key).filter(|id| seen.insert(id.clone())).map(|id| (id, message)))
+        .collect()
+}
+
+/// Returns the messages below a single top-level key naming `locale`
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn root<'a>(map: &'a Map<String, Value>, locale: Option<&LanguageIdentifier>) -> Option<&'a Map<String, Value>> {
+    let mut entries = map.iter();
+
+    match (entries.next(), entries.next(), locale) {
+        (Some((key, Value::Object(messages))), None, Some(locale))
+            if key.parse::<LanguageIdentifier>().is_ok_and(|key| key == *locale) =>
+        {
+            Some(messages)
+        }
+        _ => None,
+    }
+}
+
+/// Collects the messages of an object whose keys start with `prefix`
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn object(prefix: &str, map: &Map<String, Value>, messages: &mut Vec<(String, Message)>) {
+    let join = |name: &str| if prefix.is_empty() { name.to_string() } else { format!("{prefix}-{name}") };
+
+    for (name, value) in map {
+        // i18next plural forms are gathered once, when their `_other` key is reached
+        if let Some(base) = plural_base(name, map) {
+            if name.ends_with("_other") {
+                let forms = CATEGORIES
+                    .iter()
+                    .filter_map(|category| Some((*category, text_of(map.get(&format!("{base}_{category}"))?)?)))
+                    .collect();
+
+                messages.push((join(base), Message::Plural(forms)));
+            }
+
+            continue;
+        }
+
+        match value {
+            Value::Object(forms) if is_plural(forms) => {
+                let forms = CATEGORIES
+                    .iter()
+                    .filter_map(|category| Some((*category, text_of(forms.get(*category)?)?)))
+                    .collect();
+
+                messages.push((join(name), Message::Plural(forms)));
+            }
+            Value::Object(map) => object(&join(name), map, messages),
+            value => {
+                if let Some(text) = text_of(value) {
+                    messages.push((join(name), Message::Text(text)));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the base of an i18next plural key such as `emails_one`
+///
+/// Keys only count as plural forms if the object also has the `_other` form.
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn plural_base<'a>(name: &'a str, map: &Map<String, Value>) -> Option<&'a str> {
+    let (base, category) = name.rsplit_once('_')?;
+
+    (CATEGORIES.contains(&category) && map.contains_key(&format!("{base}_other"))).then_some(base)
+}
+
+/// Returns whether an object lists plural forms, keyed by CLDR category
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn is_plural(map: &Map<String, Value>) -> bool {
+    map.contains_key("other") && map.keys().all(|key| CATEGORIES.contains(&key.as_str()))
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn text_of(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
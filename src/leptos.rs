@@ -0,0 +1,100 @@
+//! Leptos integration with a reactive current locale
+//!
+//! ```no_run
+//! use leptos::prelude::*;
+//! use i18n::leptos::{provide_locale, set_locale, t};
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     provide_locale();
+//!
+//!     view! {
+//!         <h1>{t("hello")}</h1>
+//!         <button on:click=|_| set_locale("es-MX").unwrap()>"Español"</button>
+//!     }
+//! }
+//! ```
+
+use crate::{I18nError, LanguageIdentifier, Locale};
+use ::leptos::prelude::{Get, RwSignal, Set, provide_context, use_context};
+
+#[derive(Clone, Copy)]
+struct LocaleContext(RwSignal<LanguageIdentifier>);
+
+/// Provides the current locale as a signal to the component and its children
+///
+/// Call it once in the root component. The signal starts at the active
+/// locale.
+///
+/// # Return
+/// Returns the locale signal
+pub fn provide_locale() -> RwSignal<LanguageIdentifier> {
+    let locale = RwSignal::new(crate::locale());
+    provide_context(LocaleContext(locale));
+
+    locale
+}
+
+/// Returns the locale signal provided by `provide_locale`
+///
+/// Reading it inside a closure, a memo, or an effect subscribes to locale
+/// changes.
+///
+/// # Return
+/// Returns the locale signal
+///
+/// # Panics
+/// Panics if no ancestor component called `provide_locale`
+pub fn use_locale() -> RwSignal<LanguageIdentifier> {
+    match use_context::<LocaleContext>() {
+        Some(LocaleContext(locale)) => locale,
+        None => panic!("i18n::leptos::provide_locale must be called in an ancestor component"),
+    }
+}
+
+/// Switches the active locale and re-renders every component translating with it
+///
+/// The global locale is changed like with `i18n::set_locale`, and the signal
+/// provided by `provide_locale` is updated, if any.
+///
+/// # Parameters
+/// * `locale` - The language identifier to use (e.g., "es-MX")
+///
+/// # Return
+/// Returns an error if the locale is not a valid language identifier
+pub fn set_locale<T>(locale: T) -> Result<(), I18nError>
+where
+    T: ToString,
+{
+    crate::set_locale(locale)?;
+
+    if let Some(LocaleContext(signal)) = use_context::<LocaleContext>() {
+        signal.set(crate::locale());
+    }
+
+    Ok(())
+}
+
+/// Returns a reactive translation of the given key
+///
+/// The closure renders the key in the current locale, and re-runs whenever
+/// the locale signal changes, so it can be used directly as a child in
+/// `view!`.
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+///
+/// # Return
+/// Returns a closure producing the translated string
+///
+/// # Panics
+/// Panics if no ancestor component called `provide_locale`
+pub fn t<T>(key: T) -> impl Fn() -> String + Clone + Send + Sync + 'static
+where
+    T: ToString,
+{
+    let key = key.to_string();
+    let locale = use_locale();
+
+    move || Locale(locale.get()).get(&key)
+}
@@ -1,69 +1,1000 @@
-use fluent_templates::{ArcLoader, Loader, fluent_bundle::FluentValue};
-use once_cell::sync::Lazy;
-use std::{collections::HashMap, env};
+mod args;
+mod audit;
+mod cache;
+mod catalog;
+mod config;
+mod coverage;
+mod diagnostics;
+mod direction;
+mod domain;
+mod error;
+mod fixture;
+mod function;
+mod guard;
+mod handle;
+mod library;
+mod locale;
+mod macros;
+mod message;
+mod missing;
+mod names;
+mod negotiate;
+mod ordinal;
+mod plural;
+mod pseudo;
+mod scope;
+mod segment;
+mod source;
+mod strict;
+mod suggest;
+pub mod testing;
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "askama")]
+pub mod askama;
+#[cfg(feature = "async-graphql")]
+pub mod async_graphql;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "tokio")]
+mod task;
+#[cfg(feature = "arb")]
+mod arb;
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "compile")]
+mod compiled;
+#[cfg(feature = "json")]
+mod export;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "format")]
+mod format;
+#[cfg(feature = "chrono")]
+mod date;
+#[cfg(any(
+    feature = "arb",
+    feature = "csv",
+    feature = "gettext",
+    feature = "json",
+    feature = "mobile",
+    feature = "sqlx",
+    feature = "tms",
+    feature = "xliff",
+    feature = "yaml"
+))]
+mod ftl;
+#[cfg(any(
+    feature = "arb",
+    feature = "csv",
+    feature = "gettext",
+    feature = "json",
+    feature = "mobile",
+    feature = "sqlx",
+    feature = "tms",
+    feature = "xliff",
+    feature = "yaml"
+))]
+mod keys;
+#[cfg(feature = "gettext")]
+mod gettext;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "mobile")]
+mod mobile;
+#[cfg(feature = "sqlx")]
+mod sql;
+#[cfg(any(feature = "json", feature = "yaml"))]
+mod structured;
+
+#[cfg(feature = "check")]
+pub mod check;
+#[cfg(feature = "clap")]
+pub mod clap;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+#[cfg(feature = "extract")]
+pub mod extract;
+#[cfg(feature = "handlebars")]
+pub mod handlebars;
+#[cfg(feature = "leptos")]
+pub mod leptos;
+#[cfg(feature = "minijinja")]
+pub mod minijinja;
+#[cfg(feature = "tera")]
+pub mod tera;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "tms")]
+mod tms;
+#[cfg(feature = "watch")]
+mod watch;
+#[cfg(feature = "xliff")]
+mod xliff;
+
+use catalog::Catalog;
+use fluent_templates::fluent_bundle::resolver::ResolverError;
+use fluent_templates::fluent_bundle::resolver::errors::ReferenceKind;
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
 use std::borrow::Cow;
-use unic_langid::LanguageIdentifier;
+use std::fmt;
+use std::iter;
+use std::sync::{Arc, RwLock};
+
+pub use args::{IntoFluentValue, ToFluentArgs};
+pub use audit::{AuditReport, audit};
+pub use cache::{CacheStats, cache_stats};
+pub use config::I18nConfig;
+pub use coverage::{CoverageStats, coverage};
+pub use diagnostics::{Diagnostics, PlaceholderMismatch, SyntaxError, diagnostics};
+pub use direction::{TextDirection, direction, direction_of};
+pub use domain::{Domain, bundle, register_bundle, tenant};
+pub use error::{I18nError, LookupError};
+pub use fluent_templates::fluent_bundle::{FluentArgs, FluentError, FluentValue};
+pub use fixture::test_loader;
+pub use function::register_function;
+pub use guard::{LocaleGuard, locale_guard};
+pub use handle::I18n;
+pub use library::register_embedded;
+pub use locale::Locale;
+pub use message::LocalizedMessage;
+pub use missing::{MissingKeyHandler, MissingKeyPolicy, on_missing};
+pub use names::{DisplayName, display_name};
+pub use negotiate::{negotiate, resolve_locale};
+pub use ordinal::ordinal;
+pub use plural::{PluralCategory, plural_category};
+pub use scope::{Scope, scope};
+#[cfg(feature = "tokio")]
+pub use task::{init_async, reload_async, with_locale};
+#[cfg(feature = "embedded")]
+pub use i18n_macros::embed_locales;
+#[cfg(feature = "macros")]
+pub use i18n_macros::{FluentArgs, LocalizedError, t_static};
+#[cfg(feature = "embedded")]
+pub use source::EmbeddedLocales;
+pub use source::{DirSource, LocaleSource};
+pub use segment::{Segment, get_segments};
+pub use strict::strict;
+#[cfg(feature = "json")]
+pub use export::export_json;
+#[cfg(feature = "json")]
+pub use structured::json_to_ftl;
+#[cfg(feature = "yaml")]
+pub use structured::yaml_to_ftl;
+#[cfg(feature = "arb")]
+pub use arb::{arb_to_ftl, export_arb};
+#[cfg(feature = "compile")]
+pub use compiled::{CompiledSource, compile_catalog};
+#[cfg(feature = "csv")]
+pub use csv::{export_csv, import_csv};
+#[cfg(feature = "markdown")]
+pub use markdown::get_md;
+#[cfg(feature = "mobile")]
+pub use mobile::{export_android_strings, export_ios_strings, export_ios_stringsdict};
+#[cfg(feature = "tms")]
+pub use tms::{TmsClient, TmsProvider};
+#[cfg(feature = "xliff")]
+pub use xliff::{XliffVersion, export_xliff, export_xliff_with, import_xliff};
+#[cfg(feature = "serde")]
+pub use serialize::{InLocale, in_locale};
+#[cfg(feature = "format")]
+pub use format::{CurrencyStyle, ListStyle, format_currency, format_currency_with, format_list, format_list_with, format_number};
+#[cfg(feature = "chrono")]
+pub use date::{DateStyle, format_date, format_relative, format_time};
+#[cfg(feature = "gettext")]
+pub use gettext::{GettextSource, mo_to_ftl, po_to_ftl};
+#[cfg(feature = "http")]
+pub use http::HttpSource;
+#[cfg(feature = "sqlx")]
+pub use sql::SqlxSource;
+pub use unic_langid::LanguageIdentifier;
+#[cfg(feature = "watch")]
+pub use watch::{ReloadWatcher, watch};
 
-const ERROR_PARSING: &str = "Parsing language failed";
-const ERROR_BUILDING: &str = "Unable to build loader";
-const DEFAULT_LANG: &str = "en-US";
-const DEFAULT_DIR: &str = "./assets/locales/";
+const ERROR_LOCK: &str = "Translation lock poisoned";
+
+type Args = FluentArgs<'static>;
 
 /// Internationalization (i18n) Configuration
 ///
-/// A static, lazily initialized configuration for handling translations across different languages.
-/// Loads locale settings from environment variables or falls back to defaults.
+/// A static configuration for handling translations across different languages.
+/// Set explicitly through `init_with` or `try_init`, or lazily loaded from environment variables
+/// (see `I18nConfig::from_env`) on the first lookup.
+static I18N: OnceCell<Inner> = OnceCell::new();
+
+/// Core internationalization structure
+///
+/// Holds the translation catalog and current locale settings, for the global
+/// instance as well as for `I18n` handles, named bundles, and tenants.
+///
+/// # Fields
+/// - `config`: Settings the catalog was built from, kept for reloading
+/// - `catalog`: Loaded translation bundles, replaced on reload
+/// - `locales`: Current language identifier, switchable at runtime
+/// - `fallback`: Ordered locales tried when a key is missing
+/// - `libraries`: Whether catalogs registered by libraries are merged in
+/// - `aliases`: Requested locales mapped to the locales looked up instead
+struct Inner {
+    config: I18nConfig,
+    catalog: RwLock<Catalog>,
+    locales: RwLock<LanguageIdentifier>,
+    fallback: Vec<LanguageIdentifier>,
+    libraries: bool,
+    aliases: Vec<(LanguageIdentifier, LanguageIdentifier)>,
+}
+
+impl Inner {
+    fn new(config: &I18nConfig, libraries: bool) -> Result<Self, I18nError> {
+        let locales = parse_locale(&config.locale)?;
+        let aliases = config.aliases
+            .iter()
+            .map(|(alias, locale)| Ok((parse_locale(alias)?, parse_locale(locale)?)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let fallback = config.fallback
+            .iter()
+            .map(|locale| parse_locale(locale).map(|locale| resolve_alias(&aliases, &locale)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (catalog, config) = match Catalog::load(config, &library::sources(libraries)) {
+            Ok(catalog) => (catalog, config.clone()),
+            // The error is reported by `diagnostics`
+            Err(e) if config.lenient_init => {
+                let config = I18nConfig { missing: MissingKeyPolicy::ReturnKey, ..config.clone() };
+
+                (Catalog::empty(&config, &e), config)
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            config,
+            catalog: RwLock::new(catalog),
+            locales: RwLock::new(locales),
+            fallback,
+            libraries,
+            aliases,
+        })
+    }
+
+    /// Returns the locale looked up for `locale`, following the configured aliases
+    fn alias(&self, locale: &LanguageIdentifier) -> LanguageIdentifier {
+        resolve_alias(&self.aliases, locale)
+    }
+
+    /// Rebuilds the catalog from disk, keeping the current one on failure
+    fn reload(&self) -> Result<(), I18nError> {
+        let catalog = Catalog::load(&self.config, &library::sources(self.libraries))?;
+        *self.catalog.write().expect(ERROR_LOCK) = catalog;
+
+        Ok(())
+    }
+
+    /// Returns the locales provided by the current catalog
+    fn available(&self) -> Vec<LanguageIdentifier> {
+        self.catalog.read().expect(ERROR_LOCK).available().to_vec()
+    }
+
+    /// Resolves the installed locales to try for `locale`, best match first
+    ///
+    /// The requested locale is followed by the configured fallback chain, and
+    /// each entry also matches installed locales sharing its language (so `fr`
+    /// resolves to an installed `fr-FR`, and `pt-BR` to `pt`).
+    fn chain<'a>(&self, catalog: &'a Catalog, locale: &LanguageIdentifier) -> Vec<&'a LanguageIdentifier> {
+        let requested: Vec<LanguageIdentifier> = iter::once(locale)
+            .chain(&self.fallback)
+            .cloned()
+            .collect();
+
+        negotiate::matching(&requested, catalog.available())
+    }
+
+    fn lookup(&self, locale: &LanguageIdentifier, key: &str, args: Option<&Args>, isolating: Option<bool>) -> String {
+        if self.is_strict() {
+            return self.resolve(locale, key, args, isolating).unwrap_or_else(|e| panic!("{e}"));
+        }
+
+        self.try_lookup(locale, key, args, isolating)
+            .unwrap_or_else(|| self.config.missing.apply(locale, key, self.nearest_key(locale, key).as_deref()))
+    }
+
+    /// Looks up `key` without arguments, sharing the memoized text of static messages
+    ///
+    /// The memo is only used when the requested locale itself defines the
+    /// message, so fallbacks are still reported to the missing-key hook.
+    fn lookup_shared(&self, locale: &LanguageIdentifier, key: &str) -> Arc<str> {
+        let alias = &self.alias(locale);
+        let shared = {
+            let catalog = self.catalog.read().expect(ERROR_LOCK);
+
+            self.chain(&catalog, alias)
+                .first()
+                .filter(|first| first.matches(alias, true, true))
+                .and_then(|first| catalog.shared(first, key))
+        };
+
+        shared.unwrap_or_else(|| self.lookup(locale, key, None, None).into())
+    }
+
+    /// Looks up the term `id`, with or without its leading `-`, along the chain of `locale`
+    ///
+    /// `-brand.short` addresses the `short` attribute of `-brand`.
+    fn lookup_term(&self, locale: &LanguageIdentifier, id: &str) -> String {
+        let key = format!("-{}", id.strip_prefix('-').unwrap_or(id));
+        let (term, attr) = match key[1..].split_once('.') {
+            Some((term, attr)) => (term, Some(attr)),
+            None => (&key[1..], None),
+        };
+
+        let locale = &self.alias(locale);
+        let found = {
+            let catalog = self.catalog.read().expect(ERROR_LOCK);
+            let chain = self.chain(&catalog, locale);
+
+            chain
+                .iter()
+                .enumerate()
+                .find_map(|(i, l)| catalog.format_term(l, term, attr).map(|t| (t, i == 0 && l.matches(locale, true, true))))
+        };
+
+        match found {
+            Some((term, true)) => term,
+            found => {
+                missing::notify(locale, &key);
+                match found {
+                    Some((term, _)) => term,
+                    None if self.is_strict() => panic!("{}", LookupError::MissingMessage(key, None)),
+                    None => self.config.missing.apply(locale, &key, None),
+                }
+            }
+        }
+    }
+
+    /// Returns whether lookups panic on translation problems
+    fn is_strict(&self) -> bool {
+        self.config.strict || strict::enabled()
+    }
+
+    /// Looks up every key of `keys`, taking the catalog lock only once
+    fn lookup_many<T>(&self, locale: &LanguageIdentifier, keys: &[T]) -> HashMap<String, String>
+    where
+        T: AsRef<str>,
+    {
+        if self.is_strict() {
+            return keys.iter()
+                .map(|key| (key.as_ref().to_string(), self.lookup(locale, key.as_ref(), None, None)))
+                .collect();
+        }
+
+        let locale = &self.alias(locale);
+        let catalog = self.catalog.read().expect(ERROR_LOCK);
+        let chain = self.chain(&catalog, locale);
+
+        keys.iter()
+            .map(|key| {
+                let key = key.as_ref();
+                let message = find(&catalog, &chain, locale, key, None, None)
+                    .unwrap_or_else(|| self.config.missing.apply(locale, key, self.suggest(&catalog, &chain, key).as_deref()));
+
+                (key.to_string(), message)
+            })
+            .collect()
+    }
+
+    /// Looks up `key`, telling a missing message, locale, or argument apart
+    ///
+    /// Unlike `try_lookup`, a message failing to format is reported instead of
+    /// falling back to the next locale.
+    fn resolve(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&Args>,
+        isolating: Option<bool>,
+    ) -> Result<String, LookupError> {
+        let locale = &self.alias(locale);
+        let catalog = self.catalog.read().expect(ERROR_LOCK);
+        let chain = self.chain(&catalog, locale);
+
+        let found = chain
+            .iter()
+            .enumerate()
+            .find_map(|(i, l)| catalog.format_single(l, key, args, isolating).map(|m| (i, m)));
+
+        match found {
+            Some((0, _)) if chain[0].matches(locale, true, true) => {}
+            _ => missing::notify(locale, key),
+        }
+
+        match found {
+            Some((_, message)) => message.map_err(|errors| LookupError::FormattingError(key.to_string(), errors)),
+            None if chain.is_empty() => Err(LookupError::MissingLocale(locale.clone())),
+            None => Err(LookupError::MissingMessage(key.to_string(), self.suggest(&catalog, &chain, key))),
+        }
+    }
+
+    /// Returns the key closest to `key` along the chain of `locale`, if `suggest` is enabled
+    fn nearest_key(&self, locale: &LanguageIdentifier, key: &str) -> Option<String> {
+        if !self.config.suggest {
+            return None;
+        }
+
+        let locale = &self.alias(locale);
+        let catalog = self.catalog.read().expect(ERROR_LOCK);
+        let chain = self.chain(&catalog, locale);
+
+        self.suggest(&catalog, &chain, key)
+    }
+
+    /// Returns the message ID of `chain` closest to `key`, if `suggest` is enabled
+    fn suggest(&self, catalog: &Catalog, chain: &[&LanguageIdentifier], key: &str) -> Option<String> {
+        if !self.config.suggest {
+            return None;
+        }
+
+        let keys = chain
+            .iter()
+            .filter_map(|locale| catalog.keys(locale))
+            .flatten()
+            .map(String::as_str);
+
+        suggest::nearest(key, keys).map(str::to_string)
+    }
+
+    /// Returns the message IDs of the installed locale best matching `locale`
+    fn keys(&self, locale: &LanguageIdentifier) -> Vec<String> {
+        let catalog = self.catalog.read().expect(ERROR_LOCK);
+
+        negotiate::matching(&[self.alias(locale)], catalog.available())
+            .first()
+            .and_then(|locale| catalog.keys(locale))
+            .map(|keys| keys.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether `key` is defined for `locale` or its fallback chain
+    fn has(&self, locale: &LanguageIdentifier, key: &str) -> bool {
+        let locale = &self.alias(locale);
+        let catalog = self.catalog.read().expect(ERROR_LOCK);
+
+        self.chain(&catalog, locale)
+            .iter()
+            .any(|locale| catalog.contains(locale, key))
+    }
+
+    fn try_lookup(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&Args>,
+        isolating: Option<bool>,
+    ) -> Option<String> {
+        let locale = &self.alias(locale);
+        let catalog = self.catalog.read().expect(ERROR_LOCK);
+        let chain = self.chain(&catalog, locale);
+
+        find(&catalog, &chain, locale, key, args, isolating)
+    }
+}
+
+/// Finds `key` along `chain`, reporting fallbacks and misses to the hook
+///
+/// Found messages are kept in the catalog's cache, if enabled, so repeated
+/// lookups with the same arguments skip formatting.
+fn find(
+    catalog: &Catalog,
+    chain: &[&LanguageIdentifier],
+    locale: &LanguageIdentifier,
+    key: &str,
+    args: Option<&Args>,
+    isolating: Option<bool>,
+) -> Option<String> {
+    let cache_key = catalog.cache().key(locale, key, args, isolating);
+    let cached = cache_key.as_ref().and_then(|cache_key| catalog.cache().get(cache_key));
+
+    let found = cached.or_else(|| {
+        let found = chain
+            .iter()
+            .enumerate()
+            .find_map(|(i, l)| catalog.lookup_single(l, key, args, isolating).map(|m| (m, i == 0 && l.matches(locale, true, true))))?;
+
+        if let Some(cache_key) = cache_key {
+            catalog.cache().insert(cache_key, found.clone());
+        }
+
+        Some(found)
+    });
+
+    match found {
+        Some((message, true)) => Some(message),
+        found => {
+            missing::notify(locale, key);
+            found.map(|(message, _)| message)
+        }
+    }
+}
+
+/// Returns the global instance, initializing it from the environment if needed
+///
+/// # Panics
+/// Panics if the environment configuration is invalid. Call `try_init` first
+/// to handle such errors gracefully.
+fn instance() -> &'static Inner {
+    I18N.get_or_init(|| {
+        Inner::new(&I18nConfig::from_env(), true).unwrap_or_else(|e| panic!("{e}"))
+    })
+}
+
+fn resolve_alias(aliases: &[(LanguageIdentifier, LanguageIdentifier)], locale: &LanguageIdentifier) -> LanguageIdentifier {
+    aliases
+        .iter()
+        .find_map(|(alias, target)| (alias == locale).then_some(target))
+        .unwrap_or(locale)
+        .clone()
+}
+
+fn parse_locale(locale: &str) -> Result<LanguageIdentifier, I18nError> {
+    locale
+        .parse()
+        .map_err(|_| I18nError::InvalidLocale(locale.to_string()))
+}
+
+/// Initializes translations from an explicit configuration
+///
+/// Lets applications configure the crate programmatically (e.g. from their own
+/// config files) instead of through `I18N_ID`/`I18N_DIR`. Must be called before
+/// the first lookup.
+///
+/// # Parameters
+/// * `config` - The settings used to build the loader
+///
+/// # Panics
+/// Panics if the configuration is invalid or translations were already
+/// initialized. Use `try_init` to handle these errors instead.
+///
+/// # Examples
+/// ```
+///
+/// i18n::init_with(i18n::I18nConfig {
+///     locale: "es-MX".to_string(),
+///     fallback: vec!["en-US".to_string()],
+///     dir: "./assets/locales/".to_string(),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(i18n::get("hello"), "Hola");
+/// assert_eq!(i18n::get("farewell"), "Goodbye");  // From the en-US fallback
+/// ```
+pub fn init_with(config: I18nConfig) {
+    try_init(config).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Initializes translations without panicking
+///
+/// Must be called before the first lookup; otherwise the configuration is read
+/// from the environment when a translation is first requested.
+///
+/// # Parameters
+/// * `config` - The settings used to build the loader
+///
+/// # Return
+/// Returns an error if the locale is malformed, the locale directory cannot be
+/// loaded, or translations were already initialized
+///
+/// # Examples
+/// ```
+///
+/// let config = i18n::I18nConfig {
+///     dir: "./missing/".to_string(),
+///     ..Default::default()
+/// };
+///
+/// if let Err(e) = i18n::try_init(config) {
+///     eprintln!("{e}");  // Prints "Unable to build loader: ..."
+/// }
+/// ```
+pub fn try_init(config: I18nConfig) -> Result<(), I18nError> {
+    let i18n = Inner::new(&config, true)?;
+
+    I18N.set(i18n).map_err(|_| I18nError::AlreadyInitialized)
+}
+
+/// Rebuilds the translations from disk on demand
+///
+/// Lets ops teams push updated translation files and apply them through an
+/// admin endpoint or a SIGHUP handler. On failure the previous translations
+/// stay in place.
+///
+/// # Return
+/// Returns an error if the locale directory cannot be loaded
+///
+/// # Examples
+/// ```
+///
+/// if let Err(e) = i18n::reload() {
+///     eprintln!("Keeping previous translations: {e}");
+/// }
+/// ```
+pub fn reload() -> Result<(), I18nError> {
+    instance().reload()?;
+    domain::clear_tenants();
+
+    Ok(())
+}
+
+/// Returns the currently active locale
+///
+/// While a `locale_guard` is alive this is the guard's locale. Otherwise, inside
+/// `with_locale` (including requests handled by the web framework integrations)
+/// this is the task's locale, and elsewhere it is the global locale.
+///
+/// # Return
+/// Returns a copy of the language identifier used for lookups
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::locale(), "en-US");
+/// ```
+pub fn locale() -> LanguageIdentifier {
+    if let Some(locale) = guard::current() {
+        return locale;
+    }
+
+    #[cfg(feature = "tokio")]
+    if let Some(locale) = task::current() {
+        return locale;
+    }
+
+    instance().locales.read().expect(ERROR_LOCK).clone()
+}
+
+/// Lists the locales discovered in the locale directory (or custom source)
+///
+/// Useful to render a language picker or validate user preferences against
+/// what is actually installed.
+///
+/// # Return
+/// Returns the installed language identifiers, sorted
+///
+/// # Examples
+/// ```
+///
+/// let locales: Vec<String> = i18n::available_locales().iter().map(ToString::to_string).collect();
+/// assert_eq!(locales, ["en-US", "es-MX"]);
+/// ```
+pub fn available_locales() -> Vec<LanguageIdentifier> {
+    instance().available()
+}
+
+/// Lists the message IDs defined for a locale
+///
+/// Lets tooling such as exports, audits, and admin screens enumerate the
+/// catalog without parsing FTL files. `locale` resolves to the best installed
+/// locale, but keys of its fallback chain are not included. Attributes are not
+/// listed separately.
+///
+/// # Parameters
+/// * `locale` - The language identifier to list (e.g., "es-MX")
+///
+/// # Return
+/// Returns the message IDs, sorted, or nothing if `locale` is invalid or not
+/// installed
+///
+/// # Examples
+/// ```
+///
+/// let keys: Vec<String> = i18n::keys("es-MX").collect();
+/// assert_eq!(keys[..3], ["emails", "greeting", "hello"]);
+/// assert_eq!(i18n::keys("en").count(), 7);  // The keys of en-US
+/// ```
+pub fn keys<T>(locale: T) -> impl Iterator<Item = String>
+where
+    T: ToString,
+{
+    let keys = match parse_locale(&locale.to_string()) {
+        Ok(locale) => instance().keys(&locale),
+        Err(_) => Vec::new(),
+    };
+
+    keys.into_iter()
+}
+
+/// Changes the active locale at runtime
+///
+/// Subsequent lookups from every thread use the new locale. The loader is not
+/// rebuilt, so the locale should exist in the locale directory.
+///
+/// # Parameters
+/// * `locale` - The language identifier to switch to (e.g., "es-MX")
+///
+/// # Return
+/// Returns an error if `locale` is not a valid language identifier
+///
+/// # Examples
+/// ```
+///
+/// i18n::set_locale("es-MX").unwrap();
+/// assert_eq!(i18n::get("hello"), "Hola");
+/// ```
+pub fn set_locale<T>(locale: T) -> Result<(), I18nError>
+where
+    T: ToString,
+{
+    let locale = parse_locale(&locale.to_string())?;
+    *instance().locales.write().expect(ERROR_LOCK) = locale;
+
+    Ok(())
+}
+
+/// Retrieves a translation for the given key
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+///
+/// # Return
+/// Returns the translated string for the current locale
+///
+/// # Examples
+/// ```
+///
+/// let hello = i18n::get("hello");  // Returns "Hello" for en-US
+/// let greeting = i18n::get("welcome_message");  // Returns "Welcome" for en-US
+/// ```
+pub fn get<T>(key: T) -> String
+where
+    T: ToString,
+{
+    instance().lookup(&locale(), &key.to_string(), None, None)
+}
+
+/// Retrieves a translation for the given key as a shared string
+///
+/// Messages without placeables are formatted once when the catalog loads, so
+/// for them this returns the cached text without allocating. Other messages
+/// are formatted like `get`.
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+///
+/// # Return
+/// Returns the translated string for the current locale
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(&*i18n::get_shared("hello"), "Hello");  // Shared with every other lookup
+/// ```
+pub fn get_shared<T>(key: T) -> Arc<str>
+where
+    T: ToString,
+{
+    instance().lookup_shared(&locale(), &key.to_string())
+}
+
+/// Retrieves a translation for the given key, if it exists
+///
+/// Unlike `get`, a message missing from the locale and its fallback chain yields
+/// `None` instead of a placeholder string, so callers can decide what to show.
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+///
+/// # Return
+/// Returns the translated string, or `None` if the message is missing
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::get_opt("hello").as_deref(), Some("Hello"));
+/// assert_eq!(i18n::get_opt("missing"), None);
+/// ```
+pub fn get_opt<T>(key: T) -> Option<String>
+where
+    T: ToString,
+{
+    instance().try_lookup(&locale(), &key.to_string(), None, None)
+}
+
+/// Retrieves a translation for the given key, explaining any failure
+///
+/// Unlike `get_opt`, the error tells a missing message, an uninstalled
+/// locale, and a message that fails to format (e.g. a placeable without its
+/// argument) apart, so callers can react to each case.
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+///
+/// # Return
+/// Returns the translated string, or the reason it could not be produced
+///
+/// # Examples
+/// ```
+///
+/// use i18n::LookupError;
+///
+/// assert_eq!(i18n::try_get("hello").unwrap(), "Hello");
+/// assert!(matches!(i18n::try_get("missing"), Err(LookupError::MissingMessage(key, None)) if key == "missing"));
+/// assert!(matches!(i18n::try_get("greeting"), Err(LookupError::FormattingError(..))));  // $name is unset
+/// ```
+pub fn try_get<T>(key: T) -> Result<String, LookupError>
+where
+    T: ToString,
+{
+    instance().resolve(&locale(), &key.to_string(), None, None)
+}
+
+/// Checks whether a translation exists for the given key
+///
+/// Looks the key up in the active locale and its fallback chain without
+/// formatting the message or notifying the missing-key hook, so callers can
+/// cheaply branch on optional content. Attributes are addressed as
+/// `message.attribute`.
+///
+/// # Parameters
+/// * `key` - The translation key to look for
+///
+/// # Return
+/// Returns `true` if the message exists
+///
+/// # Examples
+/// ```
+///
+/// assert!(i18n::has("hello"));
+/// assert!(i18n::has("login-button.title"));
+/// assert!(!i18n::has("promo-banner"));
+/// ```
+pub fn has<T>(key: T) -> bool
+where
+    T: ToString,
+{
+    instance().has(&locale(), &key.to_string())
+}
+
+/// Checks whether a translation exists for the given key in a specific locale
+///
+/// The locale's fallback chain is considered, like in lookups.
+///
+/// # Parameters
+/// * `locale` - The language identifier to look in (e.g., "es-MX")
+/// * `key` - The translation key to look for
 ///
-/// # Environment Variables
-/// - `I18N_ID`: The language identifier (e.g., "en-US")
-/// - `I18N_DIR`: Directory containing locale files
-static I18N: Lazy<I18n> = Lazy::new(|| {
-    let locales: LanguageIdentifier = env::var("I18N_ID")
-        .unwrap_or_else(|_| DEFAULT_LANG.to_string())
-        .parse()
-        .expect(ERROR_PARSING);
-
-    let i18n_dir = env::var("I18N_DIR").unwrap_or_else(|_| DEFAULT_DIR.to_string());
+/// # Return
+/// Returns `true` if the message exists, or `false` if it is missing or
+/// `locale` is not a valid language identifier
+///
+/// # Examples
+/// ```
+///
+/// assert!(i18n::has_in("es-MX", "hello"));
+/// ```
+pub fn has_in<T, U>(locale: T, key: U) -> bool
+where
+    T: ToString,
+    U: ToString,
+{
+    parse_locale(&locale.to_string())
+        .is_ok_and(|locale| instance().has(&locale, &key.to_string()))
+}
 
-    let loader = ArcLoader::builder(&i18n_dir, locales.clone())
-        .customize(|b| b.set_use_isolating(false))
-        .build()
-        .expect(ERROR_BUILDING);
+/// Retrieves a translation for the given key, or a caller-supplied default
+///
+/// Lets libraries ship sensible defaults without requiring every key to exist
+/// in the FTL files.
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+/// * `default` - The text returned when the message is missing
+///
+/// # Return
+/// Returns the translated string, or `default` if the message is missing
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::get_or("missing", "Default text"), "Default text");
+/// ```
+pub fn get_or<T, U>(key: T, default: U) -> String
+where
+    T: ToString,
+    U: ToString,
+{
+    get_opt(key).unwrap_or_else(|| default.to_string())
+}
 
-    I18n { locales, loader }
-});
+/// Retrieves the translations of several keys at once
+///
+/// Resolves every key under a single lock, which amortizes the lookup
+/// overhead when rendering pages that need dozens of strings. Missing keys
+/// are handled like in `get`.
+///
+/// # Parameters
+/// * `keys` - The translation keys to look up
+///
+/// # Return
+/// Returns the translated strings for the current locale, by key
+///
+/// # Examples
+/// ```
+///
+/// let strings = i18n::get_many(&["hello", "farewell"]);
+/// assert_eq!(&strings["hello"], "Hello");
+/// ```
+pub fn get_many<T>(keys: &[T]) -> HashMap<String, String>
+where
+    T: AsRef<str>,
+{
+    instance().lookup_many(&locale(), keys)
+}
 
-/// Core internationalization structure
+/// Retrieves a Fluent term, for use outside of messages
 ///
-/// Holds the translation loader and current locale settings for the application.
+/// Terms hold text shared by many messages, such as the product name, and
+/// cannot be looked up with `get`. This makes them available where Fluent is
+/// not involved, e.g. in email subjects:
 ///
-/// # Fields
-/// - `loader`: Handles loading and caching of translation files
-/// - `locales`: Current language identifier
-struct I18n {
-    loader: ArcLoader,
-    locales: LanguageIdentifier,
+/// ```ftl
+/// -brand-name = Acme
+///     .short = AC
+/// ```
+///
+/// # Parameters
+/// * `id` - The term ID, with or without its leading `-`; `-id.attr` retrieves an attribute
+///
+/// # Return
+/// Returns the term's text for the current locale, or the missing-key policy's
+/// text if no locale of the fallback chain defines it
+///
+/// # Examples
+/// ```
+///
+/// i18n::test_loader(&[("en-US", "-brand-name = Acme\n    .short = AC")]);
+///
+/// assert_eq!(i18n::term("-brand-name"), "Acme");
+/// assert_eq!(i18n::term("brand-name.short"), "AC");
+/// ```
+pub fn term<T>(id: T) -> String
+where
+    T: ToString,
+{
+    instance().lookup_term(&locale(), &id.to_string())
 }
 
-/// Retrieves a translation for the given key
+/// Retrieves an attribute of a translation
+///
+/// Fluent messages can carry attributes such as tooltips, placeholders, and
+/// aria-labels:
+///
+/// ```ftl
+/// login-button = Log in
+///     .title = Click to log in
+/// ```
 ///
 /// # Parameters
-/// * `key` - The translation key to look up
+/// * `key` - The translation key of the message
+/// * `attr` - The attribute name
 ///
 /// # Return
-/// Returns the translated string for the current locale
+/// Returns the translated attribute for the current locale
 ///
 /// # Examples
 /// ```
 ///
-/// let hello = i18n::get("hello");  // Returns "Hello" for en-US
-/// let greeting = i18n::get("welcome_message");  // Returns "Welcome" for en-US
+/// assert_eq!(i18n::get_attr("login-button", "title"), "Click to log in");
 /// ```
-pub fn get<T>(key: T) -> String
+pub fn get_attr<T, U>(key: T, attr: U) -> String
 where
     T: ToString,
+    U: ToString,
 {
-    I18N.loader.lookup(&I18N.locales, &key.to_string())
+    get(attr_key(&key.to_string(), &attr.to_string()))
+}
+
+fn attr_key(key: &str, attr: &str) -> String {
+    format!("{key}.{attr}")
 }
 
 /// Builder for handling translations with parameters
@@ -73,15 +1004,148 @@ where
 ///
 /// # Fields
 /// * `key` - The translation key to look up
-/// * `args` - HashMap storing the parameter key-value pairs
+/// * `args` - Parameters as Fluent values, kept ready to pass to the bundle so lookups do not copy them
+/// * `locale` - Optional locale overriding the active one for this lookup
+/// * `attr` - Optional attribute of the message to retrieve instead of its value
+/// * `isolating` - Optional bidi isolation overriding the configured one
+/// * `bundle` - Optional named bundle looked up instead of the global catalog
+///
+/// Builders implement `Display` and convert into `String`, both formatting
+/// through `build`, so they can be passed straight to `format!` or APIs
+/// taking `Into<String>`.
+///
+/// # Examples
+/// ```
+///
+/// let greeting = i18n::new("greeting").set_args("name", "Bob");
+/// assert_eq!(format!("> {greeting}"), "> Hello, Bob!");
+/// let text: String = greeting.into();
+/// assert_eq!(text, "Hello, Bob!");
+/// ```
 pub struct I18nBuilder {
     key: String,
-    args: HashMap<String, String>,
+    args: Args,
+    locale: Option<LanguageIdentifier>,
+    attr: Option<String>,
+    isolating: Option<bool>,
+    html_escape: bool,
+    bundle: Option<Arc<Inner>>,
 }
 
 impl I18nBuilder {
+    /// Retrieves an attribute of the message instead of its value
+    ///
+    /// # Parameters
+    /// * `attr` - The attribute name
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let title = i18n::new("login-button")
+    ///     .attr("title")
+    ///     .build();
+    /// assert_eq!(title, "Click to log in");
+    /// ```
+    pub fn attr<T>(mut self, attr: T) -> Self
+    where
+        T: ToString
+    {
+        self.attr = Some(attr.to_string());
+        self
+    }
+
+    /// Targets a specific locale for this lookup only
+    ///
+    /// The global locale is left untouched, which makes this suitable for
+    /// servers rendering responses for many users concurrently. An invalid
+    /// language identifier is ignored and the active locale is used instead.
+    ///
+    /// # Parameters
+    /// * `locale` - The language identifier to use (e.g., "es-MX")
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let hello = i18n::new("hello")
+    ///     .locale("es-MX")
+    ///     .build();
+    /// assert_eq!(hello, "Hola");
+    /// ```
+    pub fn locale<T>(mut self, locale: T) -> Self
+    where
+        T: ToString
+    {
+        self.locale = locale.to_string().parse().ok();
+        self
+    }
+
+    /// Overrides bidi isolation of placeables for this lookup only
+    ///
+    /// With isolation, each placeable is wrapped in Unicode FSI/PDI marks so
+    /// mixed-direction text (an Arabic UI showing English product names)
+    /// renders correctly. The global setting is `I18nConfig::use_isolating`.
+    ///
+    /// # Parameters
+    /// * `isolating` - Whether to wrap placeables in isolation marks
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let greeting = i18n::new("greeting")
+    ///     .isolating(true)
+    ///     .set_args("name", "Alice")
+    ///     .build();
+    /// assert_eq!(greeting, "Hello, \u{2068}Alice\u{2069}!");
+    /// ```
+    pub fn isolating(mut self, isolating: bool) -> Self {
+        self.isolating = Some(isolating);
+        self
+    }
+
+    /// Escapes HTML in the arguments of this lookup
+    ///
+    /// Text arguments have `&`, `<`, `>`, `"`, and `'` replaced with entities
+    /// before they are interpolated, while the translation itself keeps its
+    /// markup, so user-provided values can go into translated HTML snippets
+    /// without opening an XSS hole. Numbers are left untouched.
+    ///
+    /// # Parameters
+    /// * `escape` - Whether to escape the arguments
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let greeting = i18n::new("greeting")
+    ///     .html_escape(true)
+    ///     .set_args("name", "<script>")
+    ///     .build();
+    /// assert_eq!(greeting, "Hello, &lt;script&gt;!");
+    /// ```
+    pub fn html_escape(mut self, escape: bool) -> Self {
+        self.html_escape = escape;
+        self
+    }
+
     /// Sets a parameter for the translation
     ///
+    /// Values are taken as text, so any `ToString` type such as a UUID can be
+    /// passed. Text that is exactly a number, as numbers write themselves, is
+    /// kept numeric, so Fluent plural selectors
+    /// (`{ $count -> [one] ... *[other] ... }`) see the value itself. Use
+    /// `set_value` to pass a value with its own type.
+    ///
     /// # Parameters
     /// * `key` - The parameter key
     /// * `value` - The parameter value
@@ -91,16 +1155,93 @@ impl I18nBuilder {
     ///
     /// # Examples
     /// ```
+    ///
+    /// let user = String::from("Alice");
     /// let builder = i18n::new("greeting")
-    ///     .set_args("user", "Alice")
-    ///     .set_args("count", "3");
+    ///     .set_args("user", &user)
+    ///     .set_args("count", 3);
+    ///
+    /// let one = i18n::new("emails")
+    ///     .set_args("count", 1)
+    ///     .build();
+    /// assert_eq!(one, "You have one email");
     /// ```
     pub fn set_args<T, U>(mut self, key: T, value: U) -> Self
     where
         T: ToString,
         U: ToString
     {
-        self.args.insert(key.to_string(), value.to_string());
+        self.args.set(key.to_string(), args::text_value(value.to_string()));
+        self
+    }
+
+    /// Sets a parameter for the translation, keeping the value's type
+    ///
+    /// Unlike `set_args`, strings stay strings even if they look like a
+    /// number, and types implementing `IntoFluentValue` decide how they are
+    /// passed to Fluent.
+    ///
+    /// # Parameters
+    /// * `key` - The parameter key
+    /// * `value` - The parameter value (strings, integers, floats, or any `IntoFluentValue`)
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let zip = String::from("02134");
+    /// let greeting = i18n::new("greeting")
+    ///     .set_value("name", &zip)
+    ///     .build();
+    /// assert_eq!(greeting, "Hello, 02134!");
+    ///
+    /// let one = i18n::new("emails")
+    ///     .set_value("count", 1)
+    ///     .build();
+    /// assert_eq!(one, "You have one email");
+    /// ```
+    pub fn set_value<T, U>(mut self, key: T, value: U) -> Self
+    where
+        T: ToString,
+        U: IntoFluentValue
+    {
+        self.args.set(key.to_string(), value.into_fluent_value());
+        self
+    }
+
+    /// Sets a typed parameter for the translation
+    ///
+    /// Like `set_args`, but takes any Fluent value, such as a `FluentNumber`
+    /// carrying its own formatting options.
+    ///
+    /// # Parameters
+    /// * `key` - The parameter key
+    /// * `value` - The parameter value (strings, integers, or floats)
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let one = i18n::new("emails")
+    ///     .set_arg("count", 1)
+    ///     .build();
+    /// assert_eq!(one, "You have one email");
+    ///
+    /// let many = i18n::new("emails")
+    ///     .set_arg("count", 2.5)
+    ///     .build();
+    /// assert_eq!(many, "You have 2.5 emails");
+    /// ```
+    pub fn set_arg<T, U>(mut self, key: T, value: U) -> Self
+    where
+        T: ToString,
+        U: Into<FluentValue<'static>>
+    {
+        self.args.set(key.to_string(), value.into());
         self
     }
 
@@ -123,21 +1264,7 @@ impl I18nBuilder {
     where
         T: ToString
     {
-        if self.args.is_empty() {
-            return get(key);
-        }
-
-        let key = key.to_string();
-        let args =  self.args
-            .iter()
-            .map(|(k, v)| (
-                Cow::from(k.clone()),
-                FluentValue::from(v.clone())
-            ))
-            .collect();
-
-        I18N.loader
-            .lookup_with_args(&I18N.locales, &key, &args)
+        self.lookup(&key.to_string())
     }
 
     /// Executes translation using the builder's key and arguments
@@ -156,20 +1283,137 @@ impl I18nBuilder {
     ///     .build();  // Returns "Hello, Bob!"
     /// ```
     pub fn build(&self) -> String {
-        if self.args.is_empty() {
-            return get(&self.key);
+        self.lookup(&self.message_key())
+    }
+
+    /// Executes translation, returning `None` if the message is missing
+    ///
+    /// # Return
+    /// Returns the translated string, or `None` if the builder's key does not
+    /// exist in the locale or its fallback chain
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let message = i18n::new("missing")
+    ///     .set_args("name", "Bob")
+    ///     .build_opt();
+    /// assert_eq!(message, None);
+    /// ```
+    pub fn build_opt(&self) -> Option<String> {
+        let locale = self.locale.clone().unwrap_or_else(locale);
+
+        self.with_args(|args| self.i18n().try_lookup(&locale, &self.message_key(), args, self.isolating))
+    }
+
+    /// Executes translation, falling back to a caller-supplied default
+    ///
+    /// # Parameters
+    /// * `default` - The text returned when the message is missing
+    ///
+    /// # Return
+    /// Returns the translated string, or `default` if the builder's key does
+    /// not exist in the locale or its fallback chain
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let message = i18n::new("missing")
+    ///     .set_args("name", "Bob")
+    ///     .build_or("Hi, Bob");
+    /// assert_eq!(message, "Hi, Bob");
+    /// ```
+    pub fn build_or<T>(&self, default: T) -> String
+    where
+        T: ToString
+    {
+        self.build_opt().unwrap_or_else(|| default.to_string())
+    }
+
+    /// Executes translation, returning the formatting errors instead of hiding them
+    ///
+    /// Fluent reports problems such as a placeable without its argument or a
+    /// selector without a default variant. `build` falls back past such
+    /// messages; `try_build` returns the errors, and a missing message is
+    /// reported as an unresolved message reference.
+    ///
+    /// # Return
+    /// Returns the translated string, or every error raised while resolving it
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let message = i18n::new("greeting")
+    ///     .set_args("name", "Bob")
+    ///     .try_build();
+    /// assert_eq!(message.unwrap(), "Hello, Bob!");
+    ///
+    /// let errors = i18n::new("greeting")
+    ///     .try_build()
+    ///     .unwrap_err();
+    /// assert!(errors.iter().any(|e| e.to_string().contains("$name")));
+    /// ```
+    pub fn try_build(&self) -> Result<String, Vec<FluentError>> {
+        let locale = self.locale.clone().unwrap_or_else(locale);
+
+        self.with_args(|args| self.i18n().resolve(&locale, &self.message_key(), args, self.isolating))
+            .map_err(|e| match e {
+                LookupError::FormattingError(_, errors) => errors,
+                LookupError::MissingMessage(..) | LookupError::MissingLocale(_) => {
+                    let reference = ReferenceKind::Message { id: self.key.clone(), attribute: self.attr.clone() };
+                    vec![FluentError::ResolverError(ResolverError::Reference(reference))]
+                }
+            })
+    }
+
+    pub(crate) fn with_locale(mut self, locale: LanguageIdentifier) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    pub(crate) fn with_bundle(mut self, bundle: Option<Arc<Inner>>) -> Self {
+        self.bundle = bundle;
+        self
+    }
+
+    fn i18n(&self) -> &Inner {
+        self.bundle.as_deref().unwrap_or_else(|| instance())
+    }
+
+    fn lookup(&self, key: &str) -> String {
+        let locale = self.locale.clone().unwrap_or_else(locale);
+
+        self.with_args(|args| self.i18n().lookup(&locale, key, args, self.isolating))
+    }
+
+    fn message_key(&self) -> Cow<'_, str> {
+        match &self.attr {
+            Some(attr) => Cow::Owned(attr_key(&self.key, attr)),
+            None => Cow::Borrowed(&self.key),
         }
+    }
 
-        let args = self.args
-            .iter()
-            .map(|(k, v)| (
-                Cow::from(k.clone()),
-                FluentValue::from(v.clone())
-            ))
-            .collect();
+    /// Passes the arguments to `f`, or `None` if none were set, escaping them if asked to
+    fn with_args<R>(&self, f: impl FnOnce(Option<&Args>) -> R) -> R {
+        if self.args.iter().next().is_none() {
+            f(None)
+        } else if self.html_escape {
+            f(Some(&args::escape_args(&self.args, args::escape_html)))
+        } else {
+            f(Some(&self.args))
+        }
+    }
+}
 
-        I18N.loader
-            .lookup_with_args(&I18N.locales, &self.key, &args)
+impl fmt::Display for I18nBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.build())
+    }
+}
+
+impl From<I18nBuilder> for String {
+    fn from(builder: I18nBuilder) -> Self {
+        builder.build()
     }
 }
 
@@ -205,6 +1449,52 @@ where
 
     I18nBuilder {
         key,
-        args: HashMap::new(),
+        args: FluentArgs::new(),
+        locale: None,
+        attr: None,
+        isolating: None,
+        html_escape: false,
+        bundle: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    #[test]
+    fn terms_resolve_along_the_fallback_chain() {
+        let _guard = fixtures(&[
+            ("en-US", "-brand-name = Acme\n    .short = AC\n"),
+            ("es-MX", "-brand-name = Acme México\n"),
+        ]);
+
+        assert_eq!(term("-brand-name"), "Acme");
+        assert_eq!(term("brand-name.short"), "AC");
+
+        let es: LanguageIdentifier = "es-MX".parse().unwrap();
+        assert_eq!(instance().lookup_term(&es, "brand-name"), "Acme México");
+        assert_eq!(instance().lookup_term(&es, "-brand-name.short"), "AC");
+        assert_eq!(instance().lookup_term(&es, "-missing"), "Unknown localization -missing");
+    }
+
+    #[test]
+    fn set_args_keeps_only_numbers_written_as_numbers_numeric() {
+        let _guard = fixtures(&[("en-US", "pick = { $value ->\n    [7] seven\n   *[other] other { $value }\n}\n")]);
+
+        assert_eq!(new("pick").set_args("value", 7).build(), "seven");
+        assert_eq!(new("pick").set_args("value", "007").build(), "other 007");
+        assert_eq!(new("pick").set_value("value", "7").build(), "other 7");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn lenient_init_reports_the_load_error_as_a_diagnostic() {
+        let config = I18nConfig { dir: "./missing/locales/".to_string(), lenient_init: true, ..Default::default() };
+        let i18n = Inner::new(&config, false).unwrap();
+
+        let diagnostics = i18n.catalog.read().unwrap().diagnostics().clone();
+        assert!(diagnostics.load_error.is_some_and(|error| error.contains("missing/locales")));
+        assert_eq!(i18n.lookup(&i18n.locales.read().unwrap(), "hello", None, None), "hello");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::source::LocaleSource;
+use crate::{ERROR_LOCK, I18N, I18nError};
+use std::sync::{Arc, RwLock};
+
+/// Catalogs contributed by library crates, merged into the global catalog
+static LIBRARIES: RwLock<Vec<(String, Arc<dyn LocaleSource>)>> = RwLock::new(Vec::new());
+
+/// Registers the translations shipped by a library crate
+///
+/// Reusable components can embed their own FTL files (usually with
+/// `embed_locales!`) and merge them into the global catalog, so applications
+/// get their messages without copying files. The application's own messages
+/// override library messages with the same key, and only the locales the
+/// application provides are loaded. Registering the same crate again replaces
+/// its translations. Named bundles are not affected.
+///
+/// # Parameters
+/// * `crate_name` - The name of the contributing crate, usually `env!("CARGO_PKG_NAME")`
+/// * `resources` - The library's FTL sources
+///
+/// # Return
+/// Returns an error if translations are already initialized and reloading
+/// them with the new messages fails, in which case the previous translations
+/// stay in place
+///
+/// # Examples
+/// ```
+///
+/// use i18n::{I18nError, LanguageIdentifier, LocaleSource};
+///
+/// #[derive(Debug)]
+/// struct Widgets;
+///
+/// impl LocaleSource for Widgets {
+///     fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+///         Ok(vec!["en-US".parse().unwrap()])
+///     }
+///
+///     fn messages(&self, _locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+///         Ok(vec!["widgets-save = Save".to_string()])
+///     }
+/// }
+///
+/// i18n::register_embedded("widgets", Widgets).unwrap();
+///
+/// assert_eq!(i18n::get("widgets-save"), "Save");
+/// assert_eq!(i18n::get("hello"), "Hello");  // From the application
+/// ```
+pub fn register_embedded<T, S>(crate_name: T, resources: S) -> Result<(), I18nError>
+where
+    T: ToString,
+    S: LocaleSource + 'static,
+{
+    let name = crate_name.to_string();
+    let resources: Arc<dyn LocaleSource> = Arc::new(resources);
+
+    {
+        let mut libraries = LIBRARIES.write().expect(ERROR_LOCK);
+        libraries.retain(|(n, _)| *n != name);
+        libraries.push((name, resources));
+    }
+
+    // Catalogs built later pick the library up from the registry
+    match I18N.get() {
+        Some(_) => crate::reload(),
+        None => Ok(()),
+    }
+}
+
+/// Returns the registered library sources, or none for catalogs without them
+pub(crate) fn sources(libraries: bool) -> Vec<Arc<dyn LocaleSource>> {
+    if !libraries {
+        return Vec::new();
+    }
+
+    LIBRARIES.read().expect(ERROR_LOCK).iter().map(|(_, source)| Arc::clone(source)).collect()
+}
@@ -0,0 +1,64 @@
+use crate::{I18nBuilder, LanguageIdentifier, new};
+use std::fmt;
+
+/// A resolved locale scoping lookups to one language
+///
+/// Produced by the web framework integrations for each request, so handler
+/// code can translate in the visitor's language without touching the global
+/// locale.
+///
+/// # Examples
+/// ```
+///
+/// let locale = i18n::Locale::from(i18n::negotiate("es-MX, en;q=0.5"));
+///
+/// assert_eq!(locale.get("hello"), "Hola");
+/// let greeting = locale.builder("greeting")
+///     .set_args("name", "Bob")
+///     .build();
+/// assert_eq!(greeting, "¡Hola, Bob!");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale(pub LanguageIdentifier);
+
+impl Locale {
+    /// Retrieves a translation for the given key in this locale
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns the translated string, using the fallback chain if needed
+    pub fn get<T>(&self, key: T) -> String
+    where
+        T: ToString,
+    {
+        self.builder(key).build()
+    }
+
+    /// Creates a builder targeting this locale
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns a new I18nBuilder instance
+    pub fn builder<T>(&self, key: T) -> I18nBuilder
+    where
+        T: ToString,
+    {
+        new(key).with_locale(self.0.clone())
+    }
+}
+
+impl From<LanguageIdentifier> for Locale {
+    fn from(locale: LanguageIdentifier) -> Self {
+        Self(locale)
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
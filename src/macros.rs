@@ -0,0 +1,27 @@
+/// Translates a key with optional inline arguments
+///
+/// Expands to `i18n::get` for a bare key, or to the builder calls with
+/// `set_value` for each argument. Values keep their type, so numbers drive
+/// plural selection, and borrowed strings are copied.
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::t!("hello"), "Hello");
+/// assert_eq!(i18n::t!("greeting", name = "Bob"), "Hello, Bob!");
+/// assert_eq!(i18n::t!("emails", count = 3), "You have 3 emails");
+///
+/// let name = String::from("Alice");
+/// assert_eq!(i18n::t!("greeting", name = &name), "Hello, Alice!");
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr $(,)?) => {
+        $crate::get($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::new($key)
+            $(.set_value(stringify!($name), $value))+
+            .build()
+    };
+}
@@ -0,0 +1,81 @@
+use crate::{Args, I18nBuilder, args, locale, new};
+use pulldown_cmark::{Event, Options, Parser, html};
+
+/// Retrieves a translation for the given key, rendered from Markdown to HTML
+///
+/// Translators can use emphasis, links, lists, and the rest of CommonMark in
+/// FTL. Raw HTML in the message is escaped rather than passed through, so the
+/// output only holds the markup Markdown produces.
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+///
+/// # Return
+/// Returns the rendered HTML, with paragraphs wrapped in `<p>` elements
+///
+/// # Examples
+/// ```
+///
+/// i18n::test_loader(&[("en-US", "tos-summary = By signing up you accept our **[terms](https://example.com/terms)**.")]);
+///
+/// let summary = i18n::get_md("tos-summary");
+/// assert_eq!(summary, "<p>By signing up you accept our <strong><a href=\"https://example.com/terms\">terms</a></strong>.</p>\n");
+/// ```
+pub fn get_md<T>(key: T) -> String
+where
+    T: ToString,
+{
+    new(key).build_md()
+}
+
+impl I18nBuilder {
+    /// Executes translation and renders the result from Markdown to HTML
+    ///
+    /// Markdown syntax in text arguments is escaped before interpolation, so
+    /// user-provided values show up verbatim instead of adding links or
+    /// formatting. Raw HTML is escaped as with `get_md`.
+    ///
+    /// # Return
+    /// Returns the rendered HTML
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// i18n::test_loader(&[("en-US", "welcome = Welcome back, **{ $name }**!")]);
+    ///
+    /// let welcome = i18n::new("welcome")
+    ///     .set_args("name", "[Bob](https://evil.example)")
+    ///     .build_md();
+    /// assert_eq!(welcome, "<p>Welcome back, <strong>[Bob](https://evil.example)</strong>!</p>\n");
+    /// ```
+    pub fn build_md(&self) -> String {
+        let locale = self.locale.clone().unwrap_or_else(locale);
+        let args: Option<Args> = self.args.iter().next().map(|_| args::escape_args(&self.args, escape));
+
+        render(&self.i18n().lookup(&locale, &self.message_key(), args.as_ref(), self.isolating))
+    }
+}
+
+fn render(text: &str) -> String {
+    let events = Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+/// Escapes the Markdown syntax of `text` with backslashes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
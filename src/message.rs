@@ -0,0 +1,161 @@
+use crate::{I18nBuilder, IntoFluentValue, ToFluentArgs};
+use fluent_templates::fluent_bundle::FluentValue;
+use fluent_templates::fluent_bundle::types::FluentNumber;
+use std::fmt::{self, Display, Formatter};
+
+/// Argument kept by a `LocalizedMessage`, limited to owned and thread-safe values
+#[derive(Debug, Clone, PartialEq)]
+enum Arg {
+    String(String),
+    Number(FluentNumber),
+}
+
+impl Arg {
+    fn from_value(value: FluentValue<'_>) -> Option<Self> {
+        match value {
+            FluentValue::String(value) => Some(Self::String(value.into_owned())),
+            FluentValue::Number(number) => Some(Self::Number(number)),
+            FluentValue::Custom(_) | FluentValue::None | FluentValue::Error => None,
+        }
+    }
+
+    fn to_value(&self) -> FluentValue<'static> {
+        match self {
+            Self::String(value) => FluentValue::from(value.clone()),
+            Self::Number(number) => FluentValue::Number(number.clone()),
+        }
+    }
+}
+
+/// A translation whose language is decided when it is formatted
+///
+/// Stores the key and arguments only. Libraries can return it from errors or
+/// results and leave the presentation layer to render it, through `Display`
+/// in the active locale or `to_string_in` for a specific one. It is `Send`
+/// and `Sync`, so it fits inside error types.
+///
+/// # Fields
+/// * `key` - The translation key to look up
+/// * `attr` - Optional attribute of the message to render instead of its value
+/// * `args` - The arguments, in the order they were set
+///
+/// # Examples
+/// ```
+///
+/// use i18n::LocalizedMessage;
+///
+/// let name = String::from("Bob");
+/// let message = LocalizedMessage::new("greeting").set_arg("name", &name);
+///
+/// assert_eq!(message.to_string(), "Hello, Bob!");
+/// assert_eq!(message.to_string_in("es-MX"), "¡Hola, Bob!");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedMessage {
+    key: String,
+    attr: Option<String>,
+    args: Vec<(String, Arg)>,
+}
+
+impl LocalizedMessage {
+    /// Creates a message for a key, without arguments
+    ///
+    /// # Parameters
+    /// * `key` - The translation key to look up
+    ///
+    /// # Return
+    /// Returns a new LocalizedMessage
+    pub fn new<T: ToString>(key: T) -> Self {
+        Self {
+            key: key.to_string(),
+            attr: None,
+            args: Vec::new(),
+        }
+    }
+
+    /// Renders an attribute of the message instead of its value
+    ///
+    /// # Parameters
+    /// * `attr` - The attribute name
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    pub fn attr<T: ToString>(mut self, attr: T) -> Self {
+        self.attr = Some(attr.to_string());
+        self
+    }
+
+    /// Sets a typed argument, replacing any previous value of the same name
+    ///
+    /// Custom Fluent values cannot be stored and are ignored.
+    ///
+    /// # Parameters
+    /// * `key` - The argument name
+    /// * `value` - The argument value (strings, integers, floats, or any `IntoFluentValue`)
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    pub fn set_arg<T, U>(mut self, key: T, value: U) -> Self
+    where
+        T: ToString,
+        U: IntoFluentValue
+    {
+        let key = key.to_string();
+        if let Some(value) = Arg::from_value(value.into_fluent_value()) {
+            self.args.retain(|(k, _)| *k != key);
+            self.args.push((key, value));
+        }
+
+        self
+    }
+
+    /// Sets the arguments from a struct, such as one deriving `FluentArgs`
+    ///
+    /// # Parameters
+    /// * `value` - A value implementing `ToFluentArgs`
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    pub fn with<T: ToFluentArgs + ?Sized>(self, value: &T) -> Self {
+        value
+            .to_fluent_args()
+            .into_iter()
+            .fold(self, |message, (key, value)| message.set_arg(key, value))
+    }
+
+    /// Returns the translation key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Formats the message in a specific locale
+    ///
+    /// An invalid language identifier is ignored and the active locale is
+    /// used instead.
+    ///
+    /// # Parameters
+    /// * `locale` - The language identifier to render in (e.g., "es-MX")
+    ///
+    /// # Return
+    /// Returns the translated string with arguments substituted
+    pub fn to_string_in<T: ToString>(&self, locale: T) -> String {
+        self.builder().locale(locale).build()
+    }
+
+    fn builder(&self) -> I18nBuilder {
+        let builder = self.args
+            .iter()
+            .fold(crate::new(&self.key), |builder, (key, value)| builder.set_arg(key, value.to_value()));
+
+        match &self.attr {
+            Some(attr) => builder.attr(attr),
+            None => builder,
+        }
+    }
+}
+
+impl Display for LocalizedMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.builder().build())
+    }
+}
@@ -0,0 +1,97 @@
+//! MiniJinja integration exposing translations to templates
+//!
+//! ```
+//! let mut env = minijinja::Environment::new();
+//! i18n::minijinja::add_i18n_functions(&mut env);
+//!
+//! let text = env.render_str(r#"{{ t("greeting", name="Bob") }}"#, ()).unwrap();  // Returns "Hello, Bob!"
+//! ```
+
+use crate::plural::{self, PluralCategory};
+use crate::{I18nBuilder, LanguageIdentifier, locale};
+use ::minijinja::value::{Kwargs, Value};
+use ::minijinja::{Environment, Error, State};
+use intl_pluralrules::PluralRuleType;
+
+/// Name of the argument and context variable selecting the locale
+const LOCALE: &str = "locale";
+
+/// Registers the `t` and `plural` functions with a MiniJinja environment
+///
+/// `t("key", name=value)` translates a key, passing the keyword arguments to
+/// the message (numbers stay numeric for plural selectors). `plural(count)`
+/// returns the CLDR plural category of a number ("one", "few", "other", ...)
+/// for branching in templates. Both take the locale from a `locale` keyword
+/// argument, then a `locale` variable of the render context, and default to
+/// the active locale. Attributes are addressed as `"message.attribute"`.
+///
+/// # Parameters
+/// * `env` - The environment to register the functions with
+///
+/// # Examples
+/// ```
+///
+/// let mut env = minijinja::Environment::new();
+/// i18n::minijinja::add_i18n_functions(&mut env);
+///
+/// assert_eq!(env.render_str("{{ plural(count) }}", minijinja::context! { count => 1 }).unwrap(), "one");
+/// ```
+pub fn add_i18n_functions(env: &mut Environment<'_>) {
+    env.add_function("t", translate);
+    env.add_function("plural", plural_form);
+}
+
+fn translate(state: &State, key: &str, kwargs: Kwargs) -> Result<String, Error> {
+    let mut builder = crate::new(key);
+    for name in kwargs.args().filter(|name| *name != LOCALE) {
+        builder = set_arg(builder, name, kwargs.get(name)?);
+    }
+
+    if let Some(locale) = locale_of(state, &kwargs)? {
+        builder = builder.locale(locale);
+    }
+
+    Ok(builder.build())
+}
+
+fn plural_form(state: &State, count: Value, kwargs: Kwargs) -> Result<&'static str, Error> {
+    let locale = locale_of(state, &kwargs)?
+        .and_then(|locale| locale.parse::<LanguageIdentifier>().ok())
+        .unwrap_or_else(locale);
+
+    let category = match count.as_i64() {
+        Some(count) => plural::select(&locale, count, PluralRuleType::CARDINAL),
+        None => match f64::try_from(count) {
+            Ok(count) => plural::select(&locale, count, PluralRuleType::CARDINAL),
+            Err(_) => PluralCategory::Other,
+        },
+    };
+
+    kwargs.assert_all_used()?;
+
+    Ok(category.as_str())
+}
+
+fn locale_of(state: &State, kwargs: &Kwargs) -> Result<Option<String>, Error> {
+    let locale = match kwargs.get::<Option<String>>(LOCALE)? {
+        Some(locale) => Some(locale),
+        None => state.lookup(LOCALE).and_then(|locale| locale.as_str().map(str::to_string)),
+    };
+
+    Ok(locale)
+}
+
+fn set_arg(builder: I18nBuilder, name: &str, value: Value) -> I18nBuilder {
+    if let Some(value) = value.as_str() {
+        return builder.set_arg(name, value.to_string());
+    }
+
+    match value.as_i64() {
+        Some(number) => builder.set_arg(name, number),
+        None if value.is_number() => match f64::try_from(value.clone()) {
+            Ok(number) => builder.set_arg(name, number),
+            Err(_) => builder.set_arg(name, value.to_string()),
+        },
+        None => builder.set_arg(name, value.to_string()),
+    }
+}
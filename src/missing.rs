@@ -0,0 +1,130 @@
+use crate::LanguageIdentifier;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, RwLock};
+
+type MissingHook = Arc<dyn Fn(&LanguageIdentifier, &str) + Send + Sync>;
+
+static HOOK: RwLock<Option<MissingHook>> = RwLock::new(None);
+
+/// Function producing the text shown for a missing message
+pub type MissingKeyHandler = Arc<dyn Fn(&LanguageIdentifier, &str) -> String + Send + Sync>;
+
+/// What `get`, `args`, and `build` return when a message is missing
+///
+/// Applies once the locale and its whole fallback chain have been searched.
+/// `get_opt` and `get_or` are not affected.
+///
+/// # Variants
+/// * `Placeholder` - Returns `"Unknown localization {key}"` (the default)
+/// * `ReturnKey` - Returns the key itself
+/// * `ReturnEmpty` - Returns an empty string
+/// * `Panic` - Panics, so tests and CI fail loudly on untranslated keys
+/// * `Callback` - Returns the result of a function called with the locale and key
+///
+/// # Examples
+/// ```
+///
+/// use std::sync::Arc;
+///
+/// let config = i18n::I18nConfig {
+///     missing: i18n::MissingKeyPolicy::Callback(Arc::new(|locale, key| {
+///         format!("[{locale}] {key}")
+///     })),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Default)]
+pub enum MissingKeyPolicy {
+    #[default]
+    Placeholder,
+    ReturnKey,
+    ReturnEmpty,
+    Panic,
+    Callback(MissingKeyHandler),
+}
+
+impl MissingKeyPolicy {
+    /// Parses a policy name as used by the `I18N_MISSING` variable
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "placeholder" => Some(Self::Placeholder),
+            "key" => Some(Self::ReturnKey),
+            "empty" => Some(Self::ReturnEmpty),
+            "panic" => Some(Self::Panic),
+            _ => None,
+        }
+    }
+
+    /// Produces the text returned for `key`, missing in `locale`
+    ///
+    /// The placeholder and the panic message mention `suggestion`, the closest
+    /// existing key, when there is one.
+    pub(crate) fn apply(&self, locale: &LanguageIdentifier, key: &str, suggestion: Option<&str>) -> String {
+        let hint = hint(suggestion);
+
+        match self {
+            Self::Placeholder => format!("Unknown localization {key}{hint}"),
+            Self::ReturnKey => key.to_string(),
+            Self::ReturnEmpty => String::new(),
+            Self::Panic => panic!("Missing translation {key} for {locale}{hint}"),
+            Self::Callback(handler) => handler(locale, key),
+        }
+    }
+}
+
+impl Debug for MissingKeyPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Placeholder => f.write_str("Placeholder"),
+            Self::ReturnKey => f.write_str("ReturnKey"),
+            Self::ReturnEmpty => f.write_str("ReturnEmpty"),
+            Self::Panic => f.write_str("Panic"),
+            Self::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+/// Formats the " (did you mean `key`?)" suffix of miss reports
+pub(crate) fn hint(suggestion: Option<&str>) -> String {
+    suggestion
+        .map(|key| format!(" (did you mean `{key}`?)"))
+        .unwrap_or_default()
+}
+
+/// Registers a function called whenever a lookup falls back or fails
+///
+/// The hook receives the requested locale and the key. It runs when the
+/// message is missing everywhere, and also when it was only found in another
+/// language of the fallback chain, which makes untranslated strings visible in
+/// production logs and metrics. Registering a hook replaces the previous one.
+///
+/// # Parameters
+/// * `hook` - The function called with the locale and key
+///
+/// # Examples
+/// ```
+///
+/// i18n::on_missing(|locale, key| {
+///     eprintln!("Untranslated {key} for {locale}");
+/// });
+///
+/// let farewell = i18n::new("farewell")
+///     .locale("es-MX")
+///     .build();
+/// assert_eq!(farewell, "Goodbye");  // Also reports farewell for es-MX
+/// ```
+pub fn on_missing<F>(hook: F)
+where
+    F: Fn(&LanguageIdentifier, &str) + Send + Sync + 'static,
+{
+    *HOOK.write().expect(crate::ERROR_LOCK) = Some(Arc::new(hook));
+}
+
+/// Calls the registered hook, if any, for `key` missing in `locale`
+pub(crate) fn notify(locale: &LanguageIdentifier, key: &str) {
+    let hook = HOOK.read().expect(crate::ERROR_LOCK).clone();
+
+    if let Some(hook) = hook {
+        hook(locale, key);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::catalog::Bundle;
+use crate::ftl::variable;
+use crate::keys::CATEGORIES;
+use crate::{ERROR_LOCK, LanguageIdentifier, instance, locale, negotiate};
+use fluent_syntax::ast::{Expression, InlineExpression, Pattern, PatternElement, VariantKey};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// A message converted for mobile platforms
+///
+/// # Variants
+/// * `Text` - A string, with its arguments numbered as in the default locale
+/// * `Plural` - Forms by plural category, selected on argument 1
+#[derive(Debug)]
+enum Resource {
+    Text(Vec<Segment>),
+    Plural(Vec<(&'static str, Vec<Segment>)>),
+}
+
+/// Part of a converted message
+///
+/// # Variants
+/// * `Text` - Literal text
+/// * `Argument` - A format argument, by its 1-based position
+#[derive(Debug)]
+enum Segment {
+    Text(String),
+    Argument(usize),
+}
+
+/// Exports a locale as an Android `strings.xml` resource file
+///
+/// Every message and attribute of the locale becomes a `<string>`, named after
+/// its key with `-` and `.` replaced by `_` (`login-button.title` becomes
+/// `login_button_title`). Variables become positional format arguments
+/// (`%1$s`) numbered by first use in the default locale, so every locale
+/// takes them in the same order and `getString(R.string.greeting, name)`
+/// fills them in. Messages that are a single plural select on a variable
+/// become `<plurals>`, with the variable as argument 1 (`%1$d`), for
+/// `getQuantityString`; variants keyed by exact numbers are skipped. Selects
+/// elsewhere keep their default variant, and other placeables are resolved to
+/// their text. Only the locale's own messages are exported, as Android falls
+/// back to the default `values/strings.xml` itself. An invalid language
+/// identifier is ignored and the active locale is used instead.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+///
+/// # Return
+/// Returns the contents of the `strings.xml` file
+///
+/// # Examples
+/// ```
+///
+/// let xml = i18n::export_android_strings("es-MX");
+/// assert!(xml.contains(r#"<string name="greeting">¡Hola, %1$s!</string>"#));
+/// assert!(xml.contains(r#"
+///     <plurals name="emails">
+///         <item quantity="one">Tienes un correo</item>
+///         <item quantity="other">Tienes %1$d correos</item>
+///     </plurals>
+/// "#));
+/// ```
+pub fn export_android_strings<T>(locale: T) -> String
+where
+    T: ToString,
+{
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+    let mut names = HashSet::new();
+
+    for (key, resource) in resources(&locale.to_string()) {
+        let name = key.replace(['-', '.'], "_");
+        if !names.insert(name.clone()) {
+            continue;
+        }
+
+        match resource {
+            Resource::Text(segments) => {
+                let _ = writeln!(xml, "    <string name=\"{name}\">{}</string>", android(&segments, false));
+            }
+            Resource::Plural(forms) => {
+                let _ = writeln!(xml, "    <plurals name=\"{name}\">");
+                for (category, segments) in forms {
+                    let _ = writeln!(xml, "        <item quantity=\"{category}\">{}</item>", android(&segments, true));
+                }
+                xml.push_str("    </plurals>\n");
+            }
+        }
+    }
+
+    xml.push_str("</resources>\n");
+    xml
+}
+
+/// Exports a locale as an iOS `Localizable.strings` file
+///
+/// Every message and attribute of the locale becomes an entry keyed by
+/// message ID or `message.attribute`. Variables become positional format
+/// arguments (`%1$@`) numbered by first use in the default locale, for
+/// `String(format:)`. Plural messages are written with their `other` form,
+/// which apps get when the key is missing from `export_ios_stringsdict`'s
+/// file. Selects elsewhere keep their default variant, and other placeables
+/// are resolved to their text. Only the locale's own messages are exported,
+/// and an invalid language identifier is ignored in favor of the active
+/// locale.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+///
+/// # Return
+/// Returns the contents of the `.strings` file
+///
+/// # Examples
+/// ```
+///
+/// let strings = i18n::export_ios_strings("es-MX");
+/// assert!(strings.contains(r#""greeting" = "¡Hola, %1$@!";"#));
+/// assert!(strings.contains(r#""login-button.title" = "Haz clic para iniciar sesión";"#));
+/// ```
+pub fn export_ios_strings<T>(locale: T) -> String
+where
+    T: ToString,
+{
+    let mut strings = String::new();
+
+    for (key, resource) in resources(&locale.to_string()) {
+        let text = match resource {
+            Resource::Text(segments) => ios(&segments, false),
+            Resource::Plural(forms) => match forms.iter().find(|(category, _)| *category == "other") {
+                Some((_, segments)) => ios(segments, true),
+                None => continue,
+            },
+        };
+
+        let _ = writeln!(strings, "\"{}\" = \"{}\";", strings_escape(&key), strings_escape(&text));
+    }
+
+    strings
+}
+
+/// Exports the plural messages of a locale as an iOS `Localizable.stringsdict` file
+///
+/// Messages that are a single plural select on a variable become plural
+/// rules, with the variable as argument 1 (`%1$d`), so
+/// `String.localizedStringWithFormat` picks the form for a count. Variants
+/// keyed by exact numbers are skipped. Other messages belong in
+/// `export_ios_strings`'s file.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+///
+/// # Return
+/// Returns the contents of the `.stringsdict` property list
+///
+/// # Examples
+/// ```
+///
+/// let plist = i18n::export_ios_stringsdict("es-MX");
+/// assert!(plist.contains(r#"
+///     <key>emails</key>
+///     <dict>
+///         <key>NSStringLocalizedFormatKey</key>
+///         <string>%#@count@</string>
+/// "#));
+/// ```
+pub fn export_ios_stringsdict<T>(locale: T) -> String
+where
+    T: ToString,
+{
+    let mut plist = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    plist.push_str("<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n");
+    plist.push_str("<plist version=\"1.0\">\n<dict>\n");
+
+    for (key, resource) in resources(&locale.to_string()) {
+        let Resource::Plural(forms) = resource else {
+            continue;
+        };
+
+        let _ = writeln!(plist, "    <key>{}</key>\n    <dict>", xml_escape(&key));
+        plist.push_str("        <key>NSStringLocalizedFormatKey</key>\n        <string>%#@count@</string>\n");
+        plist.push_str("        <key>count</key>\n        <dict>\n");
+        plist.push_str("            <key>NSStringFormatSpecTypeKey</key>\n            <string>NSStringPluralRuleType</string>\n");
+        plist.push_str("            <key>NSStringFormatValueTypeKey</key>\n            <string>d</string>\n");
+        for (category, segments) in forms {
+            let _ = writeln!(plist, "            <key>{category}</key>\n            <string>{}</string>", xml_escape(&ios(&segments, true)));
+        }
+        plist.push_str("        </dict>\n    </dict>\n");
+    }
+
+    plist.push_str("</dict>\n</plist>\n");
+    plist
+}
+
+/// Converts every message and attribute of the installed locale matching `locale`
+///
+/// Arguments are numbered in the order the default locale's message first
+/// uses them, so every locale fills the same positions even when a
+/// translation reorders its placeholders. An invalid language identifier is
+/// ignored and the active locale is used instead.
+fn resources(locale: &str) -> Vec<(String, Resource)> {
+    let locale: LanguageIdentifier = locale.parse().unwrap_or_else(|_| self::locale());
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let Some(installed) = negotiate::matching(&[i18n.alias(&locale)], catalog.available()).first().copied() else {
+        return Vec::new();
+    };
+
+    let default = i18n
+        .config
+        .locale
+        .parse::<LanguageIdentifier>()
+        .ok()
+        .and_then(|default| negotiate::matching(&[i18n.alias(&default)], catalog.available()).first().copied());
+
+    let order: HashMap<String, Vec<String>> = default
+        .map(|default| catalog.convert(default, |_, _, pattern| variables(pattern)))
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+
+    catalog.convert(installed, |key, bundle, pattern| resource(bundle, pattern, order.get(key).map_or(&[], Vec::as_slice)))
+}
+
+/// Lists the variables of a pattern in order of first use, across every variant
+fn variables(pattern: &Pattern<&str>) -> Vec<String> {
+    fn walk(pattern: &Pattern<&str>, names: &mut Vec<String>) {
+        for element in &pattern.elements {
+            if let PatternElement::Placeable { expression } = element {
+                expression_variables(expression, names);
+            }
+        }
+    }
+
+    fn expression_variables(expression: &Expression<&str>, names: &mut Vec<String>) {
+        match expression {
+            Expression::Select { selector, variants } => {
+                inline_variables(selector, names);
+                for variant in variants {
+                    walk(&variant.value, names);
+                }
+            }
+            Expression::Inline(inline) => inline_variables(inline, names),
+        }
+    }
+
+    fn inline_variables(inline: &InlineExpression<&str>, names: &mut Vec<String>) {
+        match inline {
+            InlineExpression::Placeable { expression } => expression_variables(expression, names),
+            inline => {
+                if let Some(name) = variable(inline)
+                    && !names.iter().any(|known| known == name)
+                {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    let mut names = Vec::new();
+    walk(pattern, &mut names);
+    names
+}
+
+/// Converts a pattern, as plurals if it is a single plural select on a variable
+///
+/// Arguments take their position in `order`, and variables missing from it
+/// are numbered after them by first use. In plurals, the selector comes first.
+fn resource(bundle: &Bundle, pattern: &Pattern<&str>, order: &[String]) -> Resource {
+    let mut arguments: Vec<String> = order.to_vec();
+
+    if let [PatternElement::Placeable { expression: Expression::Select { selector, variants } }] = pattern.elements.as_slice()
+        && let Some(name) = variable(selector)
+        && variants.iter().all(|variant| match &variant.key {
+            VariantKey::Identifier { name } => CATEGORIES.contains(name),
+            VariantKey::NumberLiteral { .. } => true,
+        })
+    {
+        arguments.retain(|argument| argument != name);
+        arguments.insert(0, name.to_string());
+        let has_other = variants.iter().any(|variant| matches!(variant.key, VariantKey::Identifier { name: "other" }));
+
+        let mut forms = Vec::new();
+        for category in CATEGORIES {
+            let variant = variants.iter().find(|variant| match variant.key {
+                VariantKey::Identifier { name } => name == category,
+                VariantKey::NumberLiteral { .. } => category == "other" && !has_other && variant.default,
+            });
+
+            if let Some(variant) = variant {
+                let mut form = Vec::new();
+                segments(bundle, &variant.value, &mut arguments, &mut form);
+                forms.push((category, form));
+            }
+        }
+
+        return Resource::Plural(forms);
+    }
+
+    let mut text = Vec::new();
+    segments(bundle, pattern, &mut arguments, &mut text);
+
+    Resource::Text(text)
+}
+
+/// Converts the elements of a pattern, numbering its variables in `arguments`
+fn segments(bundle: &Bundle, pattern: &Pattern<&str>, arguments: &mut Vec<String>, segments: &mut Vec<Segment>) {
+    for element in &pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => push_text(segments, value),
+            PatternElement::Placeable { expression } => self::expression(bundle, expression, arguments, segments),
+        }
+    }
+}
+
+fn expression(bundle: &Bundle, expression: &Expression<&str>, arguments: &mut Vec<String>, segments: &mut Vec<Segment>) {
+    match expression {
+        Expression::Select { variants, .. } => {
+            if let Some(variant) = variants.iter().find(|variant| variant.default) {
+                self::segments(bundle, &variant.value, arguments, segments);
+            }
+        }
+        Expression::Inline(InlineExpression::Placeable { expression }) => self::expression(bundle, expression, arguments, segments),
+        Expression::Inline(inline) => match variable(inline) {
+            Some(name) => {
+                let position = arguments.iter().position(|argument| *argument == name).unwrap_or_else(|| {
+                    arguments.push(name.to_string());
+                    arguments.len() - 1
+                });
+
+                segments.push(Segment::Argument(position + 1));
+            }
+            None => {
+                let pattern = Pattern { elements: vec![PatternElement::Placeable { expression: expression.clone() }] };
+                let text = bundle.format_pattern(&pattern, None, &mut Vec::new());
+
+                push_text(segments, &text);
+            }
+        },
+    }
+}
+
+fn push_text(segments: &mut Vec<Segment>, text: &str) {
+    match segments.last_mut() {
+        Some(Segment::Text(last)) => last.push_str(text),
+        _ => segments.push(Segment::Text(text.to_string())),
+    }
+}
+
+/// Writes segments as a format string, with `%` doubled if it has arguments
+///
+/// In plurals, argument 1 is the count and is formatted as an integer.
+fn format(segments: &[Segment], plural: bool, string: &str) -> String {
+    let formatted = plural || segments.iter().any(|segment| matches!(segment, Segment::Argument(_)));
+    let mut text = String::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Text(value) if formatted => text.push_str(&value.replace('%', "%%")),
+            Segment::Text(value) => text.push_str(value),
+            Segment::Argument(1) if plural => text.push_str("%1$d"),
+            Segment::Argument(position) => {
+                let _ = write!(text, "%{position}${string}");
+            }
+        }
+    }
+
+    text
+}
+
+/// Formats segments as the text of an Android string resource
+fn android(segments: &[Segment], plural: bool) -> String {
+    let text = format(segments, plural, "s");
+    let mut escaped = String::new();
+
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            // Leading `@` and `?` would reference other resources
+            '@' | '?' if i == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Formats segments as an iOS format string
+fn ios(segments: &[Segment], plural: bool) -> String {
+    format(segments, plural, "@")
+}
+
+/// Escapes text for a quoted `.strings` value
+fn strings_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\t', "\\t")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    const EN: &str = "order = { $user } bought { $item }\nemails = { $count ->\n    [one] One email from { $sender }\n   *[other] { $count } emails from { $sender }\n}\n";
+    const DE: &str = "order = { $item } wurde von { $user } gekauft\nemails = { $count ->\n    [one] Eine E-Mail von { $sender }\n   *[other] { $sender } schickte { $count } E-Mails\n}\n";
+
+    #[test]
+    fn reordered_placeholders_keep_their_positions() {
+        let _guard = fixtures(&[("en-US", EN), ("de-DE", DE)]);
+
+        let en = export_android_strings("en-US");
+        let de = export_android_strings("de-DE");
+        assert!(en.contains("<string name=\"order\">%1$s bought %2$s</string>"), "{en}");
+        assert!(de.contains("<string name=\"order\">%2$s wurde von %1$s gekauft</string>"), "{de}");
+
+        let ios = export_ios_strings("de-DE");
+        assert!(ios.contains("\"order\" = \"%2$@ wurde von %1$@ gekauft\";"), "{ios}");
+    }
+
+    #[test]
+    fn plural_count_is_the_first_argument() {
+        let _guard = fixtures(&[("en-US", EN), ("de-DE", DE)]);
+
+        let de = export_android_strings("de-DE");
+        assert!(de.contains("<item quantity=\"one\">Eine E-Mail von %2$s</item>"), "{de}");
+        assert!(de.contains("<item quantity=\"other\">%2$s schickte %1$d E-Mails</item>"), "{de}");
+    }
+
+    #[test]
+    fn variables_are_listed_across_variants() {
+        let resource = fluent_syntax::parser::parse("m = { $a ->\n    [x] { $b }\n   *[y] { $c } { $b }\n}\n").unwrap();
+        let Some(fluent_syntax::ast::Entry::Message(message)) = resource.body.first() else {
+            panic!("no message");
+        };
+
+        assert_eq!(variables(message.value.as_ref().unwrap()), ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::LanguageIdentifier;
+
+/// English and native names of languages, sorted by ISO 639 code
+const NAMES: &[(&str, &str, &str)] = &[
+    ("af", "Afrikaans", "Afrikaans"),
+    ("am", "Amharic", "አማርኛ"),
+    ("ar", "Arabic", "العربية"),
+    ("az", "Azerbaijani", "Azərbaycan"),
+    ("be", "Belarusian", "Беларуская"),
+    ("bg", "Bulgarian", "Български"),
+    ("bn", "Bangla", "বাংলা"),
+    ("bs", "Bosnian", "Bosanski"),
+    ("ca", "Catalan", "Català"),
+    ("cs", "Czech", "Čeština"),
+    ("cy", "Welsh", "Cymraeg"),
+    ("da", "Danish", "Dansk"),
+    ("de", "German", "Deutsch"),
+    ("el", "Greek", "Ελληνικά"),
+    ("en", "English", "English"),
+    ("eo", "Esperanto", "Esperanto"),
+    ("es", "Spanish", "Español"),
+    ("et", "Estonian", "Eesti"),
+    ("eu", "Basque", "Euskara"),
+    ("fa", "Persian", "فارسی"),
+    ("fi", "Finnish", "Suomi"),
+    ("fil", "Filipino", "Filipino"),
+    ("fr", "French", "Français"),
+    ("ga", "Irish", "Gaeilge"),
+    ("gl", "Galician", "Galego"),
+    ("gu", "Gujarati", "ગુજરાતી"),
+    ("he", "Hebrew", "עברית"),
+    ("hi", "Hindi", "हिन्दी"),
+    ("hr", "Croatian", "Hrvatski"),
+    ("hu", "Hungarian", "Magyar"),
+    ("hy", "Armenian", "Հայերեն"),
+    ("id", "Indonesian", "Indonesia"),
+    ("is", "Icelandic", "Íslenska"),
+    ("it", "Italian", "Italiano"),
+    ("ja", "Japanese", "日本語"),
+    ("ka", "Georgian", "ქართული"),
+    ("kk", "Kazakh", "Қазақ тілі"),
+    ("km", "Khmer", "ខ្មែរ"),
+    ("kn", "Kannada", "ಕನ್ನಡ"),
+    ("ko", "Korean", "한국어"),
+    ("lo", "Lao", "ລາວ"),
+    ("lt", "Lithuanian", "Lietuvių"),
+    ("lv", "Latvian", "Latviešu"),
+    ("mk", "Macedonian", "Македонски"),
+    ("ml", "Malayalam", "മലയാളം"),
+    ("mn", "Mongolian", "Монгол"),
+    ("mr", "Marathi", "मराठी"),
+    ("ms", "Malay", "Melayu"),
+    ("my", "Burmese", "မြန်မာ"),
+    ("nb", "Norwegian Bokmål", "Norsk bokmål"),
+    ("ne", "Nepali", "नेपाली"),
+    ("nl", "Dutch", "Nederlands"),
+    ("nn", "Norwegian Nynorsk", "Norsk nynorsk"),
+    ("pa", "Punjabi", "ਪੰਜਾਬੀ"),
+    ("pl", "Polish", "Polski"),
+    ("pt", "Portuguese", "Português"),
+    ("ro", "Romanian", "Română"),
+    ("ru", "Russian", "Русский"),
+    ("si", "Sinhala", "සිංහල"),
+    ("sk", "Slovak", "Slovenčina"),
+    ("sl", "Slovenian", "Slovenščina"),
+    ("sq", "Albanian", "Shqip"),
+    ("sr", "Serbian", "Српски"),
+    ("sv", "Swedish", "Svenska"),
+    ("sw", "Swahili", "Kiswahili"),
+    ("ta", "Tamil", "தமிழ்"),
+    ("te", "Telugu", "తెలుగు"),
+    ("th", "Thai", "ไทย"),
+    ("tl", "Tagalog", "Tagalog"),
+    ("tr", "Turkish", "Türkçe"),
+    ("uk", "Ukrainian", "Українська"),
+    ("ur", "Urdu", "اردو"),
+    ("uz", "Uzbek", "Oʻzbek"),
+    ("vi", "Vietnamese", "Tiếng Việt"),
+    ("zh", "Chinese", "中文"),
+    ("zu", "Zulu", "isiZulu"),
+];
+
+/// English and native name of a language
+///
+/// # Fields
+/// * `english` - The name in English (e.g., "German")
+/// * `native` - The name in the language itself (e.g., "Deutsch")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayName {
+    pub english: &'static str,
+    pub native: &'static str,
+}
+
+/// Returns the English and native name of a locale's language
+///
+/// Names come from an embedded table of common languages and only depend on
+/// the language subtag, so "de-AT" and "de-DE" are both "German" / "Deutsch".
+/// Useful for building language selection menus.
+///
+/// # Parameters
+/// * `locale` - The language identifier to name (e.g., "de-DE")
+///
+/// # Return
+/// Returns the names, or `None` if the identifier is invalid or the language
+/// is not in the table
+///
+/// # Examples
+/// ```
+///
+/// let name = i18n::display_name("de-DE").unwrap();
+/// assert_eq!(name.english, "German");
+/// let native = name.native;
+/// assert_eq!(native, "Deutsch");
+///
+/// for locale in i18n::available_locales() {
+///     let label = i18n::display_name(&locale).map_or(locale.to_string(), |name| name.native.to_string());
+/// }
+/// ```
+pub fn display_name<T>(locale: T) -> Option<DisplayName>
+where
+    T: ToString,
+{
+    let locale: LanguageIdentifier = locale.to_string().parse().ok()?;
+    let language = locale.language.as_str();
+
+    NAMES
+        .binary_search_by(|(code, _, _)| (*code).cmp(language))
+        .ok()
+        .map(|i| DisplayName { english: NAMES[i].1, native: NAMES[i].2 })
+}
@@ -0,0 +1,205 @@
+use crate::{LanguageIdentifier, instance, locale};
+use fluent_langneg::{NegotiationStrategy, negotiate_languages};
+
+/// Picks the best installed locale for an `Accept-Language` header
+///
+/// Language ranges are ordered by their q-value (defaulting to 1), and
+/// malformed or zero-weighted ranges are ignored. Matching also considers
+/// installed locales sharing a language, so `fr-CH` can resolve to `fr-FR`
+/// and `pt` to `pt-BR`, and configured aliases are applied to every range
+/// first.
+///
+/// # Parameters
+/// * `header` - The `Accept-Language` header value
+///
+/// # Return
+/// Returns the best matching installed locale, or the active locale if none match
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::negotiate("fr-CH, fr;q=0.9, es;q=0.8").to_string(), "es-MX");
+/// assert_eq!(i18n::negotiate("de").to_string(), "en-US");  // The active locale
+/// ```
+pub fn negotiate<T>(header: T) -> LanguageIdentifier
+where
+    T: AsRef<str>,
+{
+    let i18n = instance();
+    let requested: Vec<_> = parse_accept_language(header.as_ref())
+        .iter()
+        .map(|locale| i18n.alias(locale))
+        .collect();
+    let available = i18n.available();
+
+    matching(&requested, &available)
+        .first()
+        .map(|locale| (*locale).clone())
+        .unwrap_or_else(locale)
+}
+
+/// Matches `requested` against the `available` locales, best match first
+///
+/// Each requested locale matches installed locales exactly, then as a range
+/// (`fr` matches `fr-FR`), then through its likely subtags and region
+/// variants. As a last resort, installed locales sharing only the language are
+/// appended, so `sr` still resolves to an installed `sr-Latn`.
+pub(crate) fn matching<'a, R>(requested: &[R], available: &'a [LanguageIdentifier]) -> Vec<&'a LanguageIdentifier>
+where
+    R: AsRef<LanguageIdentifier> + 'a,
+{
+    let mut matched = negotiate_languages(requested, available, None, NegotiationStrategy::Filtering);
+
+    for locale in requested {
+        let language = LanguageIdentifier::from_parts(locale.as_ref().language, None, None, &[]);
+
+        for candidate in available {
+            if language.matches(candidate, true, false) && !matched.contains(&candidate) {
+                matched.push(candidate);
+            }
+        }
+    }
+
+    matched
+}
+
+/// Parses an `Accept-Language` header into language identifiers, best first
+pub(crate) fn parse_accept_language(header: &str) -> Vec<LanguageIdentifier> {
+    let mut ranges: Vec<(f32, LanguageIdentifier)> = header
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let tag = parts.next()?;
+            let weight = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+
+            (weight > 0.0 && tag != "*")
+                .then(|| tag.parse().ok().map(|locale| (weight, locale)))
+                .flatten()
+        })
+        .collect();
+
+    ranges.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    ranges.into_iter().map(|(_, locale)| locale).collect()
+}
+
+/// Name of the query parameter and cookie selecting a locale explicitly
+#[cfg_attr(not(any(feature = "actix", feature = "axum", feature = "tower")), allow(dead_code))]
+pub(crate) const LOCALE_PARAM: &str = "lang";
+
+/// Resolves the locale of a request from its explicit and negotiated choices
+///
+/// Applies the usual precedence without tying it to a web framework: an
+/// explicit query parameter (e.g. `?lang=fr`), then a cookie remembering the
+/// user's choice, then the `Accept-Language` header. Explicit values that match
+/// no installed locale are skipped. The `axum` and `actix` extractors use it
+/// with the `lang` query parameter and cookie.
+///
+/// # Parameters
+/// * `header` - The `Accept-Language` header value, if sent
+/// * `cookie` - The value of the locale cookie, if set
+/// * `query` - The value of the locale query parameter, if given
+///
+/// # Return
+/// Returns the best matching installed locale, or the active locale if none match
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::resolve_locale(Some("es;q=0.8"), None, Some("en-US")).to_string(), "en-US");
+/// assert_eq!(i18n::resolve_locale(Some("es;q=0.8"), Some("tlh"), None).to_string(), "es-MX");
+/// assert_eq!(i18n::resolve_locale(None, None, None).to_string(), "en-US");  // The active locale
+/// ```
+pub fn resolve_locale(header: Option<&str>, cookie: Option<&str>, query: Option<&str>) -> LanguageIdentifier {
+    let i18n = instance();
+    let available = i18n.available();
+
+    let explicit = [query, cookie]
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| tag.trim().parse::<LanguageIdentifier>().ok())
+        .find_map(|requested| {
+            matching(&[i18n.alias(&requested)], &available)
+                .first()
+                .map(|locale| (*locale).clone())
+        });
+
+    explicit
+        .or_else(|| header.map(negotiate))
+        .unwrap_or_else(locale)
+}
+
+/// Finds the value of `name` in a URL query string
+#[cfg_attr(not(any(feature = "actix", feature = "axum", feature = "tower")), allow(dead_code))]
+pub(crate) fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+/// Finds the value of cookie `name` in a `Cookie` header
+#[cfg_attr(not(any(feature = "actix", feature = "axum", feature = "tower")), allow(dead_code))]
+pub(crate) fn cookie_value<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locales(tags: &[&str]) -> Vec<LanguageIdentifier> {
+        tags.iter().map(|tag| tag.parse().unwrap()).collect()
+    }
+
+    fn names(locales: &[&LanguageIdentifier]) -> Vec<String> {
+        locales.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn accept_language_is_sorted_by_weight() {
+        let parsed = parse_accept_language("fr;q=0.5, de-CH, en;q=0.9, *;q=0.1");
+
+        assert_eq!(parsed, locales(&["de-CH", "en", "fr"]));
+    }
+
+    #[test]
+    fn accept_language_skips_invalid_and_refused_ranges() {
+        assert_eq!(parse_accept_language("es;q=0, it;q=abc, !!, pt-BR"), locales(&["pt-BR"]));
+        assert_eq!(parse_accept_language(""), locales(&[]));
+        assert_eq!(parse_accept_language("*"), locales(&[]));
+    }
+
+    #[test]
+    fn equal_weights_keep_header_order() {
+        assert_eq!(parse_accept_language("nl;q=0.8, da;q=0.8, sv"), locales(&["sv", "nl", "da"]));
+    }
+
+    #[test]
+    fn ranges_match_regional_locales() {
+        let available = locales(&["en-US", "es-MX", "fr-FR"]);
+
+        assert_eq!(names(&matching(&locales(&["fr"]), &available)), ["fr-FR"]);
+        assert_eq!(names(&matching(&locales(&["es-ES", "en-GB"]), &available)), ["es-MX", "en-US"]);
+        assert!(matching(&locales(&["de"]), &available).is_empty());
+    }
+
+    #[test]
+    fn languages_match_other_scripts_as_a_last_resort() {
+        let available = locales(&["sr-Latn", "en-US"]);
+
+        assert_eq!(names(&matching(&locales(&["sr-Cyrl-RS"]), &available)), ["sr-Latn"]);
+    }
+
+    #[test]
+    fn query_and_cookie_values_are_found_by_name() {
+        assert_eq!(query_param("page=2&lang=fr&x", "lang"), Some("fr"));
+        assert_eq!(query_param("language=fr", "lang"), None);
+        assert_eq!(cookie_value("theme=dark; lang=es-MX", "lang"), Some("es-MX"));
+        assert_eq!(cookie_value("", "lang"), None);
+    }
+}
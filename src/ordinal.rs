@@ -0,0 +1,100 @@
+use crate::catalog::Bundle;
+use crate::plural::{self, PluralCategory};
+use crate::{LanguageIdentifier, locale};
+use fluent_templates::fluent_bundle::FluentValue;
+use intl_pluralrules::PluralRuleType;
+
+/// Returns the prefix and suffix marking an ordinal in `language`
+///
+/// The affix depends on the CLDR ordinal category where the language has
+/// more than one form. Gendered languages use the masculine form.
+fn affixes(language: &str, category: PluralCategory) -> Option<(&'static str, &'static str)> {
+    use PluralCategory::*;
+
+    let affixes = match (language, category) {
+        ("en", One) => ("", "st"),
+        ("en", Two) => ("", "nd"),
+        ("en", Few) => ("", "rd"),
+        ("en", _) => ("", "th"),
+        ("fr", One) => ("", "er"),
+        ("fr", _) => ("", "e"),
+        ("ca", One) => ("", "r"),
+        ("ca", Two) => ("", "n"),
+        ("ca", Few) => ("", "t"),
+        ("ca", _) => ("", "è"),
+        ("sv", One) => ("", ":a"),
+        ("sv", _) => ("", ":e"),
+        ("es", _) => ("", ".º"),
+        ("gl" | "it" | "pt", _) => ("", "º"),
+        ("nl", _) => ("", "e"),
+        ("ru" | "uk", _) => ("", "-й"),
+        ("ko", _) => ("", "번째"),
+        ("ja" | "zh", _) => ("第", ""),
+        ("id" | "ms", _) => ("ke-", ""),
+        ("vi", _) => ("thứ ", ""),
+        ("cs" | "da" | "de" | "et" | "fi" | "fo" | "hr" | "hu" | "is" | "lv" | "nb" | "nn" | "no" | "pl" | "sk" | "sl" | "sr"
+            | "tr", _) => ("", "."),
+        _ => return None,
+    };
+
+    Some(affixes)
+}
+
+/// Formats `number` as an ordinal for `locale`
+///
+/// Languages without known ordinal markers get the bare number.
+pub(crate) fn format(locale: &LanguageIdentifier, number: i64) -> String {
+    let category = plural::select(locale, number, PluralRuleType::ORDINAL);
+
+    match affixes(locale.language.as_str(), category) {
+        Some((prefix, suffix)) => format!("{prefix}{number}{suffix}"),
+        None => number.to_string(),
+    }
+}
+
+/// Adds the `ORDINAL` function, formatting integers for the bundle's locale
+///
+/// Installed after the registered functions so an application can replace it.
+pub(crate) fn install(bundle: &mut Bundle, locale: &LanguageIdentifier) {
+    let locale = locale.clone();
+
+    let _ = bundle.add_function("ORDINAL", move |positional, _named| match positional {
+        [FluentValue::Number(number)] if number.value.fract() == 0.0 => format(&locale, number.value as i64).into(),
+        [FluentValue::String(number)] => match number.parse() {
+            Ok(number) => format(&locale, number).into(),
+            Err(_) => FluentValue::Error,
+        },
+        _ => FluentValue::Error,
+    });
+}
+
+/// Formats a number as an ordinal for the active locale
+///
+/// The suffix or prefix is chosen with the CLDR ordinal rules of the locale's
+/// language ("1st", "2nd", "3rd", "4th" in English), so "21st" and "11th" are
+/// both right. Languages with grammatical gender use the masculine marker.
+/// FTL messages can call the same helper as `ORDINAL`:
+///
+/// ```ftl
+/// finished = You finished { ORDINAL($place) }!
+/// ```
+///
+/// # Parameters
+/// * `number` - The position to format
+///
+/// # Return
+/// Returns the localized ordinal, or the bare number for languages without
+/// known ordinal markers
+///
+/// # Examples
+/// ```
+///
+/// assert_eq!(i18n::ordinal(1), "1st");
+/// assert_eq!(i18n::ordinal(22), "22nd");
+///
+/// i18n::set_locale("es-MX").unwrap();
+/// assert_eq!(i18n::ordinal(3), "3.º");
+/// ```
+pub fn ordinal<T: Into<i64>>(number: T) -> String {
+    format(&locale(), number.into())
+}
@@ -0,0 +1,105 @@
+use crate::{LanguageIdentifier, locale};
+use intl_pluralrules::operands::PluralOperands;
+use intl_pluralrules::{PluralRuleType, PluralRules};
+use std::fmt::{self, Display, Formatter};
+
+/// CLDR plural category of a number
+///
+/// Languages use a subset of these; English only distinguishes `One` and
+/// `Other`, while Polish also uses `Few` and `Many`.
+///
+/// # Variants
+/// * `Zero` - Used for zero in languages such as Arabic and Welsh
+/// * `One` - Singular forms ("1 file")
+/// * `Two` - Dual forms, as in Arabic or Slovenian
+/// * `Few` - Paucal forms, such as Polish "2 pliki"
+/// * `Many` - Forms for larger counts, such as Polish "5 plików"
+/// * `Other` - The general plural, required in every language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Returns the CLDR name of the category, as used for Fluent variant keys
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl Display for PluralCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<intl_pluralrules::PluralCategory> for PluralCategory {
+    fn from(category: intl_pluralrules::PluralCategory) -> Self {
+        match category {
+            intl_pluralrules::PluralCategory::ZERO => Self::Zero,
+            intl_pluralrules::PluralCategory::ONE => Self::One,
+            intl_pluralrules::PluralCategory::TWO => Self::Two,
+            intl_pluralrules::PluralCategory::FEW => Self::Few,
+            intl_pluralrules::PluralCategory::MANY => Self::Many,
+            intl_pluralrules::PluralCategory::OTHER => Self::Other,
+        }
+    }
+}
+
+/// Selects the plural category of a number for a locale
+///
+/// Rules are looked up for the full identifier first and then for its bare
+/// language, so "es-MX" uses the Spanish rules. Locales without CLDR rules
+/// and numbers that cannot be parsed select `Other`.
+pub(crate) fn select<T>(locale: &LanguageIdentifier, number: T, kind: PluralRuleType) -> PluralCategory
+where
+    T: TryInto<PluralOperands>,
+{
+    let language = LanguageIdentifier::from_parts(locale.language, None, None, &[]);
+
+    PluralRules::create(locale.clone(), kind)
+        .or_else(|_| PluralRules::create(language, kind))
+        .ok()
+        .and_then(|rules| rules.select(number).ok())
+        .map_or(PluralCategory::Other, PluralCategory::from)
+}
+
+/// Returns the plural category of a number for the active locale
+///
+/// Useful outside Fluent messages, e.g. to build keys such as `files-few`
+/// dynamically. Strings are accepted so that visible fraction digits are kept
+/// ("1.0" is `Other` in English while 1 is `One`).
+///
+/// # Parameters
+/// * `number` - An integer, float, or numeric string
+///
+/// # Return
+/// Returns the category the active locale's cardinal rules select
+///
+/// # Examples
+/// ```
+///
+/// use i18n::PluralCategory;
+///
+/// assert_eq!(i18n::plural_category(1), PluralCategory::One);
+/// assert_eq!(format!("files-{}", i18n::plural_category(5)), "files-other");
+/// assert_eq!(i18n::plural_category("1.0"), PluralCategory::Other);
+/// ```
+pub fn plural_category<T>(number: T) -> PluralCategory
+where
+    T: TryInto<PluralOperands>,
+{
+    select(&locale(), number, PluralRuleType::CARDINAL)
+}
@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+
+const LOWER: [char; 26] = [
+    'á', 'ƀ', 'ç', 'ḓ', 'é', 'ƒ', 'ģ', 'ĥ', 'í', 'ĵ', 'ķ', 'ļ', 'ḿ',
+    'ñ', 'ö', 'þ', 'ʠ', 'ŕ', 'š', 'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+const UPPER: [char; 26] = [
+    'Å', 'Ɓ', 'Ç', 'Ḓ', 'É', 'Ƒ', 'Ĝ', 'Ḩ', 'Í', 'Ĵ', 'Ķ', 'Ļ', 'Ḿ',
+    'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š', 'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+];
+
+/// Replaces ASCII letters with accented look-alikes
+///
+/// Installed as the bundle transform, so only the translated text is changed
+/// and placeables (`{ $name }`) keep their values.
+pub(crate) fn transform(text: &str) -> Cow<'_, str> {
+    text.chars()
+        .map(|c| match c {
+            'a'..='z' => LOWER[(c as u8 - b'a') as usize],
+            'A'..='Z' => UPPER[(c as u8 - b'A') as usize],
+            _ => c,
+        })
+        .collect::<String>()
+        .into()
+}
+
+/// Brackets a formatted message, expanding it to reveal truncation
+pub(crate) fn wrap(message: String) -> String {
+    format!("[!!! {message} !!!]")
+}
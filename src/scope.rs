@@ -0,0 +1,164 @@
+use crate::{I18nBuilder, get, get_opt, get_or, new};
+
+const DEFAULT_SEPARATOR: &str = "-";
+
+/// Translation keys sharing a prefix
+///
+/// Created by `scope`. Lets large applications organize keys by domain
+/// (`errors-not-found`, `errors-forbidden`) without repeating the prefix at
+/// every call site.
+///
+/// # Fields
+/// * `prefix` - The prefix prepended to every key
+/// * `separator` - The text placed between the prefix and the key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope {
+    prefix: String,
+    separator: String,
+}
+
+impl Scope {
+    /// Changes the text placed between the prefix and the key
+    ///
+    /// # Parameters
+    /// * `separator` - The separator to use instead of "-"
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let errors = i18n::scope("errors").separator("_");
+    /// assert_eq!(errors.key("not_found"), "errors_not_found");
+    /// ```
+    pub fn separator<T>(mut self, separator: T) -> Self
+    where
+        T: ToString,
+    {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Returns the full key of `key` within this scope
+    ///
+    /// # Parameters
+    /// * `key` - The key relative to the scope
+    ///
+    /// # Return
+    /// Returns the prefixed key
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// assert_eq!(i18n::scope("errors").key("not-found"), "errors-not-found");
+    /// ```
+    pub fn key<T>(&self, key: T) -> String
+    where
+        T: ToString,
+    {
+        format!("{}{}{}", self.prefix, self.separator, key.to_string())
+    }
+
+    /// Creates a nested scope, joined with the same separator
+    ///
+    /// # Parameters
+    /// * `prefix` - The prefix relative to this scope
+    ///
+    /// # Return
+    /// Returns a scope for the combined prefix
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// let http = i18n::scope("errors").scope("http");
+    /// assert_eq!(http.key("not-found"), "errors-http-not-found");
+    /// ```
+    pub fn scope<T>(&self, prefix: T) -> Scope
+    where
+        T: ToString,
+    {
+        Scope { prefix: self.key(prefix), separator: self.separator.clone() }
+    }
+
+    /// Retrieves a translation for a key of this scope
+    ///
+    /// # Parameters
+    /// * `key` - The key relative to the scope
+    ///
+    /// # Return
+    /// Returns the translated string for the current locale
+    pub fn get<T>(&self, key: T) -> String
+    where
+        T: ToString,
+    {
+        get(self.key(key))
+    }
+
+    /// Retrieves a translation for a key of this scope, if it exists
+    ///
+    /// # Parameters
+    /// * `key` - The key relative to the scope
+    ///
+    /// # Return
+    /// Returns the translated string, or `None` if the message is missing
+    pub fn get_opt<T>(&self, key: T) -> Option<String>
+    where
+        T: ToString,
+    {
+        get_opt(self.key(key))
+    }
+
+    /// Retrieves a translation for a key of this scope, or a default
+    ///
+    /// # Parameters
+    /// * `key` - The key relative to the scope
+    /// * `default` - The text returned when the message is missing
+    ///
+    /// # Return
+    /// Returns the translated string, or `default` if the message is missing
+    pub fn get_or<T, U>(&self, key: T, default: U) -> String
+    where
+        T: ToString,
+        U: ToString,
+    {
+        get_or(self.key(key), default)
+    }
+
+    /// Creates a builder for a key of this scope
+    ///
+    /// # Parameters
+    /// * `key` - The key relative to the scope
+    ///
+    /// # Return
+    /// Returns a builder for parameterized translations
+    pub fn builder<T>(&self, key: T) -> I18nBuilder
+    where
+        T: ToString,
+    {
+        new(self.key(key))
+    }
+}
+
+/// Creates a scope whose keys share `prefix`
+///
+/// Keys are joined to the prefix with "-" unless another separator is set.
+///
+/// # Parameters
+/// * `prefix` - The prefix prepended to every key (e.g., "errors")
+///
+/// # Return
+/// Returns a scope resolving keys relative to `prefix`
+///
+/// # Examples
+/// ```
+///
+/// let login = i18n::scope("login");
+/// assert_eq!(login.get("button"), "Log in");
+/// ```
+pub fn scope<T>(prefix: T) -> Scope
+where
+    T: ToString,
+{
+    Scope { prefix: prefix.to_string(), separator: DEFAULT_SEPARATOR.to_string() }
+}
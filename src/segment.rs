@@ -0,0 +1,257 @@
+use crate::catalog::Bundle;
+use crate::{Args, ERROR_LOCK, I18nBuilder, ToFluentArgs, locale, missing, new};
+use fluent_syntax::ast::{Expression, InlineExpression, Pattern, PatternElement, Variant};
+use fluent_templates::fluent_bundle::FluentError;
+
+/// A part of a translation, as text written by translators or an interpolated argument
+///
+/// GUI frontends render the segments in order, and can style arguments
+/// differently or make them clickable.
+///
+/// # Variants
+/// * `Text` - Literal text of the translation, including selected variants
+/// * `Arg` - The formatted value of the argument `name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Text(String),
+    Arg { name: String, value: String },
+}
+
+/// Retrieves a translation split into literal text and interpolated arguments
+///
+/// # Parameters
+/// * `key` - The translation key to look up
+/// * `args` - The arguments of the translation, as `FluentArgs` or any `ToFluentArgs` value
+///
+/// # Return
+/// Returns the segments of the translated string, in order
+///
+/// # Examples
+/// ```
+///
+/// use i18n::Segment;
+///
+/// let mut args = i18n::FluentArgs::new();
+/// args.set("name", "Bob");
+///
+/// let segments = i18n::get_segments("greeting", &args);
+/// assert_eq!(segments, [
+///     Segment::Text("Hello, ".to_string()),
+///     Segment::Arg { name: "name".to_string(), value: "Bob".to_string() },
+///     Segment::Text("!".to_string()),
+/// ]);
+/// ```
+pub fn get_segments<T, A>(key: T, args: &A) -> Vec<Segment>
+where
+    T: ToString,
+    A: ToFluentArgs + ?Sized,
+{
+    new(key).with(args).build_segments()
+}
+
+impl I18nBuilder {
+    /// Executes translation, splitting the result into literal text and interpolated arguments
+    ///
+    /// Arguments are found in the message itself: every `{ $name }`
+    /// placeable, or function call on an argument such as
+    /// `{ NUMBER($count) }`, becomes an `Arg` segment named after the
+    /// variable, whatever its value. Selectors contribute the segments of the
+    /// variant they pick; terms, message references, and literals are part of
+    /// the surrounding text.
+    ///
+    /// # Return
+    /// Returns the segments of the translated string, in order
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// use i18n::Segment;
+    ///
+    /// let segments = i18n::new("greeting")
+    ///     .set_args("name", "Bob")
+    ///     .build_segments();
+    /// assert_eq!(segments, [
+    ///     Segment::Text("Hello, ".to_string()),
+    ///     Segment::Arg { name: "name".to_string(), value: "Bob".to_string() },
+    ///     Segment::Text("!".to_string()),
+    /// ]);
+    /// ```
+    pub fn build_segments(&self) -> Vec<Segment> {
+        let locale = self.locale.clone().unwrap_or_else(locale);
+        let key = self.message_key();
+        let i18n = self.i18n();
+
+        self.with_args(|args| {
+            let alias = i18n.alias(&locale);
+            let found = {
+                let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+                let chain = i18n.chain(&catalog, &alias);
+
+                chain.iter().enumerate().find_map(|(i, l)| {
+                    let mut errors = Vec::new();
+                    let segments = catalog.convert_single(l, &key, |bundle, pattern| {
+                        let mut segments = Vec::new();
+                        walk(bundle, &pattern.elements, args, &mut errors, &mut segments);
+                        segments
+                    })?;
+
+                    Some((segments, errors, i == 0 && l.matches(&alias, true, true)))
+                })
+            };
+
+            match found {
+                // Lookup panics with the formatting errors in strict mode
+                Some((_, errors, _)) if !errors.is_empty() && i18n.is_strict() => {
+                    vec![Segment::Text(i18n.lookup(&locale, &key, args, Some(false)))]
+                }
+                Some((segments, _, direct)) => {
+                    if !direct {
+                        missing::notify(&alias, &key);
+                    }
+
+                    segments
+                }
+                None => vec![Segment::Text(i18n.lookup(&locale, &key, args, Some(false)))],
+            }
+        })
+    }
+}
+
+/// Appends the segments of `elements`, following the variant each selector picks
+fn walk(
+    bundle: &Bundle,
+    elements: &[PatternElement<&str>],
+    args: Option<&Args>,
+    errors: &mut Vec<FluentError>,
+    segments: &mut Vec<Segment>,
+) {
+    for element in elements {
+        match element {
+            PatternElement::TextElement { value } => text(segments, value),
+            PatternElement::Placeable { expression: Expression::Select { selector, variants } } => {
+                if let Some(variant) = select(bundle, selector, variants, args, errors) {
+                    walk(bundle, &variant.value.elements, args, errors, segments);
+                }
+            }
+            PatternElement::Placeable { expression: Expression::Inline(expression) } => {
+                let value = format(bundle, element.clone(), args, errors);
+
+                match argument(expression) {
+                    Some(name) => segments.push(Segment::Arg { name: name.to_string(), value }),
+                    None => text(segments, &value),
+                }
+            }
+        }
+    }
+}
+
+/// Appends literal text, merging it with a preceding text segment
+fn text(segments: &mut Vec<Segment>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    match segments.last_mut() {
+        Some(Segment::Text(last)) => last.push_str(text),
+        _ => segments.push(Segment::Text(text.to_string())),
+    }
+}
+
+/// Returns the variable an inline expression interpolates, directly or as the first argument of a function
+fn argument<'s>(expression: &InlineExpression<&'s str>) -> Option<&'s str> {
+    match expression {
+        InlineExpression::VariableReference { id } => Some(id.name),
+        InlineExpression::FunctionReference { arguments, .. } => match arguments.positional.first() {
+            Some(InlineExpression::VariableReference { id }) => Some(id.name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the variant the bundle picks for `selector`
+///
+/// The select expression is formatted with every variant replaced by its
+/// index, so plural rules and number matching are Fluent's own.
+fn select<'p>(
+    bundle: &Bundle,
+    selector: &InlineExpression<&str>,
+    variants: &'p [Variant<&str>],
+    args: Option<&Args>,
+    errors: &mut Vec<FluentError>,
+) -> Option<&'p Variant<&'p str>> {
+    let indices: Vec<String> = (0..variants.len()).map(|i| i.to_string()).collect();
+    let probe = Expression::Select {
+        selector: selector.clone(),
+        variants: variants
+            .iter()
+            .zip(&indices)
+            .map(|(variant, index)| Variant {
+                key: variant.key.clone(),
+                value: Pattern { elements: vec![PatternElement::TextElement { value: index.as_str() }] },
+                default: variant.default,
+            })
+            .collect(),
+    };
+
+    let index = format(bundle, PatternElement::Placeable { expression: probe }, args, errors);
+    variants.get(index.parse::<usize>().ok()?)
+}
+
+/// Formats a single pattern element
+fn format(bundle: &Bundle, element: PatternElement<&str>, args: Option<&Args>, errors: &mut Vec<FluentError>) -> String {
+    let pattern = Pattern { elements: vec![element] };
+
+    bundle.format_pattern(&pattern, args, errors).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    const FTL: &str = "\
+-brand = Acme
+posted = { $user } posted at { $time }
+made = Made by { -brand } for { $name }
+emails = { $count ->
+    [one] One email from { $sender }
+   *[other] { $count } emails from { $sender }
+}
+";
+
+    fn text(text: &str) -> Segment {
+        Segment::Text(text.to_string())
+    }
+
+    fn arg(name: &str, value: &str) -> Segment {
+        Segment::Arg { name: name.to_string(), value: value.to_string() }
+    }
+
+    #[test]
+    fn arguments_with_equal_values_keep_their_names() {
+        let _guard = fixtures(&[("en-US", FTL)]);
+
+        let segments = new("posted").set_args("user", "x").set_args("time", "x").build_segments();
+        assert_eq!(segments, [arg("user", "x"), text(" posted at "), arg("time", "x")]);
+    }
+
+    #[test]
+    fn terms_matching_an_argument_stay_text() {
+        let _guard = fixtures(&[("en-US", FTL)]);
+
+        let segments = new("made").set_args("name", "Acme").build_segments();
+        assert_eq!(segments, [text("Made by Acme for "), arg("name", "Acme")]);
+    }
+
+    #[test]
+    fn selectors_contribute_their_variant() {
+        let _guard = fixtures(&[("en-US", FTL)]);
+
+        let one = new("emails").set_args("count", 1).set_args("sender", "Bob").build_segments();
+        assert_eq!(one, [text("One email from "), arg("sender", "Bob")]);
+
+        let other = new("emails").set_args("count", 3).set_args("sender", "Bob").build_segments();
+        assert_eq!(other, [arg("count", "3"), text(" emails from "), arg("sender", "Bob")]);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{I18nBuilder, LanguageIdentifier, LocalizedMessage, locale_guard};
+use fluent_templates::fluent_bundle::FluentValue;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+impl I18nBuilder {
+    /// Sets the parameters for the translation from a serializable struct
+    ///
+    /// Each field becomes an argument named after its serialized name, so
+    /// `#[serde(rename = "...")]` controls the FTL variable. Numbers stay
+    /// numeric for plural selectors, booleans become "true" or "false", and
+    /// `None`, nested structs, and sequences are skipped. Values that do not
+    /// serialize to a map leave the arguments unchanged.
+    ///
+    /// # Parameters
+    /// * `value` - The struct or map to take the parameters from
+    ///
+    /// # Return
+    /// Returns self for method chaining
+    ///
+    /// # Examples
+    /// ```
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Inbox {
+    ///     count: u32,
+    /// }
+    ///
+    /// let message = i18n::new("emails")
+    ///     .args_from(&Inbox { count: 2 })
+    ///     .build();
+    /// assert_eq!(message, "You have 2 emails");
+    /// ```
+    pub fn args_from<T: Serialize + ?Sized>(mut self, value: &T) -> Self {
+        let Ok(Value::Object(fields)) = serde_json::to_value(value) else {
+            return self;
+        };
+
+        for (key, value) in fields {
+            let value = match value {
+                Value::String(value) => FluentValue::from(value),
+                Value::Number(number) => match number.as_f64() {
+                    Some(number) => FluentValue::from(number),
+                    None => continue,
+                },
+                Value::Bool(value) => FluentValue::from(value.to_string()),
+                Value::Null | Value::Array(_) | Value::Object(_) => continue,
+            };
+
+            self.args.set(key, value);
+        }
+
+        self
+    }
+}
+
+/// Serializes as the message text, rendered in the active locale
+///
+/// Wrap the value in `in_locale` to choose the locale for one serialization.
+impl Serialize for LocalizedMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A value whose `LocalizedMessage` fields serialize in a given locale
+///
+/// Created by `in_locale`.
+#[derive(Debug)]
+pub struct InLocale<'a, T: ?Sized> {
+    locale: Option<LanguageIdentifier>,
+    value: &'a T,
+}
+
+impl<T: Serialize + ?Sized> Serialize for InLocale<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let _guard = self.locale.as_ref().map(locale_guard);
+
+        self.value.serialize(serializer)
+    }
+}
+
+/// Serializes a value with its localized messages rendered in `locale`
+///
+/// The locale is active on the current thread while the value is serialized,
+/// so API responses can render in the response locale regardless of the
+/// global one. An invalid language identifier is ignored and the active
+/// locale is used instead.
+///
+/// # Parameters
+/// * `locale` - The language identifier to render in (e.g., "es-MX")
+/// * `value` - The value to serialize
+///
+/// # Return
+/// Returns a wrapper serializing `value` in `locale`
+///
+/// # Examples
+/// ```
+///
+/// use i18n::LocalizedMessage;
+///
+/// #[derive(serde::Serialize)]
+/// struct Response {
+///     message: LocalizedMessage,
+/// }
+///
+/// let response = Response { message: LocalizedMessage::new("hello") };
+/// let json = serde_json::to_string(&i18n::in_locale("es-MX", &response)).unwrap();
+/// assert_eq!(json, r#"{"message":"Hola"}"#);
+/// ```
+pub fn in_locale<T, U>(locale: T, value: &U) -> InLocale<'_, U>
+where
+    T: ToString,
+    U: Serialize + ?Sized,
+{
+    InLocale {
+        locale: locale.to_string().parse().ok(),
+        value,
+    }
+}
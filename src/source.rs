@@ -0,0 +1,258 @@
+use crate::I18nError;
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use unic_langid::LanguageIdentifier;
+
+/// Extensions of the translation files read by `DirSource`
+pub(crate) const EXTENSIONS: &[&str] = &[
+    "ftl",
+    #[cfg(feature = "json")]
+    "json",
+    #[cfg(feature = "yaml")]
+    "yaml",
+    #[cfg(feature = "yaml")]
+    "yml",
+];
+
+/// Backend providing the FTL sources of each locale
+///
+/// Implement it to load translations from databases, HTTP services, or
+/// key-value stores while keeping the same `get`/builder API. Sources are read
+/// when translations are initialized and on every reload.
+///
+/// # Methods
+/// * `locales` - Lists the locales the source can provide
+/// * `messages` - Returns the FTL sources of one locale; messages in later
+///   sources shadow earlier ones with the same key
+/// * `files` - Returns the FTL sources of one locale along with the file each
+///   was read from, used to locate syntax errors; defaults to naming them
+///   after the locale
+///
+/// # Examples
+/// ```
+/// use i18n::{I18nConfig, I18nError, LanguageIdentifier, LocaleSource};
+/// use std::sync::Arc;
+///
+/// #[derive(Debug)]
+/// struct InMemory;
+///
+/// impl LocaleSource for InMemory {
+///     fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+///         Ok(vec!["en-US".parse().unwrap()])
+///     }
+///
+///     fn messages(&self, _locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+///         Ok(vec!["hello = Hi there".to_string()])
+///     }
+/// }
+///
+/// i18n::init_with(I18nConfig {
+///     source: Some(Arc::new(InMemory)),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(i18n::get("hello"), "Hi there");
+/// ```
+pub trait LocaleSource: Debug + Send + Sync {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError>;
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError>;
+
+    fn files(&self, locale: &LanguageIdentifier) -> Result<Vec<(PathBuf, String)>, I18nError> {
+        let files = self.messages(locale)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, ftl)| (PathBuf::from(format!("<{locale} source {}>", i + 1)), ftl))
+            .collect();
+
+        Ok(files)
+    }
+}
+
+/// Reads FTL files from one sub-directory per locale
+///
+/// This is the default source, built from `I18nConfig::dir`. With the `json`
+/// and `yaml` features, `.json`, `.yaml`, and `.yml` files are read alongside
+/// the FTL files and converted with `json_to_ftl` and `yaml_to_ftl`.
+#[derive(Debug, Clone)]
+pub struct DirSource {
+    dir: PathBuf,
+}
+
+impl DirSource {
+    /// Creates a source reading from `dir`
+    ///
+    /// # Parameters
+    /// * `dir` - Directory containing one sub-directory of FTL files per locale
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { dir: dir.into() }
+    }
+
+    /// Lists the locale sub-directories along with their parsed identifiers
+    fn entries(&self) -> Result<Vec<(LanguageIdentifier, PathBuf)>, I18nError> {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(&self.dir).map_err(|e| loader_error(&self.dir, e))? {
+            let entry = entry.map_err(|e| loader_error(&self.dir, e))?;
+            if !entry.path().is_dir() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let locale = name
+                .parse()
+                .map_err(|_| I18nError::InvalidLocale(name.clone()))?;
+
+            entries.push((locale, entry.path()));
+        }
+
+        Ok(entries)
+    }
+
+    /// Lists the FTL files of `locale`, sorted
+    pub(crate) fn paths(&self, locale: &LanguageIdentifier) -> Result<Vec<PathBuf>, I18nError> {
+        let mut paths = Vec::new();
+
+        for (_, dir) in self.entries()?.into_iter().filter(|(l, _)| l == locale) {
+            collect_files(&dir, EXTENSIONS, &mut paths)?;
+        }
+
+        paths.sort();
+
+        Ok(paths)
+    }
+}
+
+impl LocaleSource for DirSource {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        Ok(self.entries()?.into_iter().map(|(locale, _)| locale).collect())
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        self.paths(locale)?.iter().map(|path| read(path, locale)).collect()
+    }
+
+    fn files(&self, locale: &LanguageIdentifier) -> Result<Vec<(PathBuf, String)>, I18nError> {
+        self.paths(locale)?
+            .into_iter()
+            .map(|path| Ok((path.clone(), read(&path, locale)?)))
+            .collect()
+    }
+}
+
+/// Source without any locale, used by default in browsers, which have no filesystem
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug)]
+pub(crate) struct NoSource;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl LocaleSource for NoSource {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        Ok(Vec::new())
+    }
+
+    fn messages(&self, _locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Reads a translation file of `locale` as FTL, converting JSON and YAML files
+#[cfg_attr(not(any(feature = "json", feature = "yaml")), allow(unused_variables))]
+fn read(path: &Path, locale: &LanguageIdentifier) -> Result<String, I18nError> {
+    let source = fs::read_to_string(path).map_err(|e| loader_error(path, e))?;
+
+    #[cfg(any(feature = "json", feature = "yaml"))]
+    if let Some(converted) = crate::structured::convert(path, &source, locale) {
+        return converted;
+    }
+
+    Ok(source)
+}
+
+/// Collects every file below `dir` with one of `extensions`, recursively
+pub(crate) fn collect_files(dir: &Path, extensions: &[&str], paths: &mut Vec<PathBuf>) -> Result<(), I18nError> {
+    for entry in fs::read_dir(dir).map_err(|e| loader_error(dir, e))? {
+        let path = entry.map_err(|e| loader_error(dir, e))?.path();
+
+        if path.is_dir() {
+            collect_files(&path, extensions, paths)?;
+        } else if path.extension().is_some_and(|ext| extensions.iter().any(|e| ext == *e)) {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the FTL files `sources` provide for `locale`, in order
+///
+/// Sources without `locale` contribute nothing.
+pub(crate) fn layered(sources: &[Arc<dyn LocaleSource>], locale: &LanguageIdentifier) -> Result<Vec<(PathBuf, String)>, I18nError> {
+    let mut files = Vec::new();
+
+    for source in sources {
+        if source.locales()?.contains(locale) {
+            files.extend(source.files(locale)?);
+        }
+    }
+
+    Ok(files)
+}
+
+pub(crate) fn loader_error(path: &Path, error: std::io::Error) -> I18nError {
+    I18nError::Loader(format!("{}: {error}", path.display()))
+}
+
+/// FTL sources compiled into the binary by `embed_locales!`
+///
+/// # Examples
+/// ```
+///
+/// static LOCALES: i18n::EmbeddedLocales = i18n::embed_locales!("assets/locales");
+///
+/// i18n::init_with(i18n::I18nConfig {
+///     source: Some(std::sync::Arc::new(LOCALES)),
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(i18n::get("hello"), "Hello");  // Read from the binary
+/// ```
+#[cfg(feature = "embedded")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddedLocales {
+    locales: &'static [(&'static str, &'static [&'static str])],
+}
+
+#[cfg(feature = "embedded")]
+impl EmbeddedLocales {
+    /// Wraps FTL sources grouped by locale name; used by `embed_locales!`
+    #[doc(hidden)]
+    pub const fn new(locales: &'static [(&'static str, &'static [&'static str])]) -> Self {
+        Self { locales }
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl LocaleSource for EmbeddedLocales {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        self.locales
+            .iter()
+            .map(|(name, _)| name.parse().map_err(|_| I18nError::InvalidLocale(name.to_string())))
+            .collect()
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        let sources = self.locales
+            .iter()
+            .filter(|(name, _)| name.parse().ok().as_ref() == Some(locale))
+            .flat_map(|(_, sources)| sources.iter().map(|source| source.to_string()))
+            .collect();
+
+        Ok(sources)
+    }
+}
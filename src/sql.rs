@@ -0,0 +1,180 @@
+use crate::ftl::{self, Messages};
+use crate::keys;
+use crate::{I18nError, LanguageIdentifier, LocaleSource};
+use sqlx::{Database, Executor, FromRow, IntoArguments, Pool};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+const ERROR_LOCK: &str = "Translation rows lock poisoned";
+const QUERY: &str = "SELECT locale, key, value FROM translations";
+
+/// FTL assembled from the rows of each locale
+type Rows = BTreeMap<LanguageIdentifier, String>;
+
+/// Source merging messages from a `translations(locale, key, value)` table over
+/// another source
+///
+/// Rows are fetched when the source is created and whenever `refresh` is awaited;
+/// `LocaleSource` reads only the fetched snapshot. Database messages shadow the
+/// base source's messages with the same key, so product teams can edit copy in
+/// an admin UI without redeploying. A row's key is a message ID or
+/// `message.attribute`, and its value is an FTL pattern.
+///
+/// # Examples
+/// ```ignore
+/// use i18n::{DirSource, I18nConfig, SqlxSource};
+/// use std::sync::Arc;
+///
+/// let pool = sqlx::PgPool::connect("postgres://localhost/app").await?;
+/// let source = Arc::new(SqlxSource::new(pool, DirSource::new("./assets/locales/")).await?);
+///
+/// i18n::init_with(I18nConfig {
+///     source: Some(source.clone()),
+///     ..Default::default()
+/// });
+///
+/// // After copy was edited in the admin UI
+/// source.refresh().await?;
+/// i18n::reload()?;
+/// ```
+pub struct SqlxSource<DB: Database> {
+    pool: Pool<DB>,
+    base: Box<dyn LocaleSource>,
+    rows: RwLock<Rows>,
+}
+
+impl<DB> SqlxSource<DB>
+where
+    DB: Database,
+    for<'c> &'c Pool<DB>: Executor<'c, Database = DB>,
+    DB::Arguments: IntoArguments<DB>,
+    for<'r> (String, String, String): FromRow<'r, DB::Row>,
+{
+    /// Creates the source and fetches the current translation rows
+    ///
+    /// # Parameters
+    /// * `pool` - Connection pool of the database holding the `translations` table
+    /// * `base` - Source whose messages the database rows are merged over
+    ///
+    /// # Return
+    /// Returns an error if the rows cannot be fetched
+    pub async fn new<S>(pool: Pool<DB>, base: S) -> Result<Self, I18nError>
+    where
+        S: LocaleSource + 'static,
+    {
+        let source = Self {
+            pool,
+            base: Box::new(base),
+            rows: RwLock::new(Rows::new()),
+        };
+
+        source.refresh().await?;
+        Ok(source)
+    }
+
+    /// Fetches the translation rows again
+    ///
+    /// Call `i18n::reload` afterwards to apply them. Rows with a malformed
+    /// locale, a key that is not a message ID or `message.attribute`, or a
+    /// value that is not valid FTL are skipped, so one bad edit cannot break
+    /// the other messages.
+    ///
+    /// # Return
+    /// Returns an error if the rows cannot be fetched
+    pub async fn refresh(&self) -> Result<(), I18nError> {
+        let fetched: Vec<(String, String, String)> = sqlx::query_as(QUERY)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| I18nError::Loader(e.to_string()))?;
+
+        *self.rows.write().expect(ERROR_LOCK) = rows(fetched);
+        Ok(())
+    }
+}
+
+impl<DB: Database> fmt::Debug for SqlxSource<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqlxSource")
+            .field("base", &self.base)
+            .field("rows", &self.rows)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<DB: Database> LocaleSource for SqlxSource<DB> {
+    fn locales(&self) -> Result<Vec<LanguageIdentifier>, I18nError> {
+        let mut locales = self.base.locales()?;
+
+        for locale in self.rows.read().expect(ERROR_LOCK).keys() {
+            if !locales.contains(locale) {
+                locales.push(locale.clone());
+            }
+        }
+
+        Ok(locales)
+    }
+
+    fn messages(&self, locale: &LanguageIdentifier) -> Result<Vec<String>, I18nError> {
+        let mut messages = self.base.messages(locale)?;
+
+        if let Some(rows) = self.rows.read().expect(ERROR_LOCK).get(locale) {
+            messages.push(rows.clone());
+        }
+
+        Ok(messages)
+    }
+
+    fn files(&self, locale: &LanguageIdentifier) -> Result<Vec<(PathBuf, String)>, I18nError> {
+        let mut files = self.base.files(locale)?;
+
+        if let Some(rows) = self.rows.read().expect(ERROR_LOCK).get(locale) {
+            files.push((PathBuf::from(format!("<{locale} translations table>")), rows.clone()));
+        }
+
+        Ok(files)
+    }
+}
+
+/// Assembles the FTL of each locale from `(locale, key, value)` rows, skipping invalid ones
+fn rows(fetched: Vec<(String, String, String)>) -> Rows {
+    let mut messages: BTreeMap<LanguageIdentifier, Messages> = BTreeMap::new();
+
+    for (locale, key, value) in fetched {
+        if let Ok(locale) = locale.parse()
+            && is_key(&key)
+            && ftl::check(&key, &value).is_ok()
+        {
+            messages.entry(locale).or_default().insert(&key, value);
+        }
+    }
+
+    messages.into_iter().map(|(locale, messages)| (locale, messages.finish())).collect()
+}
+
+/// Returns whether a row key is a message ID or `message.attribute`
+fn is_key(key: &str) -> bool {
+    key.split('.').count() <= 2 && key.split('.').all(keys::is_identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_rows_are_skipped() {
+        let row = |locale: &str, key: &str, value: &str| (locale.to_string(), key.to_string(), value.to_string());
+        let rows = rows(vec![
+            row("es-MX", "hello", "Hola"),
+            row("es-MX", "login-button.title", "Entrar\ncon tu cuenta"),
+            row("es-MX", "broken", "Hola, { $name"),
+            row("es-MX", "not a key", "Hola"),
+            row("es-MX", "a.b.c", "Hola"),
+            row("not a locale!", "hello", "Hola"),
+        ]);
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[&"es-MX".parse().unwrap()], "hello = Hola\nlogin-button =\n    .title = Entrar\n        con tu cuenta\n");
+    }
+}
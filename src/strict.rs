@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether strict mode was switched on at runtime
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Switches strict mode on or off for every lookup
+///
+/// In strict mode, `get`, `args`, `build`, and `get_many` panic with a
+/// descriptive message when a key is missing from the locale and its
+/// fallback chain, no installed locale matches, or a message fails to format.
+/// Meant for integration tests, which then fail on translation regressions.
+/// `get_opt`, `try_get`, and the other non-panicking variants are not
+/// affected. Setting `I18nConfig::strict` enables it for one catalog.
+///
+/// # Parameters
+/// * `enabled` - Whether lookups should panic on translation problems
+///
+/// # Examples
+/// ```should_panic
+///
+/// i18n::strict(true);
+///
+/// assert_eq!(i18n::get("hello"), "Hello");
+/// let missing = i18n::get("missing");  // Panics with "Missing translation: missing"
+/// ```
+pub fn strict(enabled: bool) {
+    STRICT.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether strict mode was switched on with `strict`
+pub(crate) fn enabled() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
@@ -0,0 +1,213 @@
+use crate::ftl::Pattern;
+use crate::keys::{self, Message};
+use crate::{I18nError, LanguageIdentifier};
+use serde_json::Value;
+use std::path::Path;
+
+/// Name of the argument selecting the plural form
+const COUNT: &str = "count";
+
+/// Converts a JSON translation file to FTL
+///
+/// Nested objects are flattened, joining their keys with `-` (`{"nav":
+/// {"home": "Home"}}` becomes `nav-home`), and keys that are not valid Fluent
+/// identifiers are turned into one, so `"nav.home"` also becomes `nav-home`.
+/// The `{name}`, i18next `{{name}}`, Rails `%{name}`, and `export_json`
+/// `{$name}` placeholders become `{ $name }`. Plurals are written either as an
+/// object keyed by CLDR category (`{"one": ..., "other": ...}`) or as i18next
+/// `key_one`/`key_other` siblings, and select their form on `$count`. Numbers
+/// and booleans are kept as text, while `null` and arrays are ignored. Files
+/// loaded from a locale's directory also skip a single top-level key naming
+/// that locale, as in Rails files.
+///
+/// # Parameters
+/// * `json` - The contents of a `.json` file
+///
+/// # Return
+/// Returns the FTL source, or an error if `json` is malformed
+///
+/// # Examples
+/// ```
+///
+/// let json = r#"{
+///     "greeting": "¡Hola, {{name}}!",
+///     "inbox": { "emails_one": "Un correo", "emails_other": "{{count}} correos" }
+/// }"#;
+///
+/// let ftl = i18n::json_to_ftl(json).unwrap();
+/// assert_eq!(ftl, "\
+/// greeting = ¡Hola, { $name }!
+/// inbox-emails = { $count ->
+///     [one] Un correo
+///    *[other] { $count } correos
+/// }
+/// ");
+/// ```
+#[cfg(feature = "json")]
+pub fn json_to_ftl(json: &str) -> Result<String, I18nError> {
+    let value = serde_json::from_str(json).map_err(|e| I18nError::Loader(e.to_string()))?;
+
+    Ok(ftl(&value, None))
+}
+
+/// Converts a YAML translation file to FTL
+///
+/// Keys, placeholders, and plurals are mapped like `json_to_ftl` does, so
+/// Rails locale files such as `config/locales/es.yml` can be placed in a
+/// locale's directory as they are.
+///
+/// # Parameters
+/// * `yaml` - The contents of a `.yaml` or `.yml` file
+///
+/// # Return
+/// Returns the FTL source, or an error if `yaml` is malformed
+///
+/// # Examples
+/// ```
+///
+/// let yaml = "
+/// greeting: '¡Hola, %{name}!'
+/// emails:
+///   one: Un correo
+///   other: '%{count} correos'
+/// ";
+///
+/// let ftl = i18n::yaml_to_ftl(yaml).unwrap();
+/// assert_eq!(ftl, "\
+/// emails = { $count ->
+///     [one] Un correo
+///    *[other] { $count } correos
+/// }
+/// greeting = ¡Hola, { $name }!
+/// ");
+/// ```
+#[cfg(feature = "yaml")]
+pub fn yaml_to_ftl(yaml: &str) -> Result<String, I18nError> {
+    let value = serde_yaml::from_str(yaml).map_err(|e| I18nError::Loader(e.to_string()))?;
+
+    Ok(ftl(&value, None))
+}
+
+/// Converts a JSON or YAML translation file of `locale` to FTL
+///
+/// Returns `None` for files of other formats.
+pub(crate) fn convert(path: &Path, source: &str, locale: &LanguageIdentifier) -> Option<Result<String, I18nError>> {
+    let value: Result<Value, String> = match path.extension()?.to_str()? {
+        #[cfg(feature = "json")]
+        "json" => serde_json::from_str(source).map_err(|e| e.to_string()),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => serde_yaml::from_str(source).map_err(|e| e.to_string()),
+        _ => return None,
+    };
+
+    Some(
+        value
+            .map(|value| ftl(&value, Some(locale)))
+            .map_err(|reason| I18nError::Loader(format!("{}: {reason}", path.display()))),
+    )
+}
+
+/// Writes the messages of a translation tree as FTL
+fn ftl(value: &Value, locale: Option<&LanguageIdentifier>) -> String {
+    let mut ftl = String::new();
+
+    for (id, message) in keys::messages(value, locale) {
+        match message {
+            Message::Text(text) => ftl.push_str(&format!("{id} = {}\n", pattern(&text, "    "))),
+            Message::Plural(forms) => {
+                ftl.push_str(&format!("{id} = {{ ${COUNT} ->\n"));
+                for (category, text) in forms {
+                    let marker = if category == "other" { "   *" } else { "    " };
+                    ftl.push_str(&format!("{marker}[{category}] {}\n", pattern(&text, "        ")));
+                }
+                ftl.push_str("}\n");
+            }
+        }
+    }
+
+    ftl
+}
+
+/// Converts a translation to an FTL pattern, translating its placeholders
+fn pattern(text: &str, indent: &'static str) -> String {
+    let mut pattern = Pattern::new(indent);
+    let mut rest = text;
+
+    while let Some(start) = rest.find(['{', '%']) {
+        let (open, close) = match &rest[start..] {
+            s if s.starts_with("{{") => ("{{", "}}"),
+            s if s.starts_with("%{") => ("%{", "}"),
+            s if s.starts_with('{') => ("{", "}"),
+            _ => ("%", ""),
+        };
+
+        let body = &rest[start + open.len()..];
+        let variable = (!close.is_empty())
+            .then(|| body.find(close))
+            .flatten()
+            .map(|end| (end, body[..end].split(',').next().unwrap_or_default().trim()))
+            .map(|(end, name)| (end, name.strip_prefix('$').unwrap_or(name)))
+            .filter(|(_, name)| keys::is_identifier(name));
+
+        pattern.text(&rest[..start]);
+        match variable {
+            Some((end, name)) => {
+                pattern.variable(name);
+                rest = &body[end + close.len()..];
+            }
+            None => {
+                pattern.text(&rest[start..start + 1]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+
+    pattern.text(rest);
+    pattern.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_keys_are_joined_into_identifiers() {
+        let value = json!({ "nav": { "home": "Home", "about.us": "About" }, "1st": "First", "count": 3, "flag": true, "none": null, "list": [1] });
+
+        assert_eq!(ftl(&value, None), "msg-1st = First\ncount = 3\nflag = true\nnav-about-us = About\nnav-home = Home\n");
+    }
+
+    #[test]
+    fn placeholders_become_variables() {
+        assert_eq!(pattern("{{name}}, {name}, %{name}, {$name} and {amount, number}", "    "), "{ $name }, { $name }, { $name }, { $name } and { $amount }");
+        assert_eq!(pattern("100% {not valid} {{", "    "), "100% {\"{\"}not valid{\"}\"} {\"{\"}{\"{\"}");
+    }
+
+    #[test]
+    fn plurals_need_the_other_form() {
+        let value = json!({ "apples": { "one": "An apple", "few": "Some apples" }, "pears_one": "A pear", "pears_other": "{{count}} pears" });
+
+        assert_eq!(ftl(&value, None), "apples-few = Some apples\napples-one = An apple\npears = { $count ->\n    [one] A pear\n   *[other] { $count } pears\n}\n");
+    }
+
+    #[test]
+    fn only_the_loaded_locale_root_is_skipped() {
+        let es: LanguageIdentifier = "es-MX".parse().unwrap();
+
+        assert_eq!(ftl(&json!({ "es-MX": { "hello": "Hola" } }), Some(&es)), "hello = Hola\n");
+        assert_eq!(ftl(&json!({ "es-MX": { "hello": "Hola" } }), None), "es-MX-hello = Hola\n");
+        assert_eq!(ftl(&json!({ "fr": { "hello": "Salut" } }), Some(&es)), "fr-hello = Salut\n");
+        assert_eq!(ftl(&json!({ "nav": { "home": "Inicio" } }), Some(&es)), "nav-home = Inicio\n");
+        assert_eq!(ftl(&json!({ "es-MX": { "hello": "Hola" }, "fr": { "hello": "Salut" } }), Some(&es)), "es-MX-hello = Hola\nfr-hello = Salut\n");
+    }
+
+    #[test]
+    fn malformed_files_are_reported_with_their_path() {
+        let es = "es".parse().unwrap();
+        let error = convert(Path::new("locales/es.json"), "{", &es).unwrap().unwrap_err();
+
+        assert!(error.to_string().contains("locales/es.json"));
+        assert!(convert(Path::new("locales/es.ftl"), "", &es).is_none());
+    }
+}
@@ -0,0 +1,37 @@
+/// Returns the candidate closest to `key`, if it looks like a typo of it
+///
+/// Candidates further than a third of the key's length (at least one edit)
+/// are ignored, so unrelated keys are never suggested. Ties go to the first
+/// candidate.
+pub(crate) fn nearest<'a, I>(key: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let key: Vec<char> = key.chars().collect();
+    let limit = (key.len() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (distance(&key, candidate), candidate))
+        .filter(|(distance, _)| (1..=limit).contains(distance))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, in characters
+fn distance(a: &[char], b: &str) -> usize {
+    let mut row: Vec<usize> = (0..=a.len()).collect();
+
+    for (i, cb) in b.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, ca) in a.iter().enumerate() {
+            let substitution = diagonal + usize::from(*ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[a.len()]
+}
@@ -0,0 +1,124 @@
+use crate::{I18nConfig, I18nError, LanguageIdentifier};
+use std::future::Future;
+use std::panic;
+use tokio::task::futures::TaskLocalFuture;
+use tokio::task::{self, JoinError};
+
+tokio::task_local! {
+    static TASK_LOCALE: LanguageIdentifier;
+}
+
+/// Runs a future with its own active locale
+///
+/// Every lookup made while `future` is polled (`get`, `t!`, the builder without
+/// an explicit `.locale()`) uses `locale`, so concurrent requests in an async
+/// server can each have their own language while business logic keeps using
+/// the zero-argument API. The global locale is untouched. Work spawned onto
+/// other tasks does not inherit the scope.
+///
+/// # Parameters
+/// * `locale` - The locale active while `future` runs
+/// * `future` - The future to run
+///
+/// # Return
+/// Returns the output of `future`
+///
+/// # Examples
+/// ```
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let locale = "es-MX".parse().unwrap();
+///
+/// let hello = i18n::with_locale(locale, async {
+///     i18n::get("hello")  // Returns "Hola"
+/// }).await;
+/// # });
+/// ```
+pub async fn with_locale<F>(locale: LanguageIdentifier, future: F) -> F::Output
+where
+    F: Future,
+{
+    scope(locale, future).await
+}
+
+/// Wraps a future so `locale` is active while it is polled, as a nameable type
+#[cfg_attr(not(feature = "tower"), allow(dead_code))]
+pub(crate) fn scope<F>(locale: LanguageIdentifier, future: F) -> TaskLocalFuture<LanguageIdentifier, F>
+where
+    F: Future,
+{
+    TASK_LOCALE.scope(locale, future)
+}
+
+/// Runs `f` with `locale` active, for synchronous work done outside a future
+#[cfg_attr(not(feature = "tower"), allow(dead_code))]
+pub(crate) fn sync_scope<F, R>(locale: LanguageIdentifier, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    TASK_LOCALE.sync_scope(locale, f)
+}
+
+/// Initializes translations without blocking the async runtime
+///
+/// Scanning the locale directory, reading and parsing the files, and fetching
+/// remote sources run on tokio's blocking thread pool, so services can
+/// initialize during startup without stalling other tasks. Behaves like
+/// `try_init` otherwise.
+///
+/// # Parameters
+/// * `config` - The settings used to build the loader
+///
+/// # Return
+/// Returns an error if the locale is malformed, the locale directory cannot be
+/// loaded, or translations were already initialized
+///
+/// # Examples
+/// ```
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// i18n::init_async(i18n::I18nConfig::default()).await.unwrap();
+///
+/// assert_eq!(i18n::get("hello"), "Hello");
+/// # });
+/// ```
+pub async fn init_async(config: I18nConfig) -> Result<(), I18nError> {
+    task::spawn_blocking(move || crate::try_init(config))
+        .await
+        .unwrap_or_else(|e| Err(join_error(e)))
+}
+
+/// Rebuilds the translations without blocking the async runtime
+///
+/// Like `reload`, the previous translations stay in place on failure.
+///
+/// # Return
+/// Returns an error if the locale directory cannot be loaded
+///
+/// # Examples
+/// ```
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// if let Err(e) = i18n::reload_async().await {
+///     eprintln!("Keeping previous translations: {e}");
+/// }
+/// # });
+/// ```
+pub async fn reload_async() -> Result<(), I18nError> {
+    task::spawn_blocking(crate::reload)
+        .await
+        .unwrap_or_else(|e| Err(join_error(e)))
+}
+
+/// Resumes a panic of the blocking task, or reports its cancellation
+fn join_error(error: JoinError) -> I18nError {
+    match error.try_into_panic() {
+        Ok(payload) => panic::resume_unwind(payload),
+        Err(error) => I18nError::Loader(error.to_string()),
+    }
+}
+
+/// Returns the locale of the current task's scope, if any
+pub(crate) fn current() -> Option<LanguageIdentifier> {
+    TASK_LOCALE.try_with(Clone::clone).ok()
+}
@@ -0,0 +1,70 @@
+//! Tera integration exposing translations to templates
+//!
+//! ```
+//! let mut tera = tera::Tera::default();
+//! i18n::tera::register(&mut tera);
+//!
+//! tera.add_raw_template("hello", r#"{{ t(key="greeting", name="Bob") }}"#).unwrap();
+//! let html = tera.render("hello", &tera::Context::new()).unwrap();  // Returns "Hello, Bob!"
+//! ```
+
+use crate::I18nBuilder;
+use ::tera::{Kwargs, State, Tera, TeraResult, Value};
+use fluent_templates::fluent_bundle::FluentValue;
+
+/// Name of the argument and context variable selecting the locale
+const LOCALE: &str = "locale";
+
+/// Registers the `t` function with a Tera instance
+///
+/// `t(key="...")` translates a key, and every other named argument is passed
+/// to the message (numbers stay numeric for plural selectors). The locale is
+/// taken from a `locale` argument, then a `locale` variable of the render
+/// context, and defaults to the active locale. Attributes are addressed as
+/// `key="message.attribute"`.
+///
+/// # Parameters
+/// * `tera` - The Tera instance to register the function with
+///
+/// # Examples
+/// ```
+///
+/// let mut tera = tera::Tera::default();
+/// i18n::tera::register(&mut tera);
+///
+/// tera.add_raw_template("emails", r#"{{ t(key="emails", count=3, locale="es-MX") }}"#).unwrap();
+/// ```
+pub fn register(tera: &mut Tera) {
+    tera.register_function("t", translate);
+}
+
+fn translate(kwargs: Kwargs, state: &State) -> TeraResult<String> {
+    let key = kwargs.must_get::<&str>("key")?;
+
+    let mut builder = crate::new(key);
+    for (name, value) in kwargs.iter() {
+        match name.as_str() {
+            Some("key") | Some(LOCALE) | None => {}
+            Some(name) => builder = set_arg(builder, name, value),
+        }
+    }
+
+    let locale = match kwargs.get::<String>(LOCALE)? {
+        Some(locale) => Some(locale),
+        None => state.get::<String>(LOCALE)?,
+    };
+
+    if let Some(locale) = locale {
+        builder = builder.locale(locale);
+    }
+
+    Ok(builder.build())
+}
+
+fn set_arg(builder: I18nBuilder, name: &str, value: &Value) -> I18nBuilder {
+    match (value.as_str(), value.as_f64()) {
+        (Some(value), _) => builder.set_arg(name, value.to_string()),
+        (None, Some(number)) if value.is_number() => builder.set_arg(name, FluentValue::from(number)),
+        _ => builder.set_arg(name, value.to_string()),
+    }
+}
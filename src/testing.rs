@@ -0,0 +1,103 @@
+//! Helpers for testing the translation catalog
+//!
+//! ```
+//! let messages = i18n::testing::assert_all_messages_render("en-US", |_, name| match name {
+//!     "count" => 3.into(),
+//!     _ => "Bob".into(),
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(messages["emails"], "You have 3 emails");
+//! ```
+
+use crate::{ERROR_LOCK, LookupError, instance, negotiate, parse_locale};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::BTreeMap;
+
+/// Renders every message of a locale, collecting the ones that fail to format
+///
+/// Each message and attribute is formatted with a value for every
+/// placeholder it uses, so runtime errors such as references to missing
+/// messages or terms, or failing functions, are caught for all keys at once
+/// instead of one request at a time. The rendered messages are returned for
+/// snapshot assertions, and unwrapping a failure lists every broken message.
+///
+/// # Parameters
+/// * `locale` - The language identifier to render (e.g., "es-MX"); the installed locale matching it is used
+/// * `args` - Called with the message ID and placeholder name, returning a representative value
+///
+/// # Return
+/// Returns the rendered text of every message and attribute, keyed by
+/// message ID or `message.attribute`, or a `LookupError::FormattingError` for
+/// every message that failed to render, in key order. A locale that is not
+/// installed is reported as a single `LookupError::MissingLocale`.
+///
+/// # Panics
+/// Panics if `locale` is invalid
+///
+/// # Examples
+/// ```
+///
+/// use i18n::LookupError;
+///
+/// let messages = i18n::testing::assert_all_messages_render("es-MX", |key, _| match key {
+///     "emails" => 1.into(),
+///     _ => "Ana".into(),
+/// })
+/// .unwrap();
+///
+/// assert_eq!(messages["greeting"], "¡Hola, Ana!");
+/// assert_eq!(messages["login-button.title"], "Haz clic para iniciar sesión");
+///
+/// let missing = i18n::testing::assert_all_messages_render("fr-FR", |_, _| "".into());
+/// assert!(matches!(missing.unwrap_err()[..], [LookupError::MissingLocale(_)]));
+/// ```
+pub fn assert_all_messages_render<T, F>(locale: T, args: F) -> Result<BTreeMap<String, String>, Vec<LookupError>>
+where
+    T: ToString,
+    F: Fn(&str, &str) -> FluentValue<'static>,
+{
+    let locale = parse_locale(&locale.to_string()).unwrap_or_else(|e| panic!("{e}"));
+    let i18n = instance();
+    let requested = i18n.alias(&locale);
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let Some(installed) = negotiate::matching(&[requested], catalog.available()).first().copied() else {
+        return Err(vec![LookupError::MissingLocale(locale)]);
+    };
+
+    let mut messages = BTreeMap::new();
+    let mut failures = Vec::new();
+    for (key, rendered) in catalog.render_with(installed, args) {
+        match rendered {
+            Ok(value) => {
+                messages.insert(key, value);
+            }
+            Err(errors) => failures.push(LookupError::FormattingError(key, errors)),
+        }
+    }
+
+    if failures.is_empty() { Ok(messages) } else { Err(failures) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    #[test]
+    fn every_failing_message_is_returned() {
+        let _guard = fixtures(&[("en-US", "ok = Fine\nbroken = { missing }\nbutton = Go\n    .title = { -brand }\n")]);
+
+        let failures = assert_all_messages_render("en-US", |_, _| "x".into()).unwrap_err();
+        let keys: Vec<&str> = failures
+            .iter()
+            .filter_map(|failure| match failure {
+                LookupError::FormattingError(key, _) => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(keys, ["broken", "button.title"]);
+    }
+}
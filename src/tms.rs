@@ -0,0 +1,562 @@
+use crate::catalog::pattern_source;
+use crate::ftl::{self, Messages};
+use crate::keys;
+use crate::source::{collect_files, loader_error};
+use crate::{I18nError, LanguageIdentifier, attr_key, reload};
+use fluent_syntax::ast::Entry;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Method, RequestBuilder};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const CROWDIN_API: &str = "https://api.crowdin.com/api/v2";
+const LOKALISE_API: &str = "https://api.lokalise.com/api2";
+
+/// Page size of listing requests, and batch size of Lokalise key updates
+const PAGE: usize = 500;
+
+/// File Lokalise keys are written to when they do not name one
+const DEFAULT_FILE: &str = "main.ftl";
+
+/// Translation management systems `TmsClient` syncs with
+///
+/// # Variants
+/// * `Crowdin` - A Crowdin project, by numeric ID, with a personal access token
+/// * `Lokalise` - A Lokalise project, by ID, with an API token
+#[derive(Clone)]
+pub enum TmsProvider {
+    Crowdin { project_id: u64, token: String },
+    Lokalise { project_id: String, token: String },
+}
+
+impl std::fmt::Debug for TmsProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Crowdin { project_id, .. } => f.debug_struct("Crowdin").field("project_id", project_id).finish_non_exhaustive(),
+            Self::Lokalise { project_id, .. } => f.debug_struct("Lokalise").field("project_id", project_id).finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Client syncing a locale directory with Crowdin or Lokalise
+///
+/// `push` uploads the source strings of one locale and `pull` writes the
+/// completed translations back as FTL files, in the same `{dir}/{locale}/`
+/// layout `DirSource` reads. The FTL files of a locale's directory and its
+/// subdirectories are synced, named by their path relative to it. Crowdin
+/// projects get the FTL files themselves, in matching directories, while
+/// Lokalise projects get one key per message and attribute, holding its FTL
+/// text and the path of its file.
+///
+/// # Examples
+/// ```ignore
+/// use i18n::{TmsClient, TmsProvider};
+///
+/// let provider = TmsProvider::Crowdin { project_id: 42, token: std::env::var("CROWDIN_TOKEN")? };
+/// let client = TmsClient::new(provider, "./assets/locales");
+///
+/// client.push("en-US").await?;
+/// client.pull_and_reload(["es-MX", "fr-FR"]).await?;
+/// ```
+#[derive(Debug)]
+pub struct TmsClient {
+    client: reqwest::Client,
+    provider: TmsProvider,
+    dir: PathBuf,
+}
+
+impl TmsClient {
+    /// Creates a client for a provider and a locale directory
+    ///
+    /// # Parameters
+    /// * `provider` - The project to sync with
+    /// * `dir` - Directory holding one subdirectory per locale (e.g., "./assets/locales")
+    pub fn new<T>(provider: TmsProvider, dir: T) -> Self
+    where
+        T: AsRef<Path>,
+    {
+        Self { client: reqwest::Client::new(), provider, dir: dir.as_ref().to_path_buf() }
+    }
+
+    /// Uploads the source strings of a locale
+    ///
+    /// Files or keys already in the project are updated, and new ones are
+    /// added.
+    ///
+    /// # Parameters
+    /// * `locale` - The language identifier of the source strings (e.g., "en-US")
+    ///
+    /// # Return
+    /// Returns an error if `locale` is malformed, its files cannot be read, or
+    /// a request fails
+    pub async fn push<T>(&self, locale: T) -> Result<(), I18nError>
+    where
+        T: ToString,
+    {
+        let locale = parse(locale)?;
+        let files = ftl_files(&self.dir.join(locale.to_string()))?;
+
+        match &self.provider {
+            TmsProvider::Crowdin { project_id, .. } => self.crowdin_push(*project_id, files).await,
+            TmsProvider::Lokalise { project_id, .. } => self.lokalise_push(project_id, &locale, files).await,
+        }
+    }
+
+    /// Downloads the translations of locales and writes them as FTL files
+    ///
+    /// Untranslated strings are left out, so those keys fall back as usual.
+    /// Call `i18n::reload`, or use `pull_and_reload`, to apply them. Nothing
+    /// is written unless every download is valid.
+    ///
+    /// # Parameters
+    /// * `locales` - The language identifiers to download
+    ///
+    /// # Return
+    /// Returns an error if a locale is malformed, a request fails, a
+    /// translation is not valid FTL, a file name would point outside the
+    /// locale's directory, or a file cannot be written
+    pub async fn pull<I, L>(&self, locales: I) -> Result<(), I18nError>
+    where
+        I: IntoIterator<Item = L>,
+        L: ToString,
+    {
+        let locales = locales.into_iter().map(parse).collect::<Result<Vec<_>, _>>()?;
+
+        let files = match &self.provider {
+            TmsProvider::Crowdin { project_id, .. } => self.crowdin_pull(*project_id, &locales).await?,
+            TmsProvider::Lokalise { project_id, .. } => self.lokalise_pull(project_id, &locales).await?,
+        };
+
+        let files = files
+            .into_iter()
+            .map(|(locale, name, ftl)| {
+                let path = file_path(&self.dir.join(locale.to_string()), &name)
+                    .ok_or_else(|| self.error(format!("file name {name:?} in {locale} is not a relative FTL path")))?;
+                Ok((path, ftl))
+            })
+            .collect::<Result<Vec<_>, I18nError>>()?;
+
+        for (path, ftl) in files {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| loader_error(dir, e))?;
+            }
+
+            fs::write(&path, ftl).map_err(|e| loader_error(&path, e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Downloads the translations of locales, then reloads the translations from disk
+    ///
+    /// # Parameters
+    /// * `locales` - The language identifiers to download
+    ///
+    /// # Return
+    /// Returns an error if the download or the reload fails
+    pub async fn pull_and_reload<I, L>(&self, locales: I) -> Result<(), I18nError>
+    where
+        I: IntoIterator<Item = L>,
+        L: ToString,
+    {
+        self.pull(locales).await?;
+        reload()
+    }
+
+    async fn crowdin_push(&self, project_id: u64, files: Vec<(String, String)>) -> Result<(), I18nError> {
+        let existing = self.crowdin_files(project_id).await?;
+        let mut directories = self.crowdin_directories(project_id).await?;
+
+        for (path, source) in files {
+            let (parent, name) = path.rsplit_once('/').map_or((None, path.as_str()), |(parent, name)| (Some(parent), name));
+            let storage = self
+                .send(self.request(Method::POST, "/storages").header("Crowdin-API-FileName", name).body(source))
+                .await?;
+            let storage_id = storage["data"]["id"].as_u64().ok_or_else(|| self.error("missing storage ID"))?;
+
+            let (request, body) = match existing.get(&path) {
+                Some(file_id) => (self.request(Method::PUT, &format!("/projects/{project_id}/files/{file_id}")), json!({ "storageId": storage_id })),
+                None => {
+                    let mut body = json!({ "storageId": storage_id, "name": name });
+                    if let Some(parent) = parent {
+                        body["directoryId"] = self.crowdin_directory(project_id, &mut directories, parent).await?.into();
+                    }
+
+                    (self.request(Method::POST, &format!("/projects/{project_id}/files")), body)
+                }
+            };
+
+            self.send(with_json(request, &body)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ID of a Crowdin directory, by path, creating it and its parents if missing
+    async fn crowdin_directory(&self, project_id: u64, directories: &mut HashMap<String, u64>, path: &str) -> Result<u64, I18nError> {
+        let mut parent = None;
+        let mut current = String::new();
+
+        for name in path.split('/') {
+            if !current.is_empty() {
+                current.push('/');
+            }
+            current.push_str(name);
+
+            let id = match directories.get(&current) {
+                Some(id) => *id,
+                None => {
+                    let mut body = json!({ "name": name });
+                    if let Some(parent) = parent {
+                        body["directoryId"] = json!(parent);
+                    }
+
+                    let created = self
+                        .send(with_json(self.request(Method::POST, &format!("/projects/{project_id}/directories")), &body))
+                        .await?;
+                    let id = created["data"]["id"].as_u64().ok_or_else(|| self.error("missing directory ID"))?;
+                    directories.insert(current.clone(), id);
+                    id
+                }
+            };
+
+            parent = Some(id);
+        }
+
+        parent.ok_or_else(|| self.error("empty directory path"))
+    }
+
+    async fn crowdin_pull(&self, project_id: u64, locales: &[LanguageIdentifier]) -> Result<Vec<(LanguageIdentifier, String, String)>, I18nError> {
+        let files = self.crowdin_files(project_id).await?;
+        let mut pulled = Vec::new();
+
+        for locale in locales {
+            for (name, file_id) in &files {
+                let body = json!({ "targetLanguageId": locale.to_string(), "fileIds": [file_id], "skipUntranslatedStrings": true });
+                let export = self
+                    .send(with_json(self.request(Method::POST, &format!("/projects/{project_id}/translations/exports")), &body))
+                    .await?;
+                let url = export["data"]["url"].as_str().ok_or_else(|| self.error("missing export URL"))?;
+
+                // The export URL is signed, so it takes no token
+                let ftl = self
+                    .client
+                    .get(url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| self.error(e))?
+                    .text()
+                    .await
+                    .map_err(|e| self.error(e))?;
+
+                if let Err((_, errors)) = fluent_syntax::parser::parse(ftl.as_str())
+                    && let Some(error) = errors.first()
+                {
+                    return Err(self.error(format!("file {name} in {locale}: {error}")));
+                }
+
+                pulled.push((locale.clone(), name.clone(), ftl));
+            }
+        }
+
+        Ok(pulled)
+    }
+
+    /// Returns the FTL files of a Crowdin project, by path
+    async fn crowdin_files(&self, project_id: u64) -> Result<HashMap<String, u64>, I18nError> {
+        let files = self.crowdin_paths(project_id, "files").await?;
+
+        Ok(files.into_iter().filter(|(path, _)| path.ends_with(".ftl")).collect())
+    }
+
+    /// Returns the directories of a Crowdin project, by path
+    async fn crowdin_directories(&self, project_id: u64) -> Result<HashMap<String, u64>, I18nError> {
+        self.crowdin_paths(project_id, "directories").await
+    }
+
+    /// Lists the files or directories of a Crowdin project, by path without the leading `/`
+    async fn crowdin_paths(&self, project_id: u64, kind: &str) -> Result<HashMap<String, u64>, I18nError> {
+        let mut paths = HashMap::new();
+
+        for offset in (0..).step_by(PAGE) {
+            let page = self
+                .send(self.request(Method::GET, &format!("/projects/{project_id}/{kind}?limit={PAGE}&offset={offset}")))
+                .await?;
+            let entries = page["data"].as_array().cloned().unwrap_or_default();
+
+            for entry in &entries {
+                let data = &entry["data"];
+                if let (Some(path), Some(id)) = (data["path"].as_str().or_else(|| data["name"].as_str()), data["id"].as_u64()) {
+                    paths.insert(path.trim_start_matches('/').to_string(), id);
+                }
+            }
+
+            if entries.len() < PAGE {
+                break;
+            }
+        }
+
+        Ok(paths)
+    }
+
+    async fn lokalise_push(&self, project_id: &str, locale: &LanguageIdentifier, files: Vec<(String, String)>) -> Result<(), I18nError> {
+        let existing: HashMap<String, u64> = self
+            .lokalise_keys(project_id, false)
+            .await?
+            .iter()
+            .filter_map(|key| Some((key["key_name"]["web"].as_str()?.to_string(), key["key_id"].as_u64()?)))
+            .collect();
+
+        let language = lokalise_language(locale);
+        let mut created = Vec::new();
+        let mut updated = Vec::new();
+
+        for (name, source) in files {
+            for (key, text) in patterns(&source) {
+                let translations = json!([{ "language_iso": language, "translation": text }]);
+
+                match existing.get(&key) {
+                    Some(key_id) => updated.push(json!({ "key_id": key_id, "translations": translations })),
+                    None => created.push(json!({
+                        "key_name": key,
+                        "platforms": ["web"],
+                        "filenames": { "web": name },
+                        "translations": translations,
+                    })),
+                }
+            }
+        }
+
+        let path = format!("/projects/{project_id}/keys");
+        for keys in created.chunks(PAGE) {
+            self.send(with_json(self.request(Method::POST, &path), &json!({ "keys": keys }))).await?;
+        }
+        for keys in updated.chunks(PAGE) {
+            self.send(with_json(self.request(Method::PUT, &path), &json!({ "keys": keys }))).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn lokalise_pull(&self, project_id: &str, locales: &[LanguageIdentifier]) -> Result<Vec<(LanguageIdentifier, String, String)>, I18nError> {
+        let keys = self.lokalise_keys(project_id, true).await?;
+        let mut pulled = Vec::new();
+
+        for locale in locales {
+            let language = lokalise_language(locale);
+            let mut files: BTreeMap<&str, Messages> = BTreeMap::new();
+
+            for key in &keys {
+                let Some(name) = key["key_name"]["web"].as_str().filter(|name| name.split('.').all(keys::is_identifier)) else {
+                    continue;
+                };
+                let file = key["filenames"]["web"].as_str().filter(|file| file.ends_with(".ftl")).unwrap_or(DEFAULT_FILE);
+
+                let translation = key["translations"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .find(|translation| translation["language_iso"] == language.as_str())
+                    .and_then(|translation| translation["translation"].as_str())
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty());
+
+                if let Some(text) = translation {
+                    ftl::check(name, text).map_err(|e| self.error(format!("key {name} in {locale}: {e}")))?;
+                    files.entry(file).or_default().insert(name, text.to_string());
+                }
+            }
+
+            pulled.extend(files.into_iter().map(|(file, messages)| (locale.clone(), file.to_string(), messages.finish())));
+        }
+
+        Ok(pulled)
+    }
+
+    /// Returns the keys of a Lokalise project, with their translations if `translations`
+    async fn lokalise_keys(&self, project_id: &str, translations: bool) -> Result<Vec<Value>, I18nError> {
+        let mut keys = Vec::new();
+
+        for page in 1.. {
+            let path = format!("/projects/{project_id}/keys?include_translations={}&limit={PAGE}&page={page}", u8::from(translations));
+            let entries = self.send(self.request(Method::GET, &path)).await?["keys"].as_array().cloned().unwrap_or_default();
+
+            let count = entries.len();
+            keys.extend(entries);
+
+            if count < PAGE {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Starts an authenticated request to the provider's API
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        match &self.provider {
+            TmsProvider::Crowdin { token, .. } => self.client.request(method, format!("{CROWDIN_API}{path}")).bearer_auth(token),
+            TmsProvider::Lokalise { token, .. } => self.client.request(method, format!("{LOKALISE_API}{path}")).header("X-Api-Token", token),
+        }
+    }
+
+    /// Sends a request, returning its JSON response
+    ///
+    /// Error responses are reported with their body, which explains them.
+    async fn send(&self, request: RequestBuilder) -> Result<Value, I18nError> {
+        let response = request.send().await.map_err(|e| self.error(e))?;
+        let status = response.status();
+        let body = response.text().await.map_err(|e| self.error(e))?;
+
+        if !status.is_success() {
+            return Err(self.error(format!("{status}: {body}")));
+        }
+
+        if body.is_empty() {
+            return Ok(Value::Null);
+        }
+
+        serde_json::from_str(&body).map_err(|e| self.error(e))
+    }
+
+    fn error<T: std::fmt::Display>(&self, error: T) -> I18nError {
+        let provider = match self.provider {
+            TmsProvider::Crowdin { .. } => "Crowdin",
+            TmsProvider::Lokalise { .. } => "Lokalise",
+        };
+
+        I18nError::Loader(format!("{provider}: {error}"))
+    }
+}
+
+fn parse<T: ToString>(locale: T) -> Result<LanguageIdentifier, I18nError> {
+    let locale = locale.to_string();
+
+    locale.parse().map_err(|_| I18nError::InvalidLocale(locale))
+}
+
+fn with_json(request: RequestBuilder, body: &Value) -> RequestBuilder {
+    request.header(CONTENT_TYPE, "application/json").body(body.to_string())
+}
+
+/// Returns the Lokalise code of a locale, which separates subtags with `_`
+fn lokalise_language(locale: &LanguageIdentifier) -> String {
+    locale.to_string().replace('-', "_")
+}
+
+/// Reads the FTL files below `dir`, by path relative to it with `/` separators, sorted
+fn ftl_files(dir: &Path) -> Result<Vec<(String, String)>, I18nError> {
+    let mut paths = Vec::new();
+    collect_files(dir, &["ftl"], &mut paths)?;
+
+    let mut files = Vec::new();
+    for path in paths {
+        let Ok(relative) = path.strip_prefix(dir) else {
+            continue;
+        };
+
+        let name = relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        files.push((name, fs::read_to_string(&path).map_err(|e| loader_error(&path, e))?));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Returns where a downloaded file is written inside a locale's `dir`
+///
+/// Returns `None` unless `name` is a relative `.ftl` path of normal
+/// components separated by `/`, so it cannot point outside `dir`.
+fn file_path(dir: &Path, name: &str) -> Option<PathBuf> {
+    let normal = name
+        .split('/')
+        .all(|part| !part.contains('\\') && matches!(Path::new(part).components().collect::<Vec<_>>().as_slice(), [Component::Normal(_)]));
+
+    (normal && name.ends_with(".ftl")).then(|| name.split('/').fold(dir.to_path_buf(), |path, part| path.join(part)))
+}
+
+/// Returns the FTL text of every message and attribute of a source, keyed by
+/// message ID or `message.attribute`
+fn patterns(source: &str) -> Vec<(String, String)> {
+    let resource = fluent_syntax::parser::parse(source).unwrap_or_else(|(resource, _)| resource);
+    let mut patterns = Vec::new();
+
+    for entry in &resource.body {
+        let Entry::Message(message) = entry else {
+            continue;
+        };
+
+        if let Some(value) = &message.value {
+            patterns.push((message.id.name.to_string(), pattern_source(value)));
+        }
+
+        for attr in &message.attributes {
+            patterns.push((attr_key(message.id.name, attr.id.name), pattern_source(&attr.value)));
+        }
+    }
+
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_key_attributes_by_message() {
+        let source = "hello = Hi\nbutton = Log in\n    .title = Click { $name }\n-term = Acme\n";
+
+        assert_eq!(
+            patterns(source),
+            vec![
+                ("hello".to_string(), "Hi".to_string()),
+                ("button".to_string(), "Log in".to_string()),
+                ("button.title".to_string(), "Click { $name }".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lokalise_separates_subtags_with_underscores() {
+        assert_eq!(lokalise_language(&"es-MX".parse().unwrap()), "es_MX");
+        assert_eq!(lokalise_language(&"fr".parse().unwrap()), "fr");
+    }
+
+    #[test]
+    fn ftl_files_are_sorted_filtered_and_nested() {
+        let dir = std::env::temp_dir().join(format!("i18n-tms-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("main.ftl"), "hello = Hi\n").unwrap();
+        fs::write(dir.join("emails.ftl"), "subject = News\n").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        fs::write(dir.join("nested").join("deep.ftl"), "deep = Deep\n").unwrap();
+
+        let files = ftl_files(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            files.unwrap(),
+            vec![
+                ("emails.ftl".to_string(), "subject = News\n".to_string()),
+                ("main.ftl".to_string(), "hello = Hi\n".to_string()),
+                ("nested/deep.ftl".to_string(), "deep = Deep\n".to_string()),
+            ]
+        );
+        assert!(ftl_files(&dir).is_err());
+    }
+
+    #[test]
+    fn downloaded_files_stay_inside_the_locale_directory() {
+        let dir = Path::new("locales").join("es-MX");
+
+        assert_eq!(file_path(&dir, "main.ftl"), Some(dir.join("main.ftl")));
+        assert_eq!(file_path(&dir, "nested/deep.ftl"), Some(dir.join("nested").join("deep.ftl")));
+
+        for name in ["../main.ftl", "nested/../../main.ftl", "/etc/main.ftl", "nested\\main.ftl", "./main.ftl", "nested//main.ftl", "main.txt", ""] {
+            assert_eq!(file_path(&dir, name), None, "{name}");
+        }
+    }
+}
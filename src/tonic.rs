@@ -0,0 +1,96 @@
+//! Tonic integration resolving the locale of gRPC calls
+//!
+//! gRPC clients choose a language with the `x-locale` metadata key, or with
+//! `accept-language`. Serving through `i18n::tower::LocaleLayer`, which reads
+//! the same keys, makes the locale active for the whole call:
+//!
+//! ```ignore
+//! tonic::transport::Server::builder()
+//!     .layer(i18n::tower::LocaleLayer::new())
+//!     .add_service(GreeterServer::new(MyGreeter))
+//!     .serve(addr)
+//!     .await?;
+//! ```
+//!
+//! Inside the handler, plain `i18n::get` calls and `status` use the caller's
+//! language.
+
+use crate::Locale;
+use crate::tower::LOCALE_HEADER;
+use ::tonic::{Code, Request, Status};
+
+/// Interceptor storing the locale of a gRPC call in its extensions
+///
+/// The locale is taken from the `x-locale` metadata key, then
+/// `accept-language`, and defaults to the active locale. Interceptors run
+/// before the handler rather than around it, so this one cannot make the
+/// locale active; handlers read it back with `locale`, and `LocaleLayer`
+/// scopes the whole call instead. Use it with a generated server's
+/// `with_interceptor`.
+///
+/// # Parameters
+/// * `request` - The incoming call
+///
+/// # Return
+/// Returns the call with its `Locale` stored, never failing
+///
+/// # Examples
+/// ```
+///
+/// let mut request = tonic::Request::new(());
+/// request.metadata_mut().insert("x-locale", "es-MX".parse().unwrap());
+///
+/// let request = i18n::tonic::interceptor(request).unwrap();
+/// assert_eq!(i18n::tonic::locale(&request).get("hello"), "Hola");
+/// ```
+pub fn interceptor(mut request: Request<()>) -> Result<Request<()>, Status> {
+    let locale = from_request(&request);
+    request.extensions_mut().insert(locale);
+
+    Ok(request)
+}
+
+/// Resolves the locale of a gRPC call
+///
+/// Uses the locale stored by `interceptor` or `LocaleLayer` when either is
+/// installed, and resolves it from the call's metadata otherwise.
+///
+/// # Parameters
+/// * `request` - The incoming call
+///
+/// # Return
+/// Returns the caller's locale
+pub fn locale<T>(request: &Request<T>) -> Locale {
+    match request.extensions().get::<Locale>() {
+        Some(locale) => locale.clone(),
+        None => from_request(request),
+    }
+}
+
+/// Creates a gRPC status whose message is translated in the active locale
+///
+/// # Parameters
+/// * `code` - The status code
+/// * `key` - The translation key of the message
+///
+/// # Return
+/// Returns the status, with the message translated using the fallback chain
+///
+/// # Examples
+/// ```
+///
+/// let status = i18n::tonic::status(tonic::Code::NotFound, "hello");
+/// assert_eq!(status.message(), "Hello");
+/// ```
+pub fn status<T>(code: Code, key: T) -> Status
+where
+    T: ToString,
+{
+    Status::new(code, crate::get(key))
+}
+
+fn from_request<T>(request: &Request<T>) -> Locale {
+    let metadata = |name| request.metadata().get(name).and_then(|value| value.to_str().ok());
+
+    Locale(crate::resolve_locale(metadata("accept-language"), None, metadata(LOCALE_HEADER)))
+}
@@ -0,0 +1,115 @@
+//! Tower integration resolving the request locale
+//!
+//! The layer works with any service handling `http::Request`s, so axum, tonic,
+//! and hyper services share one way of scoping lookups to the visitor's
+//! language.
+//!
+//! ```no_run
+//! use http::{Request, Response};
+//! use i18n::tower::LocaleLayer;
+//! use std::convert::Infallible;
+//! use std::future::{Ready, ready};
+//! use std::task::{Context, Poll};
+//! use tower_layer::Layer;
+//! use tower_service::Service;
+//!
+//! struct Hello;
+//!
+//! impl Service<Request<()>> for Hello {
+//!     type Response = Response<String>;
+//!     type Error = Infallible;
+//!     type Future = Ready<Result<Self::Response, Self::Error>>;
+//!
+//!     fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+//!         Poll::Ready(Ok(()))
+//!     }
+//!
+//!     fn call(&mut self, _request: Request<()>) -> Self::Future {
+//!         ready(Ok(Response::new(i18n::get("hello"))))  // Uses the visitor's locale
+//!     }
+//! }
+//!
+//! let service = LocaleLayer::new().layer(Hello);
+//! ```
+
+use crate::Locale;
+use crate::negotiate::{LOCALE_PARAM, cookie_value, query_param};
+use crate::task::{scope, sync_scope};
+use ::http::Request;
+use ::http::header::{ACCEPT_LANGUAGE, COOKIE};
+use ::tower_layer::Layer;
+use ::tower_service::Service;
+use std::task::{Context, Poll};
+use tokio::task::futures::TaskLocalFuture;
+
+/// Name of the header, or gRPC metadata key, selecting a locale explicitly
+pub(crate) const LOCALE_HEADER: &str = "x-locale";
+
+/// Layer making each request's locale active while it is handled
+///
+/// The locale is taken from the `lang` query parameter or the `x-locale`
+/// header, then the `lang` cookie, then the `Accept-Language` header, and
+/// defaults to the active locale. It is stored in the request extensions as a `Locale` and set as the
+/// task-local locale, so every `i18n::get` call inside the service uses it.
+/// With axum, the `Locale` extractor picks up the stored locale.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocaleLayer;
+
+impl LocaleLayer {
+    /// Creates the layer
+    ///
+    /// # Return
+    /// Returns a layer wrapping services in a `LocaleService`
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for LocaleLayer {
+    type Service = LocaleService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LocaleService { inner }
+    }
+}
+
+/// Service scoping lookups to the locale of each request
+///
+/// Created by `LocaleLayer`. Both the call into the inner service and its
+/// response future run with the request's locale active.
+#[derive(Debug, Clone)]
+pub struct LocaleService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for LocaleService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<crate::LanguageIdentifier, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let locale = from_request(&request);
+        request.extensions_mut().insert(locale.clone());
+
+        let future = sync_scope(locale.0.clone(), || self.inner.call(request));
+        scope(locale.0, future)
+    }
+}
+
+fn from_request<B>(request: &Request<B>) -> Locale {
+    let header = |name| request.headers().get(name).and_then(|value| value.to_str().ok());
+
+    let explicit = request.headers().get(LOCALE_HEADER).and_then(|value| value.to_str().ok());
+
+    let query = request.uri().query().and_then(|query| query_param(query, LOCALE_PARAM)).or(explicit);
+    let cookie = header(COOKIE).and_then(|cookie| cookie_value(cookie, LOCALE_PARAM));
+
+    Locale(crate::resolve_locale(header(ACCEPT_LANGUAGE), cookie, query))
+}
@@ -0,0 +1,52 @@
+use crate::source::EXTENSIONS;
+use crate::{I18nError, instance};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::iter;
+use std::path::Path;
+
+/// Handle keeping the locale directory watched
+///
+/// Translations are reloaded whenever a translation file changes, until the
+/// handle is dropped.
+pub struct ReloadWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Watches the locale and override directories and reloads translations when FTL files change
+///
+/// Intended for development, so translators and developers see updates without
+/// restarting. A reload that fails keeps the previous translations, and
+/// entries with syntax errors (e.g. in a file saved mid-edit) are skipped and
+/// reported by `diagnostics`.
+///
+/// # Return
+/// Returns a handle that must be kept alive for as long as watching is desired
+///
+/// # Examples
+/// ```no_run
+///
+/// let _watcher = i18n::watch().unwrap();
+/// // Edits to ./assets/locales/**/*.ftl are now picked up automatically
+/// ```
+pub fn watch() -> Result<ReloadWatcher, I18nError> {
+    let i18n = instance();
+
+    let mut watcher = notify::recommended_watcher(|event: notify::Result<Event>| {
+        if event.is_ok_and(|event| event.paths.iter().any(|path| is_catalog(path))) {
+            let _ = crate::reload();
+        }
+    })
+    .map_err(|e| I18nError::Watch(e.to_string()))?;
+
+    for dir in iter::once(&i18n.config.dir).chain(&i18n.config.overrides) {
+        watcher
+            .watch(Path::new(dir), RecursiveMode::Recursive)
+            .map_err(|e| I18nError::Watch(e.to_string()))?;
+    }
+
+    Ok(ReloadWatcher { _watcher: watcher })
+}
+
+fn is_catalog(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| EXTENSIONS.iter().any(|e| ext == *e))
+}
@@ -0,0 +1,344 @@
+use crate::ftl::{self, Messages};
+use crate::{ERROR_LOCK, I18nError, LanguageIdentifier, instance, locale, negotiate};
+use quick_xml::{Reader, XmlVersion};
+use quick_xml::events::Event;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Name of the `<file>` element holding the messages
+const FILE: &str = "messages";
+
+/// XLIFF versions understood by translation tools
+///
+/// # Variants
+/// * `V1_2` - XLIFF 1.2, read by virtually every tool
+/// * `V2_0` - XLIFF 2.0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum XliffVersion {
+    #[default]
+    V1_2,
+    V2_0,
+}
+
+/// Exports a locale as an XLIFF 1.2 document for translation tools
+///
+/// Equivalent to `export_xliff_with(locale, XliffVersion::V1_2)`.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+///
+/// # Return
+/// Returns the XLIFF document
+///
+/// # Examples
+/// ```
+///
+/// let xliff = i18n::export_xliff("es-MX");
+/// assert!(xliff.contains(r#"
+///       <trans-unit id="hello" xml:space="preserve">
+///         <source>Hello</source>
+///         <target state="translated">Hola</target>
+///       </trans-unit>
+/// "#));
+/// ```
+pub fn export_xliff<T>(locale: T) -> String
+where
+    T: ToString,
+{
+    export_xliff_with(locale, XliffVersion::V1_2)
+}
+
+/// Exports a locale as an XLIFF document for translation tools
+///
+/// Every message and attribute of the default locale becomes a unit, keyed
+/// by message ID or `message.attribute`, with the default locale's text as
+/// the source and the locale's own translation, if any, as the target.
+/// Untranslated units have no target, so tools show them as new. Texts are
+/// kept as FTL, including placeables such as `{ $name }` and selectors, so
+/// `import_xliff` turns the translated document back into FTL without loss.
+/// An invalid language identifier is ignored and the active locale is used
+/// instead.
+///
+/// # Parameters
+/// * `locale` - The language identifier to export (e.g., "es-MX")
+/// * `version` - The XLIFF version to write
+///
+/// # Return
+/// Returns the XLIFF document
+///
+/// # Examples
+/// ```
+///
+/// use i18n::XliffVersion;
+///
+/// let xliff = i18n::export_xliff_with("es-MX", XliffVersion::V2_0);
+/// assert!(xliff.contains(r#"
+///     <unit id="hello">
+///       <segment state="translated">
+///         <source xml:space="preserve">Hello</source>
+///         <target xml:space="preserve">Hola</target>
+///       </segment>
+///     </unit>
+/// "#));
+/// ```
+pub fn export_xliff_with<T>(locale: T, version: XliffVersion) -> String
+where
+    T: ToString,
+{
+    let locale: LanguageIdentifier = locale.to_string().parse().unwrap_or_else(|_| self::locale());
+    let i18n = instance();
+    let catalog = i18n.catalog.read().expect(ERROR_LOCK);
+
+    let installed = |locale: &LanguageIdentifier| {
+        negotiate::matching(&[i18n.alias(locale)], catalog.available())
+            .first()
+            .map(|locale| (*locale).clone())
+    };
+
+    let source = i18n.config.locale.parse().ok().and_then(|default| installed(&default));
+    let target = installed(&locale);
+
+    let sources = source.as_ref().map(|source| catalog.patterns(source)).unwrap_or_default();
+    let targets: BTreeMap<String, String> = target.as_ref().map(|target| catalog.patterns(target)).unwrap_or_default().into_iter().collect();
+
+    let source = source.map_or_else(|| i18n.config.locale.clone(), |source| source.to_string());
+    let target = target.unwrap_or(locale).to_string();
+    let units = sources.iter().map(|(key, text)| (key.as_str(), text.as_str(), targets.get(key).map(String::as_str)));
+
+    match version {
+        XliffVersion::V1_2 => xliff_1_2(&source, &target, units),
+        XliffVersion::V2_0 => xliff_2_0(&source, &target, units),
+    }
+}
+
+fn xliff_1_2<'a>(source: &str, target: &str, units: impl Iterator<Item = (&'a str, &'a str, Option<&'a str>)>) -> String {
+    let mut xliff = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xliff.push_str("<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n");
+    let _ = writeln!(
+        xliff,
+        "  <file original=\"{FILE}\" source-language=\"{}\" target-language=\"{}\" datatype=\"plaintext\">",
+        escape(source),
+        escape(target),
+    );
+    xliff.push_str("    <body>\n");
+
+    for (id, text, translation) in units {
+        let _ = writeln!(xliff, "      <trans-unit id=\"{}\" xml:space=\"preserve\">", escape(id));
+        let _ = writeln!(xliff, "        <source>{}</source>", escape(text));
+        if let Some(translation) = translation {
+            let _ = writeln!(xliff, "        <target state=\"translated\">{}</target>", escape(translation));
+        }
+        xliff.push_str("      </trans-unit>\n");
+    }
+
+    xliff.push_str("    </body>\n  </file>\n</xliff>\n");
+    xliff
+}
+
+fn xliff_2_0<'a>(source: &str, target: &str, units: impl Iterator<Item = (&'a str, &'a str, Option<&'a str>)>) -> String {
+    let mut xliff = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xliff,
+        "<xliff version=\"2.0\" xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" srcLang=\"{}\" trgLang=\"{}\">",
+        escape(source),
+        escape(target),
+    );
+    let _ = writeln!(xliff, "  <file id=\"{FILE}\">");
+
+    for (id, text, translation) in units {
+        let state = if translation.is_some() { "translated" } else { "initial" };
+
+        let _ = writeln!(xliff, "    <unit id=\"{}\">", escape(id));
+        let _ = writeln!(xliff, "      <segment state=\"{state}\">");
+        let _ = writeln!(xliff, "        <source xml:space=\"preserve\">{}</source>", escape(text));
+        if let Some(translation) = translation {
+            let _ = writeln!(xliff, "        <target xml:space=\"preserve\">{}</target>", escape(translation));
+        }
+        xliff.push_str("      </segment>\n    </unit>\n");
+    }
+
+    xliff.push_str("  </file>\n</xliff>\n");
+    xliff
+}
+
+fn escape(text: &str) -> String {
+    quick_xml::escape::escape(text).into_owned()
+}
+
+/// Converts a translated XLIFF 1.2 or 2.0 document back to FTL
+///
+/// The target of every unit becomes a message, or an attribute for units
+/// keyed `message.attribute`, so documents written by `export_xliff` come back
+/// as the FTL of their target language. Units without a target are skipped,
+/// so those keys fall back as usual. Inline markup added by translation tools
+/// is dropped and its text kept.
+///
+/// # Parameters
+/// * `xliff` - The XLIFF document
+///
+/// # Return
+/// Returns the FTL source, or an error if the document is malformed or a
+/// target is not a valid FTL pattern
+///
+/// # Examples
+/// ```
+///
+/// let xliff = r#"<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+///   <file original="messages" source-language="en-US" target-language="fr-FR" datatype="plaintext">
+///     <body>
+///       <trans-unit id="greeting">
+///         <source>Hello, { $name }!</source>
+///         <target>Bonjour, { $name } !</target>
+///       </trans-unit>
+///     </body>
+///   </file>
+/// </xliff>"#;
+///
+/// assert_eq!(i18n::import_xliff(xliff).unwrap(), "greeting = Bonjour, { $name } !\n");
+/// ```
+pub fn import_xliff(xliff: &str) -> Result<String, I18nError> {
+    let mut reader = Reader::from_str(xliff);
+    let mut messages = Messages::default();
+    let mut unit: Option<String> = None;
+    let mut target: Option<String> = None;
+    let mut in_target = false;
+    let mut depth = 0usize;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| I18nError::Loader(format!("XLIFF at byte {}: {e}", reader.error_position())))?;
+
+        match &event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            Event::Eof if depth > 0 => return Err(I18nError::Loader("XLIFF: unexpected end of document".to_string())),
+            _ => {}
+        }
+
+        match event {
+            Event::Start(element) => match element.local_name().as_ref() {
+                "trans-unit" | "unit" => {
+                    let id = element
+                        .try_get_attribute("id")
+                        .ok()
+                        .flatten()
+                        .and_then(|id| id.normalized_value(XmlVersion::Implicit1_0).ok());
+
+                    unit = id.map(|id| id.into_owned());
+                    target = None;
+                }
+                // XLIFF 2.0 units can split their text across segments
+                "target" if unit.is_some() => {
+                    target.get_or_insert_default();
+                    in_target = true;
+                }
+                _ => {}
+            },
+            Event::End(element) => match element.local_name().as_ref() {
+                "trans-unit" | "unit" => {
+                    if let (Some(key), Some(text)) = (unit.take(), target.take()) {
+                        add(&mut messages, &key, text)?;
+                    }
+                }
+                "target" => in_target = false,
+                _ => {}
+            },
+            Event::Text(text) if in_target => target.get_or_insert_default().push_str(&text.xml10_content()),
+            Event::CData(text) if in_target => target.get_or_insert_default().push_str(&text.xml10_content()),
+            Event::GeneralRef(reference) if in_target => {
+                let reference = format!("&{};", &*reference);
+                let text = quick_xml::escape::unescape(&reference).map_err(|e| I18nError::Loader(format!("XLIFF: {e}")))?;
+
+                target.get_or_insert_default().push_str(&text);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(messages.finish())
+}
+
+/// Adds the translated text of a unit to its message, checking it is valid FTL
+fn add(messages: &mut Messages, key: &str, text: String) -> Result<(), I18nError> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    ftl::check(key, &text).map_err(|e| I18nError::Loader(format!("XLIFF unit {key}: {e}")))?;
+    messages.insert(key, text);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::fixtures;
+
+    const EN: &str = "hello = Hello & welcome\nfarewell = Goodbye\nbutton = Log in\n    .title = Click <here>\n";
+    const FR: &str = "hello = Bonjour et bienvenue\nbutton = Connexion\n    .title = Cliquez <ici>\n";
+
+    #[test]
+    fn units_hold_the_default_locale_as_source() {
+        let _guard = fixtures(&[("en-US", EN), ("fr-FR", FR)]);
+
+        assert_eq!(export_xliff("fr-FR"), r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+  <file original="messages" source-language="en-US" target-language="fr-FR" datatype="plaintext">
+    <body>
+      <trans-unit id="button" xml:space="preserve">
+        <source>Log in</source>
+        <target state="translated">Connexion</target>
+      </trans-unit>
+      <trans-unit id="button.title" xml:space="preserve">
+        <source>Click &lt;here&gt;</source>
+        <target state="translated">Cliquez &lt;ici&gt;</target>
+      </trans-unit>
+      <trans-unit id="farewell" xml:space="preserve">
+        <source>Goodbye</source>
+      </trans-unit>
+      <trans-unit id="hello" xml:space="preserve">
+        <source>Hello &amp; welcome</source>
+        <target state="translated">Bonjour et bienvenue</target>
+      </trans-unit>
+    </body>
+  </file>
+</xliff>
+"#);
+    }
+
+    #[test]
+    fn untranslated_units_are_initial_in_xliff_2() {
+        let _guard = fixtures(&[("en-US", EN), ("fr-FR", FR)]);
+
+        let xliff = export_xliff_with("fr-FR", XliffVersion::V2_0);
+        assert!(xliff.contains(r#"srcLang="en-US" trgLang="fr-FR""#));
+        assert!(xliff.contains("<unit id=\"farewell\">\n      <segment state=\"initial\">\n        <source xml:space=\"preserve\">Goodbye</source>\n      </segment>"));
+    }
+
+    #[test]
+    fn exported_documents_import_as_their_target() {
+        let _guard = fixtures(&[("en-US", EN), ("fr-FR", FR)]);
+
+        for version in [XliffVersion::V1_2, XliffVersion::V2_0] {
+            let ftl = import_xliff(&export_xliff_with("fr-FR", version)).unwrap();
+            assert_eq!(ftl, "button = Connexion\n    .title = Cliquez <ici>\nhello = Bonjour et bienvenue\n");
+        }
+    }
+
+    #[test]
+    fn inline_markup_and_segments_keep_their_text() {
+        let xliff = r#"<xliff version="2.0"><file id="f"><unit id="hello"><segment><target>Hi, <pc id="1">{ $name }</pc></target></segment><segment><target>!</target></segment></unit></file></xliff>"#;
+
+        assert_eq!(import_xliff(xliff).unwrap(), "hello = Hi, { $name }!\n");
+    }
+
+    #[test]
+    fn malformed_documents_are_reported() {
+        assert!(import_xliff("<xliff><file>").is_err());
+        assert!(matches!(import_xliff(r#"<xliff><unit id="x"><target>{ $</target></unit></xliff>"#), Err(I18nError::Loader(reason)) if reason.starts_with("XLIFF unit x")));
+    }
+}